@@ -16,6 +16,7 @@ import "@openzeppelin/contracts/utils/ReentrancyGuard.sol";
  * - Relayer fee mechanism (0.1% with minimum)
  * - ZK proof verification for cross-chain validation
  * - Order status tracking (Pending -> Completed)
+ * - Relayer claims: exclusive unlock window, optionally backed by a bond
  * - No timeout refund (as per Phase 1.4 design)
  */
 contract EVMSolanaBridge is Ownable, ReentrancyGuard {
@@ -51,6 +52,9 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
         bytes32 proofHash;          // ZK proof hash for verification
         address completedBy;        // Relayer who completed this order
         uint256 completedAt;        // Block number when completed
+        address claimedBy;          // Relayer holding the latest claim
+        uint256 claimExpiry;        // Block number the claim ends at
+        uint256 claimBond;          // Bonds held for this order, paid to whoever completes it
     }
 
     // ============ State Variables ============
@@ -65,6 +69,10 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
     uint16 public relayerFeeBps;    // Fee in basis points (10 = 0.1%)
     uint256 public minRelayerFee;   // Minimum fee in token units (e.g., 50000 for 0.05 USDC with 6 decimals)
 
+    /// @notice Relayer claim configuration
+    uint64 public claimWindowBlocks; // Blocks a claim keeps an order exclusive to its relayer
+    uint256 public claimBond;        // Native currency a relayer deposits with a claim (0 = no bond)
+
     /// @notice Token configurations: evmToken => TokenConfig
     mapping(address => TokenConfig) public tokenConfigs;
 
@@ -114,6 +122,18 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
 
     event BridgePaused(bool isPaused);
 
+    event OrderClaimed(
+        uint64 indexed orderId,
+        address indexed relayer,
+        uint256 claimExpiry,
+        uint256 bond
+    );
+
+    event ClaimConfigUpdated(
+        uint64 windowBlocks,
+        uint256 bond
+    );
+
     // ============ Errors ============
 
     error BridgeIsPaused();
@@ -123,6 +143,10 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
     error UnauthorizedRelayer();
     error TokenNotRegistered();
     error InsufficientVaultBalance();
+    error ClaimActive();
+    error InvalidBond();
+    error OrderNotFound();
+    error ClaimLapsed();
 
     // ============ Constructor ============
 
@@ -134,6 +158,9 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
         // Phase 1.4: Initialize relayer fee configuration
         relayerFeeBps = 10;         // 0.1% default
         minRelayerFee = 50_000;     // 0.05 USDC (assuming 6 decimals)
+
+        claimWindowBlocks = 20;     // ~4 minutes at 12s blocks
+        claimBond = 0;
     }
 
     // ============ Admin Functions ============
@@ -188,6 +215,20 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
         emit RelayerFeeUpdated(newFeeBps, newMinFee);
     }
 
+    /**
+     * @notice Update relayer claim configuration
+     * @param windowBlocks Blocks a claim keeps an order exclusive
+     * @param bond Native currency deposited with each claim
+     */
+    function setClaimConfig(uint64 windowBlocks, uint256 bond) external onlyOwner {
+        require(windowBlocks > 0, "Empty claim window");
+
+        claimWindowBlocks = windowBlocks;
+        claimBond = bond;
+
+        emit ClaimConfigUpdated(windowBlocks, bond);
+    }
+
     /**
      * @notice Pause or unpause the bridge
      * @param _paused New pause status
@@ -245,6 +286,9 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
         order.proofHash = bytes32(0);
         order.completedBy = address(0);
         order.completedAt = 0;
+        order.claimedBy = address(0);
+        order.claimExpiry = 0;
+        order.claimBond = 0;
         
         emit TokensLocked(
             orderId,
@@ -258,6 +302,36 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
         return orderId;
     }
 
+    // ============ Relayer Functions ============
+
+    /**
+     * @notice Claim an order before proving it, so competing relayers skip it
+     * @param orderId Order ID to claim
+     * @dev The claim is exclusive for claimWindowBlocks, after which anyone may claim or
+     *      unlock the order. The bond is held by the order and paid to whoever completes it,
+     *      so a relayer that lets its claim lapse loses it. A relayer cannot claim an order
+     *      again after letting its own claim lapse.
+     */
+    function claimOrder(uint64 orderId) external payable nonReentrant {
+        if (paused) revert BridgeIsPaused();
+
+        // Pending is the zero value, so orders not locked yet would pass the status check
+        if (orderId == 0 || orderId >= nextOrderId) revert OrderNotFound();
+
+        TransferOrder storage order = transferOrders[orderId];
+        if (order.status != OrderStatus.Pending) revert OrderNotPending();
+        if (isClaimActive(order)) revert ClaimActive();
+        // Re-claiming a lapsed claim would reopen the exclusive window without losing the bond
+        if (order.claimedBy == msg.sender && order.claimExpiry != 0) revert ClaimLapsed();
+        if (msg.value != claimBond) revert InvalidBond();
+
+        order.claimedBy = msg.sender;
+        order.claimExpiry = block.number + claimWindowBlocks;
+        order.claimBond += msg.value;
+
+        emit OrderClaimed(orderId, msg.sender, order.claimExpiry, msg.value);
+    }
+
     /**
      * @notice Unlock tokens after cross-chain transfer is verified
      * @param orderId Order ID to unlock
@@ -272,6 +346,9 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
         
         // Validate order status
         if (order.status != OrderStatus.Pending) revert OrderNotPending();
+
+        // Only the claiming relayer may unlock while its claim is active
+        if (isClaimActive(order) && order.claimedBy != msg.sender) revert ClaimActive();
         
         // Mock ZK proof verification (Phase 1.4 approach)
         // In production, this would verify the actual ZK proof
@@ -301,6 +378,14 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
         // Transfer tokens
         IERC20(order.tokenConfig).safeTransfer(order.user, userAmount);
         IERC20(order.tokenConfig).safeTransfer(msg.sender, relayerReward);
+
+        // Pay out the claim bonds held for this order
+        uint256 bond = order.claimBond;
+        if (bond > 0) {
+            order.claimBond = 0;
+            (bool sent, ) = payable(msg.sender).call{value: bond}("");
+            require(sent, "Bond transfer failed");
+        }
         
         emit TokensUnlocked(
             orderId,
@@ -314,6 +399,14 @@ contract EVMSolanaBridge is Ownable, ReentrancyGuard {
 
     // ============ View Functions ============
 
+    /**
+     * @notice Whether an order is claimed by a relayer whose window has not ended
+     * @param order Transfer order
+     */
+    function isClaimActive(TransferOrder storage order) internal view returns (bool) {
+        return order.claimedBy != address(0) && block.number < order.claimExpiry;
+    }
+
     /**
      * @notice Calculate relayer fee for a given amount
      * @param amount Total amount before fee deduction
@@ -372,6 +372,134 @@ describe("EVMSolanaBridge", function () {
     });
   });
   
+  describe("Claim Order", function () {
+    const LOCK_AMOUNT = ethers.parseUnits("1", DECIMALS);
+    let orderId;
+    let competitor;
+    
+    beforeEach(async function () {
+      competitor = (await ethers.getSigners())[3];
+      await bridge.initializeBridge(SOLANA_CHAIN_ID);
+      await bridge.registerTokenPair(
+        await mockToken.getAddress(),
+        ethers.randomBytes(32),
+        true
+      );
+      await mockToken.connect(user).approve(await bridge.getAddress(), LOCK_AMOUNT);
+      await bridge.connect(user).lockTokens(
+        await mockToken.getAddress(),
+        LOCK_AMOUNT,
+        ethers.randomBytes(32)
+      );
+      orderId = 1n;
+    });
+    
+    it("Should give the claiming relayer an exclusive window", async function () {
+      await expect(bridge.connect(relayer).claimOrder(orderId))
+        .to.emit(bridge, "OrderClaimed");
+      
+      const order = await bridge.getTransferOrder(orderId);
+      const window = await bridge.claimWindowBlocks();
+      expect(order.claimedBy).to.equal(relayer.address);
+      expect(order.claimExpiry).to.equal(BigInt(await ethers.provider.getBlockNumber()) + window);
+      
+      await expect(
+        bridge.connect(competitor).claimOrder(orderId)
+      ).to.be.revertedWithCustomError(bridge, "ClaimActive");
+      await expect(
+        bridge.connect(competitor).unlockTokens(orderId, ethers.randomBytes(32))
+      ).to.be.revertedWithCustomError(bridge, "ClaimActive");
+      
+      await bridge.connect(relayer).unlockTokens(orderId, ethers.randomBytes(32));
+      expect((await bridge.getTransferOrder(orderId)).completedBy).to.equal(relayer.address);
+    });
+    
+    it("Should open the order to anyone once the claim expires", async function () {
+      await bridge.setClaimConfig(2, 0);
+      await bridge.connect(relayer).claimOrder(orderId);
+      await ethers.provider.send("hardhat_mine", ["0x2"]);
+      
+      await bridge.connect(competitor).claimOrder(orderId);
+      expect((await bridge.getTransferOrder(orderId)).claimedBy).to.equal(competitor.address);
+      
+      await ethers.provider.send("hardhat_mine", ["0x2"]);
+      await bridge.connect(relayer).unlockTokens(orderId, ethers.randomBytes(32));
+      expect((await bridge.getTransferOrder(orderId)).completedBy).to.equal(relayer.address);
+    });
+    
+    it("Should not let a relayer re-claim its lapsed claim", async function () {
+      await bridge.setClaimConfig(2, 0);
+      await bridge.connect(relayer).claimOrder(orderId);
+      await ethers.provider.send("hardhat_mine", ["0x2"]);
+      const lapsed = await bridge.getTransferOrder(orderId);
+      
+      await expect(
+        bridge.connect(relayer).claimOrder(orderId)
+      ).to.be.revertedWithCustomError(bridge, "ClaimLapsed");
+      expect((await bridge.getTransferOrder(orderId)).claimExpiry).to.equal(lapsed.claimExpiry);
+      
+      // Another relayer may still take the order over
+      await bridge.connect(competitor).claimOrder(orderId);
+      expect((await bridge.getTransferOrder(orderId)).claimedBy).to.equal(competitor.address);
+    });
+    
+    it("Should pay the bonds to the relayer completing the order", async function () {
+      const bond = ethers.parseEther("0.01");
+      await bridge.setClaimConfig(2, bond);
+      
+      await expect(
+        bridge.connect(relayer).claimOrder(orderId)
+      ).to.be.revertedWithCustomError(bridge, "InvalidBond");
+      await bridge.connect(relayer).claimOrder(orderId, { value: bond });
+      
+      // The claim lapses and a competitor completes the order, collecting the bond
+      await ethers.provider.send("hardhat_mine", ["0x2"]);
+      await expect(
+        bridge.connect(competitor).unlockTokens(orderId, ethers.randomBytes(32))
+      ).to.changeEtherBalance(competitor, bond);
+      expect((await bridge.getTransferOrder(orderId)).claimBond).to.equal(0n);
+    });
+    
+    it("Should not claim completed orders", async function () {
+      await bridge.connect(relayer).unlockTokens(orderId, ethers.randomBytes(32));
+      await expect(
+        bridge.connect(competitor).claimOrder(orderId)
+      ).to.be.revertedWithCustomError(bridge, "OrderNotPending");
+    });
+    
+    it("Should not claim orders that do not exist yet", async function () {
+      const bond = ethers.parseEther("0.01");
+      await bridge.setClaimConfig(100, bond);
+      
+      const futureOrderId = await bridge.nextOrderId();
+      await expect(
+        bridge.connect(competitor).claimOrder(futureOrderId, { value: bond })
+      ).to.be.revertedWithCustomError(bridge, "OrderNotFound");
+      await expect(
+        bridge.connect(competitor).claimOrder(0, { value: bond })
+      ).to.be.revertedWithCustomError(bridge, "OrderNotFound");
+    });
+    
+    it("Should lock new orders without a claim", async function () {
+      await mockToken.connect(user).approve(await bridge.getAddress(), LOCK_AMOUNT);
+      await bridge.connect(user).lockTokens(
+        await mockToken.getAddress(),
+        LOCK_AMOUNT,
+        ethers.randomBytes(32)
+      );
+      
+      const order = await bridge.getTransferOrder(2n);
+      expect(order.user).to.equal(user.address);
+      expect(order.claimedBy).to.equal(ethers.ZeroAddress);
+      expect(order.claimExpiry).to.equal(0n);
+      expect(order.claimBond).to.equal(0n);
+      
+      // Any relayer may claim it right away
+      await bridge.connect(competitor).claimOrder(2n);
+      expect((await bridge.getTransferOrder(2n)).claimedBy).to.equal(competitor.address);
+    });
+  });
+  
   describe("Admin Functions", function () {
     beforeEach(async function () {
       await bridge.initializeBridge(SOLANA_CHAIN_ID);
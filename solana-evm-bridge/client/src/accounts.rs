@@ -0,0 +1,125 @@
+//! Fetching and decoding the bridge accounts.
+//!
+//! The client does not depend on an RPC client; anything that returns raw account data
+//! implements [`AccountFetcher`], for example with `solana-client`:
+//!
+//! ```ignore
+//! let fetcher = |address: &Pubkey| {
+//!     rpc.get_account_with_commitment(address, CommitmentConfig::finalized())
+//!         .map(|response| response.value.map(|account| account.data))
+//!         .map_err(ClientError::fetch)
+//! };
+//! let order = fetch_transfer_order(&fetcher, 42)?;
+//! ```
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use anchor_spl::token::TokenAccount;
+
+use crate::{pda, BridgeConfig, ClientError, RelayerAccount, TokenConfig, TransferOrder};
+
+/// A source of raw account data.
+pub trait AccountFetcher {
+    /// The data of the account at `address`, or `None` if it does not exist.
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError>;
+}
+
+impl<F> AccountFetcher for F
+where
+    F: Fn(&Pubkey) -> Result<Option<Vec<u8>>, ClientError>,
+{
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        self(address)
+    }
+}
+
+/// Check the discriminator and decode an account.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    T::try_deserialize(&mut &data[..]).map_err(ClientError::Decode)
+}
+
+/// Fetch and decode the account at `address`.
+pub fn fetch<T: AccountDeserialize>(
+    fetcher: &impl AccountFetcher,
+    address: &Pubkey,
+) -> Result<Option<T>, ClientError> {
+    fetcher
+        .account_data(address)?
+        .map(|data| decode(&data))
+        .transpose()
+}
+
+pub fn fetch_bridge_config(
+    fetcher: &impl AccountFetcher,
+) -> Result<Option<BridgeConfig>, ClientError> {
+    fetch(fetcher, &pda::bridge_config().0)
+}
+
+pub fn fetch_token_config(
+    fetcher: &impl AccountFetcher,
+    mint: &Pubkey,
+) -> Result<Option<TokenConfig>, ClientError> {
+    fetch(fetcher, &pda::token_config(mint).0)
+}
+
+/// The vault token account of `mint`.
+pub fn fetch_vault(
+    fetcher: &impl AccountFetcher,
+    mint: &Pubkey,
+) -> Result<Option<TokenAccount>, ClientError> {
+    fetch(fetcher, &pda::vault(mint).0)
+}
+
+pub fn fetch_transfer_order(
+    fetcher: &impl AccountFetcher,
+    order_id: u64,
+) -> Result<Option<TransferOrder>, ClientError> {
+    fetch(fetcher, &pda::transfer_order(order_id).0)
+}
+
+pub fn fetch_relayer_account(
+    fetcher: &impl AccountFetcher,
+    authority: &Pubkey,
+) -> Result<Option<RelayerAccount>, ClientError> {
+    fetch(fetcher, &pda::relayer(authority).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use anchor_lang::AccountSerialize;
+
+    use super::*;
+
+    #[test]
+    fn test_fetches_token_config() {
+        let mint = Pubkey::new_unique();
+        let config = TokenConfig {
+            solana_mint: mint,
+            evm_token: [0xbb; 20],
+            is_native_solana: true,
+            total_locked: 42,
+        };
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        // Accounts are allocated with their full space, decoding ignores the rest.
+        data.resize(data.len() + 16, 0);
+
+        let accounts = HashMap::from([(pda::token_config(&mint).0, data.clone())]);
+        let fetcher = |address: &Pubkey| Ok(accounts.get(address).cloned());
+
+        let fetched = fetch_token_config(&fetcher, &mint).unwrap().unwrap();
+        assert_eq!(fetched.solana_mint, mint);
+        assert_eq!(fetched.evm_token, [0xbb; 20]);
+        assert_eq!(fetched.total_locked, 42);
+        assert!(fetch_token_config(&fetcher, &Pubkey::new_unique())
+            .unwrap()
+            .is_none());
+
+        // A token config is not a transfer order.
+        assert!(matches!(
+            decode::<TransferOrder>(&data),
+            Err(ClientError::Decode(_))
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+//! Events of the bridge program, decoded from transaction logs.
+//!
+//! `emit!` writes each event as a `Program data: <base64>` log line. Only lines logged while the
+//! bridge program is the innermost running program are decoded, so data logged by programs it
+//! invokes, or by other programs of the same transaction, is ignored.
+
+use anchor_lang::{prelude::Pubkey, Event};
+use base64::{engine::general_purpose::STANDARD, Engine};
+pub use solana_evm_bridge::{
+    BridgeInitialized, BridgeReconciled, OrderClaimed, RelayerRegistered, RelayerSlashed,
+    RelayerUnbonding, TokensLocked, TokensUnlocked,
+};
+
+use crate::ID;
+
+/// An event emitted by the bridge program.
+pub enum BridgeEvent {
+    BridgeInitialized(BridgeInitialized),
+    TokensLocked(TokensLocked),
+    OrderClaimed(OrderClaimed),
+    RelayerRegistered(RelayerRegistered),
+    RelayerUnbonding(RelayerUnbonding),
+    RelayerSlashed(RelayerSlashed),
+    TokensUnlocked(TokensUnlocked),
+    BridgeReconciled(BridgeReconciled),
+}
+
+fn decode<T: Event>(data: &[u8]) -> Option<T> {
+    let mut body = data.strip_prefix(T::DISCRIMINATOR)?;
+    T::deserialize(&mut body).ok()
+}
+
+impl BridgeEvent {
+    /// Decode an event from its discriminator and Borsh data, `None` for unknown events.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        macro_rules! try_decode {
+            ($($name:ident),*) => {
+                $(
+                    if let Some(event) = decode::<$name>(data) {
+                        return Some(Self::$name(event));
+                    }
+                )*
+            };
+        }
+        try_decode!(
+            BridgeInitialized,
+            TokensLocked,
+            OrderClaimed,
+            RelayerRegistered,
+            RelayerUnbonding,
+            RelayerSlashed,
+            TokensUnlocked,
+            BridgeReconciled
+        );
+        None
+    }
+}
+
+/// The events of the bridge program in the log messages of a transaction, in emission order.
+pub fn parse_logs(logs: &[String]) -> Vec<BridgeEvent> {
+    parse_program_logs(logs, &ID)
+}
+
+/// Like [`parse_logs`], for the bridge program deployed at `program_id`.
+pub fn parse_program_logs(logs: &[String], program_id: &Pubkey) -> Vec<BridgeEvent> {
+    let program = program_id.to_string();
+    let mut invoked: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs {
+        if let Some(data) = line.strip_prefix("Program data: ") {
+            if invoked.last() == Some(&program.as_str()) {
+                events.extend(
+                    STANDARD
+                        .decode(data.trim())
+                        .ok()
+                        .and_then(|data| BridgeEvent::decode(&data)),
+                );
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            // `Program <id> invoke [<depth>]`, `Program <id> success`, `Program <id> failed: ..`
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => invoked.push(id),
+                (Some(_), Some(status)) if status == "success" || status.starts_with("failed") => {
+                    invoked.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::Pubkey;
+
+    use super::*;
+
+    fn data_line(event: &impl Event) -> String {
+        format!("Program data: {}", STANDARD.encode(event.data()))
+    }
+
+    #[test]
+    fn test_parses_events_of_the_bridge_program() {
+        let mint = Pubkey::new_unique();
+        let locked = TokensLocked {
+            order_id: 3,
+            user: Pubkey::new_unique(),
+            amount: 999_000,
+            recipient: [0xcc; 20],
+            relayer_fee: 1_000,
+            slot: 77,
+            mint,
+        };
+        let token_program = anchor_spl::token::ID.to_string();
+        let logs = vec![
+            format!("Program {ID} invoke [1]"),
+            "Program log: Instruction: LockTokens".to_string(),
+            format!("Program {token_program} invoke [2]"),
+            // Logged by the token program, not the bridge.
+            data_line(&locked),
+            format!("Program {token_program} success"),
+            data_line(&locked),
+            "Program log: Tokens locked".to_string(),
+            format!("Program {ID} success"),
+            // Logged outside of the bridge program.
+            data_line(&locked),
+        ];
+
+        let events = parse_logs(&logs);
+        assert_eq!(events.len(), 1);
+        let BridgeEvent::TokensLocked(event) = &events[0] else {
+            panic!("expected TokensLocked");
+        };
+        assert_eq!(event.order_id, 3);
+        assert_eq!(event.amount, 999_000);
+        assert_eq!(event.recipient, [0xcc; 20]);
+        assert_eq!(event.mint, mint);
+    }
+
+    #[test]
+    fn test_parses_events_of_another_deployment() {
+        let program_id = Pubkey::new_unique();
+        let unlocked = TokensUnlocked {
+            order_id: 4,
+            user: Pubkey::new_unique(),
+            amount: 500_000,
+            relayer: Pubkey::new_unique(),
+            relayer_fee: 1_000,
+            slot: 78,
+            mint: Pubkey::new_unique(),
+        };
+        let logs = vec![
+            format!("Program {program_id} invoke [1]"),
+            data_line(&unlocked),
+            format!("Program {program_id} success"),
+        ];
+
+        assert!(parse_logs(&logs).is_empty());
+        let events = parse_program_logs(&logs, &program_id);
+        assert!(matches!(
+            &events[..],
+            [BridgeEvent::TokensUnlocked(event)] if event.order_id == 4
+        ));
+    }
+
+    #[test]
+    fn test_ignores_unknown_data() {
+        assert!(BridgeEvent::decode(&[0; 16]).is_none());
+        let logs = vec![
+            format!("Program {ID} invoke [1]"),
+            "Program data: not base64!".to_string(),
+            format!("Program {ID} failed: custom program error: 0x1771"),
+        ];
+        assert!(parse_logs(&logs).is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! Typed builders of the bridge instructions.
+//!
+//! Each builder lists the accounts a caller has to choose; the program derived addresses and
+//! program ids are filled in. The account order and instruction data come from the program's
+//! generated `accounts` and `instruction` modules.
+
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, system_program, InstructionData,
+    ToAccountMetas,
+};
+use anchor_spl::token;
+use solana_evm_bridge::{accounts, instruction};
+
+use crate::{pda, ID};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `initialize_bridge`: create the `BridgeConfig` with `admin` as its admin.
+pub struct InitializeBridge {
+    pub admin: Pubkey,
+    pub evm_chain_id: u64,
+}
+
+impl InitializeBridge {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::InitializeBridge {
+                bridge_config: pda::bridge_config().0,
+                admin: self.admin,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeBridge {
+                evm_chain_id: self.evm_chain_id,
+            },
+        )
+    }
+}
+
+/// `register_token_pair`: pair `mint` with the ERC-20 `evm_token`.
+pub struct RegisterTokenPair {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub evm_token: [u8; 20],
+    pub is_native_solana: bool,
+}
+
+impl RegisterTokenPair {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::RegisterTokenPair {
+                token_config: pda::token_config(&self.mint).0,
+                solana_mint: self.mint,
+                admin: self.admin,
+                system_program: system_program::ID,
+            },
+            instruction::RegisterTokenPair {
+                evm_token: self.evm_token,
+                is_native_solana: self.is_native_solana,
+            },
+        )
+    }
+}
+
+/// `initialize_vault`: create the token account holding the locked tokens of `mint`.
+pub struct InitializeVault {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+}
+
+impl InitializeVault {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::InitializeVault {
+                vault: pda::vault(&self.mint).0,
+                solana_mint: self.mint,
+                admin: self.admin,
+                token_program: token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeVault {},
+        )
+    }
+}
+
+/// `lock_tokens`: lock `amount` of `mint` from `user_token_account` for `recipient_evm`.
+pub struct LockTokens {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub user_token_account: Pubkey,
+    /// The `next_order_id` of the `BridgeConfig`, which becomes the id of the new order.
+    pub order_id: u64,
+    pub amount: u64,
+    pub recipient_evm: [u8; 20],
+}
+
+impl LockTokens {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::LockTokens {
+                order: pda::transfer_order(self.order_id).0,
+                bridge_config: pda::bridge_config().0,
+                token_config: pda::token_config(&self.mint).0,
+                user: self.user,
+                user_token_account: self.user_token_account,
+                vault: pda::vault(&self.mint).0,
+                token_program: token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::LockTokens {
+                amount: self.amount,
+                recipient_evm: self.recipient_evm,
+            },
+        )
+    }
+}
+
+/// `unlock_tokens`: release order `order_id` of `mint` to the user, paying the fee to the
+/// relayer.
+pub struct UnlockTokens {
+    pub relayer: Pubkey,
+    pub order_id: u64,
+    pub mint: Pubkey,
+    /// Token account of the order's user.
+    pub user_token_account: Pubkey,
+    /// Token account of the relayer receiving the fee.
+    pub relayer_reward_account: Pubkey,
+    pub proof_hash: [u8; 32],
+    /// Pass the relayer's `RelayerAccount`, required when the bridge only accepts registered
+    /// relayers.
+    pub registered_relayer: bool,
+}
+
+impl UnlockTokens {
+    pub fn instruction(&self) -> Instruction {
+        build(
+            accounts::UnlockTokens {
+                order: pda::transfer_order(self.order_id).0,
+                bridge_config: pda::bridge_config().0,
+                token_config: pda::token_config(&self.mint).0,
+                user_token_account: self.user_token_account,
+                vault: pda::vault(&self.mint).0,
+                relayer: self.relayer,
+                relayer_reward_account: self.relayer_reward_account,
+                relayer_account: self
+                    .registered_relayer
+                    .then(|| pda::relayer(&self.relayer).0),
+                token_program: token::ID,
+            },
+            instruction::UnlockTokens {
+                order_id: self.order_id,
+                proof_hash: self.proof_hash,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    #[test]
+    fn test_lock_tokens() {
+        let user = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let user_token_account = Pubkey::new_unique();
+        let ix = LockTokens {
+            user,
+            mint,
+            user_token_account,
+            order_id: 7,
+            amount: 1_000_000,
+            recipient_evm: [0xaa; 20],
+        }
+        .instruction();
+
+        assert_eq!(ix.program_id, ID);
+        let addresses: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            addresses,
+            [
+                pda::transfer_order(7).0,
+                pda::bridge_config().0,
+                pda::token_config(&mint).0,
+                user,
+                user_token_account,
+                pda::vault(&mint).0,
+                token::ID,
+                system_program::ID,
+            ]
+        );
+        assert!(ix.accounts[3].is_signer);
+
+        let (discriminator, args) = ix.data.split_at(8);
+        assert_eq!(discriminator, instruction::LockTokens::DISCRIMINATOR);
+        assert_eq!(args[..8], 1_000_000u64.to_le_bytes());
+        assert_eq!(args[8..], [0xaa; 20]);
+    }
+
+    #[test]
+    fn test_unlock_tokens_relayer_account() {
+        let unlock = |registered_relayer| UnlockTokens {
+            relayer: Pubkey::new_unique(),
+            order_id: 1,
+            mint: Pubkey::new_unique(),
+            user_token_account: Pubkey::new_unique(),
+            relayer_reward_account: Pubkey::new_unique(),
+            proof_hash: [1; 32],
+            registered_relayer,
+        };
+
+        let registered = unlock(true);
+        let ix = registered.instruction();
+        assert_eq!(ix.accounts[7].pubkey, pda::relayer(&registered.relayer).0);
+
+        // Anchor passes the program id for an omitted optional account.
+        let ix = unlock(false).instruction();
+        assert_eq!(ix.accounts[7].pubkey, ID);
+    }
+}
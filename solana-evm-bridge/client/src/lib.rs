@@ -0,0 +1,53 @@
+//! Rust client for the `solana-evm-bridge` program.
+//!
+//! Builds on the program's own account, instruction and event types, so seeds and layouts are
+//! defined in one place:
+//!
+//! - [`pda`]: addresses of the `bridge_config`, `token_config`, `vault`, `transfer_order` and
+//!   `relayer` accounts.
+//! - [`instructions`]: typed builders for `initialize_bridge`, `register_token_pair`,
+//!   `initialize_vault`, `lock_tokens` and `unlock_tokens`.
+//! - [`accounts`]: fetching and decoding the program accounts through any RPC client.
+//! - [`events`]: events decoded from transaction logs.
+//!
+//! The `sp1-bridge-prover` host tooling decodes the accounts and events of the program through this
+//! crate. Deployments at another address than [`ID`] are served by [`events::parse_program_logs`]
+//! and [`pda::program_vault`].
+
+pub mod accounts;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+
+pub use solana_evm_bridge::{
+    BridgeConfig, OrderStatus, RelayerAccount, RelayerStatus, TokenConfig, TransferOrder, ID,
+};
+
+use std::fmt;
+
+/// Errors of the client.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The RPC client failed to read an account.
+    Fetch(Box<dyn std::error::Error + Send + Sync>),
+    /// The account data is not the expected account type.
+    Decode(anchor_lang::error::Error),
+}
+
+impl ClientError {
+    /// Wrap an error of the RPC client.
+    pub fn fetch(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Fetch(error.into())
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fetch(e) => write!(f, "failed to fetch account: {e}"),
+            Self::Decode(e) => write!(f, "failed to decode account: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
@@ -0,0 +1,59 @@
+//! Program derived addresses of the bridge accounts, with their bumps.
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::ID;
+
+/// The singleton `BridgeConfig`.
+pub fn bridge_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bridge_config"], &ID)
+}
+
+/// The `TokenConfig` of `mint`.
+pub fn token_config(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"token_config", mint.as_ref()], &ID)
+}
+
+/// The token account holding the locked tokens of `mint`, which is also its own authority.
+pub fn vault(mint: &Pubkey) -> (Pubkey, u8) {
+    program_vault(mint, &ID)
+}
+
+/// Like [`vault`], for the bridge program deployed at `program_id`.
+pub fn program_vault(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", mint.as_ref()], program_id)
+}
+
+/// The `TransferOrder` with id `order_id`.
+pub fn transfer_order(order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"transfer_order", &order_id.to_le_bytes()], &ID)
+}
+
+/// The `RelayerAccount` of the relayer signing with `authority`.
+pub fn relayer(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"relayer", authority.as_ref()], &ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_address() {
+        let (vault, bump) = vault(&Pubkey::new_from_array([0x44; 32]));
+        assert_eq!(
+            vault.to_string(),
+            "AG6BH3cwjyTTA529i15q6gCtPBLywhzov4mxKSTcKN2K"
+        );
+        assert_eq!(bump, 253);
+    }
+
+    #[test]
+    fn test_order_ids_are_little_endian() {
+        let (order, _) = transfer_order(1);
+        let (expected, _) =
+            Pubkey::find_program_address(&[b"transfer_order", &[1, 0, 0, 0, 0, 0, 0, 0]], &ID);
+        assert_eq!(order, expected);
+        assert_ne!(order, transfer_order(2).0);
+    }
+}
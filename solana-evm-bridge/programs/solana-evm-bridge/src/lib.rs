@@ -21,6 +21,13 @@ pub mod solana_evm_bridge {
         bridge_config.relayer_fee_bps = 10;          // 0.1% default
         bridge_config.min_relayer_fee = 50_000;      // 0.05 USDC (6 decimals)
         
+        // Relayer claims: exclusive window, no bond by default
+        bridge_config.claim_window_slots = 150;      // ~60 seconds
+        bridge_config.claim_bond = 0;
+        
+        // Any relayer may unlock until the registry is enforced
+        bridge_config.require_registered_relayer = false;
+        
         emit!(BridgeInitialized {
             admin: bridge_config.admin,
             evm_chain_id,
@@ -31,6 +38,21 @@ pub mod solana_evm_bridge {
         Ok(())
     }
 
+    pub fn set_claim_config(
+        ctx: Context<SetClaimConfig>,
+        window_slots: u64,
+        bond: u64,
+    ) -> Result<()> {
+        require!(window_slots > 0, BridgeError::InvalidClaimWindow);
+        
+        let bridge_config = &mut ctx.accounts.bridge_config;
+        bridge_config.claim_window_slots = window_slots;
+        bridge_config.claim_bond = bond;
+        
+        msg!("Claim window: {} slots, bond: {} lamports", window_slots, bond);
+        Ok(())
+    }
+
     pub fn register_token_pair(
         ctx: Context<RegisterTokenPair>,
         evm_token: [u8; 20],
@@ -98,6 +120,9 @@ pub mod solana_evm_bridge {
         // Phase 1.4: Initialize relayer tracking fields
         order.completed_by = Pubkey::default();
         order.completed_at = 0;
+        order.claimed_by = Pubkey::default();
+        order.claim_expiry = 0;
+        order.claim_bond = 0;
         
         token_config.total_locked = token_config.total_locked.checked_add(amount_to_lock).unwrap();
         bridge_config.next_order_id = bridge_config.next_order_id.checked_add(1).unwrap();
@@ -109,18 +134,303 @@ pub mod solana_evm_bridge {
             recipient: recipient_evm,
             relayer_fee,
             slot: clock.slot,
+            mint: token_config.solana_mint,
         });
         
         msg!("Tokens locked");
         Ok(())
     }
     
+    pub fn initialize_registry(
+        ctx: Context<InitializeRegistry>,
+        min_stake: u64,
+        unbonding_slots: u64,
+        slash_bps: u16,
+        jail_slots: u64,
+    ) -> Result<()> {
+        require!(slash_bps <= 10000, BridgeError::InvalidSlashRate);
+        
+        let registry = &mut ctx.accounts.registry;
+        registry.stake_mint = ctx.accounts.stake_mint.key();
+        registry.min_stake = min_stake;
+        registry.unbonding_slots = unbonding_slots;
+        registry.slash_bps = slash_bps;
+        registry.jail_slots = jail_slots;
+        registry.total_staked = 0;
+        registry.relayer_count = 0;
+        
+        msg!("Relayer registry initialized, min stake: {}", min_stake);
+        Ok(())
+    }
+    
+    /// Require a registered, active relayer for `claim_order` and `unlock_tokens`.
+    pub fn set_relayer_requirement(
+        ctx: Context<SetRelayerRequirement>,
+        required: bool,
+    ) -> Result<()> {
+        ctx.accounts.bridge_config.require_registered_relayer = required;
+        
+        msg!("Registered relayer required: {}", required);
+        Ok(())
+    }
+    
+    pub fn register_relayer(ctx: Context<RegisterRelayer>, stake: u64) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        require!(stake >= registry.min_stake, BridgeError::InsufficientStake);
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.stake_source.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            stake,
+        )?;
+        
+        let clock = Clock::get()?;
+        let relayer = &mut ctx.accounts.relayer_account;
+        relayer.authority = ctx.accounts.authority.key();
+        relayer.stake = stake;
+        relayer.status = RelayerStatus::Active;
+        relayer.jailed_until = 0;
+        relayer.unbonding_at = 0;
+        relayer.slash_count = 0;
+        relayer.registered_at = clock.slot;
+        relayer.claimed_until = 0;
+        
+        registry.total_staked = registry.total_staked.checked_add(stake).unwrap();
+        registry.relayer_count = registry.relayer_count.checked_add(1).unwrap();
+        
+        emit!(RelayerRegistered {
+            relayer: relayer.authority,
+            stake,
+            slot: clock.slot,
+        });
+        
+        msg!("Relayer registered with stake {}", stake);
+        Ok(())
+    }
+    
+    /// Start the unbonding delay. The relayer stops relaying but can still be slashed for claims
+    /// it missed until it withdraws. The delay also runs from the expiry of its last claim, so a
+    /// claim it lets lapse can be reported before the stake leaves.
+    pub fn request_unbond(ctx: Context<RequestUnbond>) -> Result<()> {
+        let relayer = &mut ctx.accounts.relayer_account;
+        require!(
+            relayer.status != RelayerStatus::Unbonding,
+            BridgeError::RelayerNotActive
+        );
+        
+        let clock = Clock::get()?;
+        relayer.status = RelayerStatus::Unbonding;
+        relayer.unbonding_at = clock.slot;
+        
+        emit!(RelayerUnbonding {
+            relayer: relayer.authority,
+            stake: relayer.stake,
+            slot: clock.slot,
+        });
+        
+        msg!("Relayer unbonding");
+        Ok(())
+    }
+    
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        let relayer = &ctx.accounts.relayer_account;
+        let clock = Clock::get()?;
+        
+        require!(
+            relayer.status == RelayerStatus::Unbonding,
+            BridgeError::UnbondingNotFinished
+        );
+        require!(
+            clock.slot >= relayer.unbonding_at.checked_add(registry.unbonding_slots).unwrap(),
+            BridgeError::UnbondingNotFinished
+        );
+        require!(
+            clock.slot >= relayer.claimed_until.checked_add(registry.unbonding_slots).unwrap(),
+            BridgeError::UnbondingNotFinished
+        );
+        
+        let stake = relayer.stake;
+        let vault_seeds = &[b"stake_vault".as_ref(), &[ctx.bumps.stake_vault]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.stake_destination.to_account_info(),
+                    authority: ctx.accounts.stake_vault.to_account_info(),
+                },
+                &[&vault_seeds[..]],
+            ),
+            stake,
+        )?;
+        
+        registry.total_staked = registry.total_staked.checked_sub(stake).unwrap();
+        registry.relayer_count = registry.relayer_count.checked_sub(1).unwrap();
+        
+        msg!("Relayer withdrew stake {}", stake);
+        Ok(())
+    }
+    
+    /// Slash a relayer that claimed an order and let the claim expire without completing it.
+    /// Anyone may report; the slashed stake goes to the reporter and the relayer is jailed.
+    /// The claim is cleared, so it cannot be reported twice.
+    pub fn slash_relayer(ctx: Context<SlashRelayer>, order_id: u64) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        let relayer = &mut ctx.accounts.relayer_account;
+        let order = &mut ctx.accounts.order;
+        let clock = Clock::get()?;
+        
+        require!(order.order_id == order_id, BridgeError::OrderNotFound);
+        require!(
+            order.claimed_by == relayer.authority,
+            BridgeError::NotSlashable
+        );
+        require!(clock.slot >= order.claim_expiry, BridgeError::NotSlashable);
+        require!(
+            order.status == OrderStatus::Pending || order.completed_by != relayer.authority,
+            BridgeError::NotSlashable
+        );
+        
+        let slashed = (relayer.stake as u128)
+            .checked_mul(registry.slash_bps as u128)
+            .unwrap()
+            .checked_div(10000)
+            .unwrap() as u64;
+        
+        if slashed > 0 {
+            let vault_seeds = &[b"stake_vault".as_ref(), &[ctx.bumps.stake_vault]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.stake_vault.to_account_info(),
+                        to: ctx.accounts.reporter_token_account.to_account_info(),
+                        authority: ctx.accounts.stake_vault.to_account_info(),
+                    },
+                    &[&vault_seeds[..]],
+                ),
+                slashed,
+            )?;
+        }
+        
+        relayer.stake = relayer.stake.checked_sub(slashed).unwrap();
+        relayer.slash_count = relayer.slash_count.checked_add(1).unwrap();
+        relayer.jailed_until = clock.slot.checked_add(registry.jail_slots).unwrap();
+        if relayer.status == RelayerStatus::Active {
+            relayer.status = RelayerStatus::Jailed;
+        }
+        registry.total_staked = registry.total_staked.checked_sub(slashed).unwrap();
+        
+        order.claimed_by = Pubkey::default();
+        order.claim_expiry = 0;
+        
+        emit!(RelayerSlashed {
+            relayer: relayer.authority,
+            order_id,
+            amount: slashed,
+            reporter: ctx.accounts.reporter.key(),
+            jailed_until: relayer.jailed_until,
+        });
+        
+        msg!("Relayer slashed {} for order {}", slashed, order_id);
+        Ok(())
+    }
+    
+    pub fn unjail(ctx: Context<Unjail>) -> Result<()> {
+        let registry = &ctx.accounts.registry;
+        let relayer = &mut ctx.accounts.relayer_account;
+        let clock = Clock::get()?;
+        
+        require!(
+            relayer.status == RelayerStatus::Jailed,
+            BridgeError::RelayerNotJailed
+        );
+        require!(clock.slot >= relayer.jailed_until, BridgeError::StillJailed);
+        require!(relayer.stake >= registry.min_stake, BridgeError::InsufficientStake);
+        
+        relayer.status = RelayerStatus::Active;
+        
+        msg!("Relayer unjailed");
+        Ok(())
+    }
+    
+    /// Claim an order before proving it, so competing relayers skip it. The claim is exclusive
+    /// for `claim_window_slots`, after which anyone may claim or unlock the order. The bond is
+    /// held by the order account and paid to whoever completes the order.
+    /// A relayer cannot claim an order again after letting its own claim lapse.
+    pub fn claim_order(ctx: Context<ClaimOrder>, order_id: u64) -> Result<()> {
+        require!(!ctx.accounts.bridge_config.paused, BridgeError::BridgePaused);
+        check_relayer(
+            &ctx.accounts.bridge_config,
+            &ctx.accounts.relayer_account,
+        )?;
+        
+        let order = &mut ctx.accounts.order;
+        let bridge_config = &ctx.accounts.bridge_config;
+        let clock = Clock::get()?;
+        
+        require!(order.order_id == order_id, BridgeError::OrderNotFound);
+        require!(
+            !order.is_claim_active(clock.slot),
+            BridgeError::ClaimActive
+        );
+        // Re-claiming a lapsed claim would push the expiry forward and escape `slash_relayer`
+        require!(
+            order.claimed_by != ctx.accounts.relayer.key(),
+            BridgeError::ClaimLapsed
+        );
+        
+        let bond = bridge_config.claim_bond;
+        if bond > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.relayer.to_account_info(),
+                        to: order.to_account_info(),
+                    },
+                ),
+                bond,
+            )?;
+        }
+        
+        order.claimed_by = ctx.accounts.relayer.key();
+        order.claim_expiry = clock.slot.checked_add(bridge_config.claim_window_slots).unwrap();
+        order.claim_bond = order.claim_bond.checked_add(bond).unwrap();
+        
+        // Keeps the stake slashable until a full unbonding delay after the claim expires
+        if let Some(relayer_account) = ctx.accounts.relayer_account.as_mut() {
+            relayer_account.claimed_until = relayer_account.claimed_until.max(order.claim_expiry);
+        }
+        
+        emit!(OrderClaimed {
+            order_id,
+            relayer: order.claimed_by,
+            claim_expiry: order.claim_expiry,
+            bond,
+        });
+        
+        msg!("Order claimed until slot {}", order.claim_expiry);
+        Ok(())
+    }
+    
     pub fn unlock_tokens(
         ctx: Context<UnlockTokens>,
         order_id: u64,
         proof_hash: [u8; 32],
     ) -> Result<()> {
         require!(!ctx.accounts.bridge_config.paused, BridgeError::BridgePaused);
+        check_relayer(
+            &ctx.accounts.bridge_config,
+            &ctx.accounts.relayer_account,
+        )?;
         
         let order = &mut ctx.accounts.order;
         let token_config = &mut ctx.accounts.token_config;
@@ -136,6 +446,12 @@ pub mod solana_evm_bridge {
         // Verify order_id matches
         require!(order.order_id == order_id, BridgeError::OrderNotFound);
         
+        // Only the claiming relayer may unlock while its claim is active
+        require!(
+            !order.is_claim_active(clock.slot) || order.claimed_by == ctx.accounts.relayer.key(),
+            BridgeError::ClaimActive
+        );
+        
         // Phase 1.4: Verify ZK proof (mock verification for now, Phase 6 will use real SP1)
         require!(
             proof_hash != [0u8; 32],
@@ -198,6 +514,14 @@ pub mod solana_evm_bridge {
         order.completed_at = clock.slot;
         order.proof_hash = proof_hash;
         
+        // Pay out the claim bonds held by the order account
+        let bond = order.claim_bond;
+        if bond > 0 {
+            order.claim_bond = 0;
+            **order.to_account_info().try_borrow_mut_lamports()? -= bond;
+            **ctx.accounts.relayer.to_account_info().try_borrow_mut_lamports()? += bond;
+        }
+        
         // Update token config
         token_config.total_locked = token_config.total_locked.checked_sub(total_amount).unwrap();
         
@@ -208,11 +532,81 @@ pub mod solana_evm_bridge {
             relayer: ctx.accounts.relayer.key(),
             relayer_fee,
             slot: clock.slot,
+            mint: token_config.solana_mint,
         });
         
         msg!("Tokens unlocked successfully");
         Ok(())
     }
+    
+    /// Compare the vault balance with `total_locked` and with the pending orders passed as
+    /// remaining accounts, in increasing order id, and emit the result. Nothing is modified.
+    pub fn reconcile<'info>(ctx: Context<'_, '_, 'info, 'info, Reconcile<'info>>) -> Result<()> {
+        let token_config = &ctx.accounts.token_config;
+        let vault_balance = ctx.accounts.vault.amount;
+        
+        let mut outstanding: u64 = 0;
+        let mut pending_orders: u32 = 0;
+        let mut last_order_id: Option<u64> = None;
+        for info in ctx.remaining_accounts.iter() {
+            let order: Account<TransferOrder> = Account::try_from(info)?;
+            require!(
+                order.token_config == token_config.key(),
+                BridgeError::TokenConfigMismatch
+            );
+            // Increasing ids rule out counting an order twice
+            require!(
+                last_order_id < Some(order.order_id),
+                BridgeError::OrdersNotSorted
+            );
+            last_order_id = Some(order.order_id);
+            
+            if order.status == OrderStatus::Pending {
+                outstanding = outstanding.checked_add(order.amount).unwrap();
+                pending_orders = pending_orders.checked_add(1).unwrap();
+            }
+        }
+        
+        let clock = Clock::get()?;
+        emit!(BridgeReconciled {
+            mint: token_config.solana_mint,
+            vault_balance,
+            total_locked: token_config.total_locked,
+            outstanding,
+            pending_orders,
+            balanced: vault_balance == token_config.total_locked
+                && outstanding == token_config.total_locked,
+            slot: clock.slot,
+        });
+        
+        msg!(
+            "Reconciled vault {} against total locked {} and {} pending order(s) holding {}",
+            vault_balance,
+            token_config.total_locked,
+            pending_orders,
+            outstanding
+        );
+        Ok(())
+    }
+}
+
+// Require an active registered relayer when the registry is enforced. The account is derived from
+// the signing relayer's key, so its presence proves the registration.
+fn check_relayer(
+    bridge_config: &BridgeConfig,
+    relayer_account: &Option<Account<RelayerAccount>>,
+) -> Result<()> {
+    if !bridge_config.require_registered_relayer {
+        return Ok(());
+    }
+    let relayer = relayer_account
+        .as_ref()
+        .ok_or(BridgeError::RelayerNotRegistered)?;
+    require!(
+        relayer.status == RelayerStatus::Active,
+        BridgeError::RelayerNotActive
+    );
+    Ok(())
 }
 
 // Helper function to calculate relayer fee
@@ -233,7 +627,7 @@ pub struct InitializeBridge<'info> {
     #[account(
         init,
         payer = admin,
-        space = 8 + 32 + 8 + 1 + 8 + 2 + 8,
+        space = 8 + 32 + 8 + 1 + 8 + 2 + 8 + 8 + 8 + 1,
         seeds = [b"bridge_config"],
         bump
     )]
@@ -245,6 +639,235 @@ pub struct InitializeBridge<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetClaimConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_config"],
+        bump,
+        has_one = admin
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + 8 + 8 + 2 + 8 + 8 + 8,
+        seeds = [b"relayer_registry"],
+        bump
+    )]
+    pub registry: Account<'info, RelayerRegistry>,
+    
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = stake_vault,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    pub stake_mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [b"bridge_config"],
+        bump,
+        has_one = admin
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRelayerRequirement<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_config"],
+        bump,
+        has_one = admin
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"relayer_registry"],
+        bump
+    )]
+    pub registry: Account<'info, RelayerRegistry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 8 + 1 + 8 + 8 + 4 + 8 + 8,
+        seeds = [b"relayer", authority.key().as_ref()],
+        bump
+    )]
+    pub relayer_account: Account<'info, RelayerAccount>,
+    
+    #[account(
+        mut,
+        constraint = stake_source.owner == authority.key(),
+        constraint = stake_source.mint == registry.stake_mint
+    )]
+    pub stake_source: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnbond<'info> {
+    #[account(
+        mut,
+        seeds = [b"relayer", authority.key().as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub relayer_account: Account<'info, RelayerAccount>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"relayer_registry"],
+        bump
+    )]
+    pub registry: Account<'info, RelayerRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer", authority.key().as_ref()],
+        bump,
+        has_one = authority,
+        close = authority
+    )]
+    pub relayer_account: Account<'info, RelayerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = stake_destination.owner == authority.key(),
+        constraint = stake_destination.mint == registry.stake_mint
+    )]
+    pub stake_destination: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct SlashRelayer<'info> {
+    #[account(
+        mut,
+        seeds = [b"relayer_registry"],
+        bump
+    )]
+    pub registry: Account<'info, RelayerRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer", relayer_account.authority.as_ref()],
+        bump
+    )]
+    pub relayer_account: Account<'info, RelayerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"transfer_order", order_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub order: Account<'info, TransferOrder>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake_vault"],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = reporter_token_account.mint == registry.stake_mint
+    )]
+    pub reporter_token_account: Account<'info, TokenAccount>,
+    
+    pub reporter: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Unjail<'info> {
+    #[account(
+        seeds = [b"relayer_registry"],
+        bump
+    )]
+    pub registry: Account<'info, RelayerRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer", authority.key().as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub relayer_account: Account<'info, RelayerAccount>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Reconcile<'info> {
+    #[account(
+        seeds = [b"token_config", token_config.solana_mint.as_ref()],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        seeds = [b"vault", token_config.solana_mint.as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+}
+
 #[derive(Accounts)]
 pub struct RegisterTokenPair<'info> {
     #[account(
@@ -291,7 +914,7 @@ pub struct LockTokens<'info> {
     #[account(
         init,
         payer = user,
-        space = 8 + 8 + 32 + 1 + 32 + 32 + 8 + 20 + 8 + 8 + 8 + 32 + 32 + 8,
+        space = 8 + 8 + 32 + 1 + 32 + 32 + 8 + 20 + 8 + 8 + 8 + 32 + 32 + 8 + 32 + 8 + 8,
         seeds = [b"transfer_order", bridge_config.next_order_id.to_le_bytes().as_ref()],
         bump
     )]
@@ -326,7 +949,7 @@ pub struct LockTokens<'info> {
 
 #[derive(Accounts)]
 #[instruction(order_id: u64)]
-pub struct UnlockTokens<'info> {
+pub struct ClaimOrder<'info> {
     #[account(
         mut,
         seeds = [b"transfer_order", order_id.to_le_bytes().as_ref()],
@@ -335,7 +958,42 @@ pub struct UnlockTokens<'info> {
     )]
     pub order: Account<'info, TransferOrder>,
     
+    #[account(
+        seeds = [b"bridge_config"],
+        bump
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    
     #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    // Required when the bridge only accepts registered relayers; records the claim expiry
+    #[account(
+        mut,
+        seeds = [b"relayer", relayer.key().as_ref()],
+        bump
+    )]
+    pub relayer_account: Option<Account<'info, RelayerAccount>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct UnlockTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"transfer_order", order_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = order.status == OrderStatus::Pending @ BridgeError::OrderNotPending,
+    )]
+    pub order: Account<'info, TransferOrder>,
+    
+    #[account(
+        mut,
+        seeds = [b"bridge_config"],
+        bump
+    )]
     pub bridge_config: Account<'info, BridgeConfig>,
     
     #[account(mut)]
@@ -355,6 +1013,8 @@ pub struct UnlockTokens<'info> {
     )]
     pub vault: Account<'info, TokenAccount>,
     
+    // Mutable to receive the claim bonds
+    #[account(mut)]
     pub relayer: Signer<'info>,
     
     #[account(
@@ -364,6 +1024,13 @@ pub struct UnlockTokens<'info> {
     )]
     pub relayer_reward_account: Account<'info, TokenAccount>,
     
+    // Required when the bridge only accepts registered relayers
+    #[account(
+        seeds = [b"relayer", relayer.key().as_ref()],
+        bump
+    )]
+    pub relayer_account: Option<Account<'info, RelayerAccount>>,
+    
     pub token_program: Program<'info, Token>,
 }
 
@@ -376,6 +1043,9 @@ pub struct BridgeConfig {
     pub next_order_id: u64,
     pub relayer_fee_bps: u16,
     pub min_relayer_fee: u64,
+    pub claim_window_slots: u64,
+    pub claim_bond: u64,
+    pub require_registered_relayer: bool,
 }
 
 #[account]
@@ -400,6 +1070,47 @@ pub struct TransferOrder {
     pub proof_hash: [u8; 32],
     pub completed_by: Pubkey,
     pub completed_at: u64,
+    pub claimed_by: Pubkey,
+    pub claim_expiry: u64,
+    pub claim_bond: u64,
+}
+
+impl TransferOrder {
+    /// Whether a relayer's claim on the order is still exclusive at `slot`.
+    pub fn is_claim_active(&self, slot: u64) -> bool {
+        self.claimed_by != Pubkey::default() && slot < self.claim_expiry
+    }
+}
+
+#[account]
+pub struct RelayerRegistry {
+    pub stake_mint: Pubkey,
+    pub min_stake: u64,
+    pub unbonding_slots: u64,
+    pub slash_bps: u16,
+    pub jail_slots: u64,
+    pub total_staked: u64,
+    pub relayer_count: u64,
+}
+
+#[account]
+pub struct RelayerAccount {
+    pub authority: Pubkey,
+    pub stake: u64,
+    pub status: RelayerStatus,
+    pub jailed_until: u64,
+    pub unbonding_at: u64,
+    pub slash_count: u32,
+    pub registered_at: u64,
+    /// Latest expiry of the relayer's claims
+    pub claimed_until: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum RelayerStatus {
+    Active,
+    Jailed,
+    Unbonding,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -424,6 +1135,38 @@ pub struct TokensLocked {
     pub recipient: [u8; 20],
     pub relayer_fee: u64,
     pub slot: u64,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct OrderClaimed {
+    pub order_id: u64,
+    pub relayer: Pubkey,
+    pub claim_expiry: u64,
+    pub bond: u64,
+}
+
+#[event]
+pub struct RelayerRegistered {
+    pub relayer: Pubkey,
+    pub stake: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct RelayerUnbonding {
+    pub relayer: Pubkey,
+    pub stake: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct RelayerSlashed {
+    pub relayer: Pubkey,
+    pub order_id: u64,
+    pub amount: u64,
+    pub reporter: Pubkey,
+    pub jailed_until: u64,
 }
 
 #[event]
@@ -434,6 +1177,19 @@ pub struct TokensUnlocked {
     pub relayer: Pubkey,
     pub relayer_fee: u64,
     pub slot: u64,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct BridgeReconciled {
+    pub mint: Pubkey,
+    pub vault_balance: u64,
+    pub total_locked: u64,
+    /// Sum of the pending orders passed in
+    pub outstanding: u64,
+    pub pending_orders: u32,
+    pub balanced: bool,
+    pub slot: u64,
 }
 
 // Errors
@@ -451,4 +1207,30 @@ pub enum BridgeError {
     InvalidProof,
     #[msg("Insufficient amount for relayer fee")]
     InsufficientAmount,
+    #[msg("Order is claimed by another relayer")]
+    ClaimActive,
+    #[msg("Claim window must be at least one slot")]
+    InvalidClaimWindow,
+    #[msg("Relayer is not registered")]
+    RelayerNotRegistered,
+    #[msg("Relayer is jailed or unbonding")]
+    RelayerNotActive,
+    #[msg("Stake below the registry minimum")]
+    InsufficientStake,
+    #[msg("Unbonding delay has not passed")]
+    UnbondingNotFinished,
+    #[msg("Relayer is not jailed")]
+    RelayerNotJailed,
+    #[msg("Relayer is still jailed")]
+    StillJailed,
+    #[msg("Relayer did not miss a claim on this order")]
+    NotSlashable,
+    #[msg("Slash rate above 100%")]
+    InvalidSlashRate,
+    #[msg("Order belongs to another token")]
+    TokenConfigMismatch,
+    #[msg("Orders must be passed in increasing order id")]
+    OrdersNotSorted,
+    #[msg("Relayer let its previous claim on this order lapse")]
+    ClaimLapsed,
 }
@@ -28,6 +28,16 @@ describe("solana-evm-bridge with SPL Token", () => {
   let userTokenAccount: PublicKey;
   let relayerTokenAccount: PublicKey;
   
+  let registryPda: PublicKey;
+  let stakeVaultPda: PublicKey;
+  let relayerAccountPda: PublicKey;
+  
+  const waitForSlot = async (slot: number) => {
+    while ((await provider.connection.getSlot()) <= slot) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+  };
+  
   before(async () => {
     const signature = await provider.connection.requestAirdrop(
       relayer.publicKey,
@@ -216,6 +226,70 @@ describe("solana-evm-bridge with SPL Token", () => {
     console.log("   Vault balance:", vaultAccountAfter.amount.toString());
   });
   
+  it("Claim order", async () => {
+    const orderId = 1;
+    const bond = 1_000_000; // lamports
+    const competitor = Keypair.generate();
+    
+    const [orderPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer_order"), new anchor.BN(orderId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    
+    await program.methods
+      .setClaimConfig(new anchor.BN(150), new anchor.BN(bond))
+      .accounts({
+        bridgeConfig: bridgeConfigPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+    
+    const orderLamportsBefore = await provider.connection.getBalance(orderPda);
+    
+    const tx = await program.methods
+      .claimOrder(new anchor.BN(orderId))
+      .accounts({
+        order: orderPda,
+        bridgeConfig: bridgeConfigPda,
+        relayer: relayer.publicKey,
+        relayerAccount: null,
+      })
+      .signers([relayer])
+      .rpc();
+    
+    console.log("Claim order tx:", tx);
+    
+    const order = await program.account.transferOrder.fetch(orderPda);
+    assert.equal(order.claimedBy.toBase58(), relayer.publicKey.toBase58());
+    assert.ok(order.claimExpiry.toNumber() > order.createdSlot.toNumber());
+    assert.equal(order.claimBond.toString(), bond.toString());
+    assert.equal(
+      (await provider.connection.getBalance(orderPda)) - orderLamportsBefore,
+      bond
+    );
+    
+    // A competing relayer can neither claim nor unlock during the window
+    try {
+      await program.methods
+        .claimOrder(new anchor.BN(orderId))
+        .accounts({
+          order: orderPda,
+          bridgeConfig: bridgeConfigPda,
+          relayer: competitor.publicKey,
+          relayerAccount: null,
+        })
+        .signers([competitor])
+        .rpc();
+      assert.fail("claim of a claimed order should fail");
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "ClaimActive");
+    }
+    
+    console.log("✅ Order claimed successfully");
+    console.log("   Claimed by:", order.claimedBy.toBase58());
+    console.log("   Claim expiry slot:", order.claimExpiry.toString());
+  });
+  
   it("Unlock tokens", async () => {
     const orderId = 1;
     const mockProofHash = Array(32).fill(1);
@@ -247,6 +321,7 @@ describe("solana-evm-bridge with SPL Token", () => {
         vault: vaultPda,
         relayer: relayer.publicKey,
         relayerRewardAccount: relayerTokenAccount,
+        relayerAccount: null,
       })
       .signers([relayer])
       .rpc();
@@ -259,6 +334,8 @@ describe("solana-evm-bridge with SPL Token", () => {
     assert.deepEqual(Array.from(orderAfter.proofHash), mockProofHash);
     assert.equal(orderAfter.completedBy.toBase58(), relayer.publicKey.toBase58());
     assert.ok(orderAfter.completedAt.toNumber() > 0);
+    // The claim bond is returned to the claiming relayer
+    assert.equal(orderAfter.claimBond.toString(), "0");
     
     const userAccountAfter = await getAccount(provider.connection, userTokenAccount);
     const relayerAccountAfter = await getAccount(provider.connection, relayerTokenAccount);
@@ -297,4 +374,420 @@ describe("solana-evm-bridge with SPL Token", () => {
     console.log("   Vault balance after:", vaultAccountAfter.amount.toString());
     console.log("   Total locked:", tokenConfig.totalLocked.toString());
   });
+  
+  it("Register relayer", async () => {
+    const stake = 2_000_000;
+    
+    [registryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer_registry")],
+      program.programId
+    );
+    [stakeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_vault")],
+      program.programId
+    );
+    [relayerAccountPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer"), relayer.publicKey.toBuffer()],
+      program.programId
+    );
+    
+    await program.methods
+      .initializeRegistry(new anchor.BN(1_000_000), new anchor.BN(10), 5000, new anchor.BN(1000))
+      .accounts({
+        registry: registryPda,
+        stakeVault: stakeVaultPda,
+        stakeMint: tokenMint,
+        bridgeConfig: bridgeConfigPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+    
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      relayerTokenAccount,
+      admin.publicKey,
+      stake
+    );
+    
+    const tx = await program.methods
+      .registerRelayer(new anchor.BN(stake))
+      .accounts({
+        registry: registryPda,
+        relayerAccount: relayerAccountPda,
+        stakeSource: relayerTokenAccount,
+        stakeVault: stakeVaultPda,
+        authority: relayer.publicKey,
+      })
+      .signers([relayer])
+      .rpc();
+    
+    console.log("Register relayer tx:", tx);
+    
+    const relayerAccount = await program.account.relayerAccount.fetch(relayerAccountPda);
+    assert.equal(relayerAccount.authority.toBase58(), relayer.publicKey.toBase58());
+    assert.equal(relayerAccount.stake.toString(), stake.toString());
+    assert.ok("active" in relayerAccount.status);
+    
+    const registry = await program.account.relayerRegistry.fetch(registryPda);
+    assert.equal(registry.totalStaked.toString(), stake.toString());
+    assert.equal(registry.relayerCount.toString(), "1");
+    
+    const stakeVault = await getAccount(provider.connection, stakeVaultPda);
+    assert.equal(stakeVault.amount.toString(), stake.toString());
+    
+    console.log("✅ Relayer registered successfully");
+    console.log("   Stake:", relayerAccount.stake.toString());
+  });
+  
+  it("Require registered relayers", async () => {
+    const orderId = 2;
+    const competitor = Keypair.generate();
+    
+    const [orderPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer_order"), new anchor.BN(orderId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    
+    await program.methods
+      .setRelayerRequirement(true)
+      .accounts({
+        bridgeConfig: bridgeConfigPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+    
+    // A one slot claim window, so the claim below expires right away
+    await program.methods
+      .setClaimConfig(new anchor.BN(1), new anchor.BN(0))
+      .accounts({
+        bridgeConfig: bridgeConfigPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+    
+    await program.methods
+      .lockTokens(new anchor.BN(1_000_000), Array(20).fill(2))
+      .accounts({
+        order: orderPda,
+        bridgeConfig: bridgeConfigPda,
+        tokenConfig: tokenConfigPda,
+        user: admin.publicKey,
+        userTokenAccount,
+        vault: vaultPda,
+      })
+      .rpc();
+    
+    try {
+      await program.methods
+        .claimOrder(new anchor.BN(orderId))
+        .accounts({
+          order: orderPda,
+          bridgeConfig: bridgeConfigPda,
+          relayer: competitor.publicKey,
+          relayerAccount: null,
+        })
+        .signers([competitor])
+        .rpc();
+      assert.fail("claim by an unregistered relayer should fail");
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "RelayerNotRegistered");
+    }
+    
+    await program.methods
+      .claimOrder(new anchor.BN(orderId))
+      .accounts({
+        order: orderPda,
+        bridgeConfig: bridgeConfigPda,
+        relayer: relayer.publicKey,
+        relayerAccount: relayerAccountPda,
+      })
+      .signers([relayer])
+      .rpc();
+    
+    const order = await program.account.transferOrder.fetch(orderPda);
+    assert.equal(order.claimedBy.toBase58(), relayer.publicKey.toBase58());
+    
+    console.log("✅ Only registered relayers may claim");
+  });
+  
+  it("Slash relayer that missed its claim", async () => {
+    const orderId = 2;
+    
+    const [orderPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer_order"), new anchor.BN(orderId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    
+    const orderBefore = await program.account.transferOrder.fetch(orderPda);
+    await waitForSlot(orderBefore.claimExpiry.toNumber());
+    
+    // Re-claiming the lapsed claim would push its expiry past the slash
+    try {
+      await program.methods
+        .claimOrder(new anchor.BN(orderId))
+        .accounts({
+          order: orderPda,
+          bridgeConfig: bridgeConfigPda,
+          relayer: relayer.publicKey,
+          relayerAccount: relayerAccountPda,
+        })
+        .signers([relayer])
+        .rpc();
+      assert.fail("re-claim of a lapsed claim should fail");
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "ClaimLapsed");
+    }
+    const orderReclaimed = await program.account.transferOrder.fetch(orderPda);
+    assert.equal(orderReclaimed.claimExpiry.toString(), orderBefore.claimExpiry.toString());
+    
+    const reporterBefore = await getAccount(provider.connection, userTokenAccount);
+    
+    const tx = await program.methods
+      .slashRelayer(new anchor.BN(orderId))
+      .accounts({
+        registry: registryPda,
+        relayerAccount: relayerAccountPda,
+        order: orderPda,
+        stakeVault: stakeVaultPda,
+        reporterTokenAccount: userTokenAccount,
+        reporter: admin.publicKey,
+      })
+      .rpc();
+    
+    console.log("Slash relayer tx:", tx);
+    
+    const relayerAccount = await program.account.relayerAccount.fetch(relayerAccountPda);
+    assert.ok("jailed" in relayerAccount.status);
+    assert.equal(relayerAccount.stake.toString(), "1000000");
+    assert.equal(relayerAccount.slashCount, 1);
+    
+    const reporterAfter = await getAccount(provider.connection, userTokenAccount);
+    assert.equal(Number(reporterAfter.amount - reporterBefore.amount), 1_000_000);
+    
+    const order = await program.account.transferOrder.fetch(orderPda);
+    assert.equal(order.claimedBy.toBase58(), PublicKey.default.toBase58());
+    
+    // The claim is cleared, so the same miss cannot be reported twice
+    try {
+      await program.methods
+        .slashRelayer(new anchor.BN(orderId))
+        .accounts({
+          registry: registryPda,
+          relayerAccount: relayerAccountPda,
+          order: orderPda,
+          stakeVault: stakeVaultPda,
+          reporterTokenAccount: userTokenAccount,
+          reporter: admin.publicKey,
+        })
+        .rpc();
+      assert.fail("second slash should fail");
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "NotSlashable");
+    }
+    
+    // A jailed relayer cannot unlock
+    try {
+      await program.methods
+        .unlockTokens(new anchor.BN(orderId), Array(32).fill(1))
+        .accounts({
+          order: orderPda,
+          bridgeConfig: bridgeConfigPda,
+          tokenConfig: tokenConfigPda,
+          userTokenAccount,
+          vault: vaultPda,
+          relayer: relayer.publicKey,
+          relayerRewardAccount: relayerTokenAccount,
+          relayerAccount: relayerAccountPda,
+        })
+        .signers([relayer])
+        .rpc();
+      assert.fail("unlock by a jailed relayer should fail");
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "RelayerNotActive");
+    }
+    
+    console.log("✅ Relayer slashed and jailed");
+    console.log("   Remaining stake:", relayerAccount.stake.toString());
+    console.log("   Jailed until slot:", relayerAccount.jailedUntil.toString());
+  });
+  
+  it("Unbond and withdraw stake", async () => {
+    try {
+      await program.methods
+        .unjail()
+        .accounts({
+          registry: registryPda,
+          relayerAccount: relayerAccountPda,
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
+      assert.fail("unjail before the jail period should fail");
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "StillJailed");
+    }
+    
+    // Claim order 2 again right before unbonding; open to any relayer once not enforced
+    const orderId = 2;
+    const [orderPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("transfer_order"), new anchor.BN(orderId).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods
+      .setRelayerRequirement(false)
+      .accounts({
+        bridgeConfig: bridgeConfigPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .setClaimConfig(new anchor.BN(20), new anchor.BN(0))
+      .accounts({
+        bridgeConfig: bridgeConfigPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+    await program.methods
+      .claimOrder(new anchor.BN(orderId))
+      .accounts({
+        order: orderPda,
+        bridgeConfig: bridgeConfigPda,
+        relayer: relayer.publicKey,
+        relayerAccount: relayerAccountPda,
+      })
+      .signers([relayer])
+      .rpc();
+    const claimed = await program.account.transferOrder.fetch(orderPda);
+    const claimExpiry = claimed.claimExpiry.toNumber();
+    
+    await program.methods
+      .requestUnbond()
+      .accounts({
+        relayerAccount: relayerAccountPda,
+        authority: relayer.publicKey,
+      })
+      .signers([relayer])
+      .rpc();
+    
+    const unbonding = await program.account.relayerAccount.fetch(relayerAccountPda);
+    assert.ok("unbonding" in unbonding.status);
+    assert.equal(unbonding.claimedUntil.toNumber(), claimExpiry);
+    await waitForSlot(unbonding.unbondingAt.toNumber() + 10);
+    
+    // The claim has not expired yet, so the stake stays until it can be reported
+    try {
+      await program.methods
+        .withdrawStake()
+        .accounts({
+          registry: registryPda,
+          relayerAccount: relayerAccountPda,
+          stakeVault: stakeVaultPda,
+          stakeDestination: relayerTokenAccount,
+          authority: relayer.publicKey,
+        })
+        .signers([relayer])
+        .rpc();
+      assert.fail("withdraw before the claim expired should fail");
+    } catch (err) {
+      assert.equal(err.error.errorCode.code, "UnbondingNotFinished");
+    }
+    
+    await waitForSlot(claimExpiry);
+    await program.methods
+      .slashRelayer(new anchor.BN(orderId))
+      .accounts({
+        registry: registryPda,
+        relayerAccount: relayerAccountPda,
+        order: orderPda,
+        stakeVault: stakeVaultPda,
+        reporterTokenAccount: userTokenAccount,
+        reporter: admin.publicKey,
+      })
+      .rpc();
+    const slashed = await program.account.relayerAccount.fetch(relayerAccountPda);
+    assert.equal(slashed.stake.toString(), "500000");
+    assert.ok("unbonding" in slashed.status);
+    await waitForSlot(claimExpiry + 10);
+    
+    const relayerBefore = await getAccount(provider.connection, relayerTokenAccount);
+    
+    const tx = await program.methods
+      .withdrawStake()
+      .accounts({
+        registry: registryPda,
+        relayerAccount: relayerAccountPda,
+        stakeVault: stakeVaultPda,
+        stakeDestination: relayerTokenAccount,
+        authority: relayer.publicKey,
+      })
+      .signers([relayer])
+      .rpc();
+    
+    console.log("Withdraw stake tx:", tx);
+    
+    const relayerAfter = await getAccount(provider.connection, relayerTokenAccount);
+    assert.equal(Number(relayerAfter.amount - relayerBefore.amount), 500_000);
+    assert.isNull(await provider.connection.getAccountInfo(relayerAccountPda));
+    
+    const registry = await program.account.relayerRegistry.fetch(registryPda);
+    assert.equal(registry.totalStaked.toString(), "0");
+    assert.equal(registry.relayerCount.toString(), "0");
+    
+    console.log("✅ Stake withdrawn after unbonding");
+  });
+  
+  it("Reconcile vault", async () => {
+    const orderPdas = [1, 2].map((orderId) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("transfer_order"), new anchor.BN(orderId).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0]
+    );
+    
+    const simulation = await program.methods
+      .reconcile()
+      .accounts({
+        tokenConfig: tokenConfigPda,
+        vault: vaultPda,
+      })
+      .remainingAccounts(
+        orderPdas.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }))
+      )
+      .simulate();
+    
+    const event = simulation.events.find((e) => e.name === "bridgeReconciled");
+    assert.ok(event, "reconcile should emit BridgeReconciled");
+    
+    // Order 1 was unlocked, order 2 is still pending and backed by the vault
+    const pending = await program.account.transferOrder.fetch(orderPdas[1]);
+    const vault = await getAccount(provider.connection, vaultPda);
+    assert.equal(event.data.vaultBalance.toString(), vault.amount.toString());
+    assert.equal(event.data.outstanding.toString(), pending.amount.toString());
+    assert.equal(event.data.pendingOrders, 1);
+    assert.equal(event.data.balanced, true);
+    
+    // An order passed twice is rejected
+    try {
+      await program.methods
+        .reconcile()
+        .accounts({
+          tokenConfig: tokenConfigPda,
+          vault: vaultPda,
+        })
+        .remainingAccounts(
+          [orderPdas[1], orderPdas[1]].map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }))
+        )
+        .simulate();
+      assert.fail("duplicate orders should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "OrdersNotSorted");
+    }
+    
+    console.log("✅ Vault reconciled");
+    console.log("   Vault balance:", event.data.vaultBalance.toString());
+    console.log("   Total locked:", event.data.totalLocked.toString());
+    console.log("   Outstanding:", event.data.outstanding.toString());
+  });
 });
@@ -0,0 +1,38 @@
+//! Bridge Proof Aggregation Guest Program
+//!
+//! This program recursively verifies compressed proofs of the bridge order program and commits a
+//! Merkle root of their public values, so the destination chain verifies a single proof and
+//! releases each order with an inclusion check.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use alloy_sol_types::SolType;
+use fibonacci_lib::{
+    aggregation::{aggregation_tree, vkey_digest_bytes, AggregationPublicValues},
+    hash::sha256,
+};
+
+pub fn main() {
+    // 1. 读取输入：单订单程序的 vkey 与各证明的公开值
+    let vkey: [u32; 8] = sp1_zkvm::io::read();
+    let public_values: Vec<Vec<u8>> = sp1_zkvm::io::read();
+    assert!(!public_values.is_empty(), "No proofs to aggregate");
+
+    // 2. 逐个递归验证证明；所有证明都按同一个 vkey 验证，且该 vkey 会被提交，
+    //    目标链据此确认被聚合的都是订单验证程序的证明
+    for bytes in &public_values {
+        sp1_zkvm::lib::verify::verify_sp1_proof(&vkey, &sha256(bytes));
+    }
+
+    // 3. 构建公开值的 Merkle 树
+    let root = aggregation_tree(&public_values).root();
+
+    // 4. 提交公开值
+    let output = AggregationPublicValues {
+        vkey: vkey_digest_bytes(&vkey).into(),
+        count: public_values.len() as u32,
+        ordersRoot: root.into(),
+    };
+    sp1_zkvm::io::commit_slice(&AggregationPublicValues::abi_encode(&output));
+}
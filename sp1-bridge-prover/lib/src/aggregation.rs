@@ -0,0 +1,69 @@
+use alloy_sol_types::sol;
+
+use crate::{
+    bridge::{MerkleProof, MerkleTree},
+    hash::{keccak256, HashFunction, Keccak256Hasher},
+};
+
+// 聚合证明的公开值：目标链只需验证一次聚合证明，再对每个订单做包含性检查
+sol! {
+    /// 聚合证明的公开输出
+    struct AggregationPublicValues {
+        /// 被聚合的单订单证明的 vkey 摘要（`hash_u32` 的 8 个字按大端拼接）
+        bytes32 vkey;
+        /// 聚合的订单数量
+        uint32 count;
+        /// 各订单公开值组成的 Merkle 根（Keccak-256，排序后哈希）
+        bytes32 ordersRoot;
+    }
+}
+
+/// 聚合树使用的哈希函数；Keccak 排序树与 OpenZeppelin `MerkleProof` 兼容，Solana 上也有 keccak syscall
+pub const AGGREGATION_HASH: HashFunction = HashFunction::Keccak256;
+
+/// 单个订单证明在聚合树中的叶子：对其完整公开值取 Keccak-256
+pub fn aggregation_leaf(public_values: &[u8]) -> [u8; 32] {
+    keccak256(public_values)
+}
+
+/// vkey 摘要（`hash_u32` 的 8 个字）按大端拼接为 bytes32
+pub fn vkey_digest_bytes(vkey: &[u32; 8]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, word) in vkey.iter().enumerate() {
+        bytes[i * 4..(i + 1) * 4].copy_from_slice(&word.to_be_bytes());
+    }
+    bytes
+}
+
+/// 由各订单证明的公开值构建聚合树
+pub fn aggregation_tree(public_values: &[Vec<u8>]) -> MerkleTree<Keccak256Hasher> {
+    MerkleTree::new(
+        public_values
+            .iter()
+            .map(|bytes| aggregation_leaf(bytes))
+            .collect(),
+    )
+}
+
+/// 第 `index` 个订单的包含性证明
+pub fn inclusion_proof(public_values: &[Vec<u8>], index: usize) -> Option<MerkleProof> {
+    aggregation_tree(public_values).proof(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inclusion_proofs() {
+        let public_values: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; 320]).collect();
+        let root = aggregation_tree(&public_values).root();
+
+        for (index, bytes) in public_values.iter().enumerate() {
+            let proof = inclusion_proof(&public_values, index).unwrap();
+            assert_eq!(proof.leaf, aggregation_leaf(bytes));
+            assert_eq!(proof.root, root);
+            assert!(proof.verify(AGGREGATION_HASH));
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use alloy_sol_types::sol;
 use serde::{Deserialize, Serialize};
 
+use std::marker::PhantomData;
+
+use crate::hash::{HashFunction, Keccak256Hasher, MerkleHasher, Sha256Hasher};
+
 // Solidity 结构体定义，用于 EVM 链上验证
 sol! {
     /// 跨链订单验证的公开输入/输出
@@ -21,6 +25,8 @@ sol! {
         bytes32 stateRoot;
         /// 时间戳/区块号
         uint64 timestamp;
+        /// 状态树使用的哈希函数 (0 = SHA-256, 1 = Keccak-256)
+        uint8 hashFunction;
     }
 }
 
@@ -53,52 +59,149 @@ pub struct MerkleProof {
 }
 
 impl MerkleProof {
-    /// 验证 Merkle 证明
-    pub fn verify(&self) -> bool {
-        let mut current = self.leaf;
-        
-        for sibling in &self.proof {
-            current = if current <= *sibling {
-                hash_pair(&current, sibling)
-            } else {
-                hash_pair(sibling, &current)
-            };
-        }
-        
-        current == self.root
+    /// 由叶子和兄弟节点构造证明，根根据路径计算得出
+    pub fn from_siblings(hash: HashFunction, leaf: [u8; 32], proof: Vec<[u8; 32]>) -> Self {
+        let root = compute_root(hash, &leaf, &proof);
+        Self { leaf, proof, root }
+    }
+
+    /// 用指定的哈希函数验证 Merkle 证明
+    pub fn verify(&self, hash: HashFunction) -> bool {
+        compute_root(hash, &self.leaf, &self.proof) == self.root
+    }
+
+    pub fn verify_with<H: MerkleHasher>(&self) -> bool {
+        compute_root_with::<H>(&self.leaf, &self.proof) == self.root
+    }
+}
+
+/// 沿证明路径计算 Merkle 根（兄弟节点按字节序排序后哈希）
+pub fn compute_root(hash: HashFunction, leaf: &[u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    match hash {
+        HashFunction::Sha256 => compute_root_with::<Sha256Hasher>(leaf, proof),
+        HashFunction::Keccak256 => compute_root_with::<Keccak256Hasher>(leaf, proof),
+    }
+}
+
+pub fn compute_root_with<H: MerkleHasher>(leaf: &[u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut current = *leaf;
+
+    for sibling in proof {
+        current = sorted_pair::<H>(&current, sibling);
     }
+
+    current
 }
 
-/// 简单的哈希函数（使用 sha256）
-fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    hasher.update(a);
-    hasher.update(b);
-    let result = hasher.finalize();
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&result);
-    hash
+fn sorted_pair<H: MerkleHasher>(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        H::hash_pair(a, b)
+    } else {
+        H::hash_pair(b, a)
+    }
 }
 
-/// 计算订单的哈希
+/// 计算订单的哈希（叶子），哈希函数由源链决定
 pub fn hash_order(order: &TransferOrder) -> [u8; 32] {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    
-    hasher.update(&order.order_id.to_le_bytes());
-    hasher.update(&order.user);
-    hasher.update(&[order.source_chain]);
-    hasher.update(&order.token);
-    hasher.update(&order.amount.to_le_bytes());
-    hasher.update(&order.recipient);
-    hasher.update(&order.relayer_fee.to_le_bytes());
-    hasher.update(&order.created_at.to_le_bytes());
-    
-    let result = hasher.finalize();
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&result);
-    hash
+    match HashFunction::for_chain(order.source_chain) {
+        HashFunction::Sha256 => hash_order_with::<Sha256Hasher>(order),
+        HashFunction::Keccak256 => hash_order_with::<Keccak256Hasher>(order),
+    }
+}
+
+pub fn hash_order_with<H: MerkleHasher>(order: &TransferOrder) -> [u8; 32] {
+    let mut data = Vec::with_capacity(129);
+
+    data.extend_from_slice(&order.order_id.to_le_bytes());
+    data.extend_from_slice(&order.user);
+    data.push(order.source_chain);
+    data.extend_from_slice(&order.token);
+    data.extend_from_slice(&order.amount.to_le_bytes());
+    data.extend_from_slice(&order.recipient);
+    data.extend_from_slice(&order.relayer_fee.to_le_bytes());
+    data.extend_from_slice(&order.created_at.to_le_bytes());
+
+    H::hash(&data)
+}
+
+/// 由叶子构建的 Merkle 树（兄弟节点排序后哈希，奇数个节点时最后一个直接提升到上一层）
+pub struct MerkleTree<H: MerkleHasher> {
+    levels: Vec<Vec<[u8; 32]>>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: MerkleHasher> MerkleTree<H> {
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => sorted_pair::<H>(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self {
+            levels,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// 树根，空树返回全零
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.levels[0]
+    }
+
+    /// 第 `index` 个叶子的证明
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        let leaf = *self.leaves().get(index)?;
+        let mut proof = Vec::new();
+        let mut index = index;
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(MerkleProof {
+            leaf,
+            proof,
+            root: self.root(),
+        })
+    }
+}
+
+/// 根据订单和状态根构造 guest 提交的公开值
+pub fn public_values(order: &TransferOrder, state_root: [u8; 32]) -> BridgeProofPublicValues {
+    let target_chain = if order.source_chain == 0 { 1 } else { 0 };
+
+    BridgeProofPublicValues {
+        orderId: order.order_id,
+        sourceChain: order.source_chain,
+        targetChain: target_chain,
+        token: order.token.into(),
+        amount: alloy_sol_types::private::U256::from(order.amount),
+        recipient: order.recipient.into(),
+        stateRoot: state_root.into(),
+        timestamp: order.created_at,
+        hashFunction: HashFunction::for_chain(order.source_chain) as u8,
+    }
 }
 
 #[cfg(test)]
@@ -110,7 +213,7 @@ mod tests {
         // 创建一个简单的 Merkle 树测试
         let leaf = [1u8; 32];
         let sibling = [2u8; 32];
-        let root = hash_pair(&leaf, &sibling);
+        let root = Sha256Hasher::hash_pair(&leaf, &sibling);
         
         let proof = MerkleProof {
             leaf,
@@ -118,7 +221,8 @@ mod tests {
             root,
         };
         
-        assert!(proof.verify());
+        assert!(proof.verify(HashFunction::Sha256));
+        assert!(!proof.verify(HashFunction::Keccak256));
     }
     
     #[test]
@@ -133,7 +237,32 @@ mod tests {
             root: wrong_root,
         };
         
-        assert!(!proof.verify());
+        assert!(!proof.verify(HashFunction::Sha256));
+    }
+
+    #[test]
+    fn test_merkle_proof_from_siblings() {
+        let leaf = [1u8; 32];
+        let siblings = vec![[2u8; 32], [0u8; 32]];
+        let proof = MerkleProof::from_siblings(HashFunction::Sha256, leaf, siblings);
+
+        let node = Sha256Hasher::hash_pair(&leaf, &[2u8; 32]);
+        assert_eq!(proof.root, Sha256Hasher::hash_pair(&[0u8; 32], &node));
+        assert!(proof.verify(HashFunction::Sha256));
+    }
+
+    #[test]
+    fn test_merkle_tree_proofs() {
+        let leaves: Vec<[u8; 32]> = (1..=5u8).map(|i| [i; 32]).collect();
+        let tree = MerkleTree::<Keccak256Hasher>::new(leaves.clone());
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            assert_eq!(proof.leaf, *leaf);
+            assert!(proof.verify_with::<Keccak256Hasher>());
+            assert!(!proof.verify_with::<Sha256Hasher>());
+        }
+        assert!(tree.proof(5).is_none());
     }
     
     #[test]
@@ -156,5 +285,13 @@ mod tests {
         // 相同的订单应该产生相同的哈希
         let hash2 = hash_order(&order);
         assert_eq!(hash, hash2);
+
+        // EVM 来源的订单使用 Keccak-256 叶子
+        let evm_order = TransferOrder {
+            source_chain: 1,
+            ..order
+        };
+        assert_eq!(hash_order(&evm_order), hash_order_with::<Keccak256Hasher>(&evm_order));
+        assert_eq!(public_values(&evm_order, [0u8; 32]).hashFunction, 1);
     }
 }
@@ -0,0 +1,177 @@
+//! 哈希原语
+//!
+//! 使用 `sha2` / `tiny-keccak`。guest 工作区通过 `[patch.crates-io]` 换成 sp1-patches 的版本，
+//! 在 zkVM 中每个压缩块只消耗一次预编译系统调用，而不是在 RISC-V 上逐条执行普通实现的指令；
+//! 主机上仍是普通实现，两者输出完全一致。
+//!
+//! 状态树的哈希函数按源链选择（见 [`HashFunction::for_chain`]），并通过 [`MerkleHasher`]
+//! 提供给订单叶子、Merkle 证明和建树使用。
+
+use core::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// SHA-256 摘要
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(data).into()
+}
+
+/// Keccak-256 摘要（以太坊使用的版本，而不是 SHA3-256）
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    use tiny_keccak::{Hasher, Keccak};
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Merkle 树与订单叶子使用的哈希函数
+pub trait MerkleHasher {
+    /// 写入公开值的哈希函数标识
+    const FUNCTION: HashFunction;
+
+    fn hash(data: &[u8]) -> [u8; 32];
+
+    /// 内部节点哈希：两个子节点直接拼接
+    fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(a);
+        data[32..].copy_from_slice(b);
+        Self::hash(&data)
+    }
+}
+
+/// SHA-256 树，Solana 上验证成本低
+pub struct Sha256Hasher;
+
+impl MerkleHasher for Sha256Hasher {
+    const FUNCTION: HashFunction = HashFunction::Sha256;
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        sha256(data)
+    }
+}
+
+/// Keccak-256 树，EVM 上验证成本低
+pub struct Keccak256Hasher;
+
+impl MerkleHasher for Keccak256Hasher {
+    const FUNCTION: HashFunction = HashFunction::Keccak256;
+
+    fn hash(data: &[u8]) -> [u8; 32] {
+        keccak256(data)
+    }
+}
+
+/// 运行时选择的哈希函数，数值会写入公开值的 `hashFunction` 字段
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum HashFunction {
+    #[default]
+    Sha256 = 0,
+    Keccak256 = 1,
+}
+
+impl HashFunction {
+    /// 源链对应的状态树哈希：Solana (0) 用 SHA-256，EVM (1) 用 Keccak-256
+    pub fn for_chain(source_chain: u8) -> Self {
+        match source_chain {
+            1 => HashFunction::Keccak256,
+            _ => HashFunction::Sha256,
+        }
+    }
+
+    pub fn hash(self, data: &[u8]) -> [u8; 32] {
+        match self {
+            HashFunction::Sha256 => Sha256Hasher::hash(data),
+            HashFunction::Keccak256 => Keccak256Hasher::hash(data),
+        }
+    }
+
+    pub fn hash_pair(self, a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        match self {
+            HashFunction::Sha256 => Sha256Hasher::hash_pair(a, b),
+            HashFunction::Keccak256 => Keccak256Hasher::hash_pair(a, b),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HashFunction::Sha256 => "sha256",
+            HashFunction::Keccak256 => "keccak256",
+        }
+    }
+}
+
+impl TryFrom<u8> for HashFunction {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, u8> {
+        match value {
+            0 => Ok(HashFunction::Sha256),
+            1 => Ok(HashFunction::Keccak256),
+            other => Err(other),
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for HashFunction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "sha256" => Ok(HashFunction::Sha256),
+            "keccak256" | "keccak" => Ok(HashFunction::Keccak256),
+            other => Err(format!("unknown hash function: {other}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests() {
+        assert_eq!(
+            sha256(b"abc"),
+            [
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+                0xf2, 0x00, 0x15, 0xad,
+            ]
+        );
+        assert_eq!(
+            keccak256(b""),
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+                0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+                0x5d, 0x85, 0xa4, 0x70,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hash_function_for_chain() {
+        assert_eq!(HashFunction::for_chain(0), HashFunction::Sha256);
+        assert_eq!(HashFunction::for_chain(1), HashFunction::Keccak256);
+        assert_eq!(HashFunction::Keccak256 as u8, 1);
+        assert_eq!("keccak256".parse(), Ok(HashFunction::Keccak256));
+
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+        assert_eq!(HashFunction::Sha256.hash_pair(&a, &b), Sha256Hasher::hash_pair(&a, &b));
+        assert_ne!(
+            HashFunction::Sha256.hash_pair(&a, &b),
+            HashFunction::Keccak256.hash_pair(&a, &b)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_sol_types::sol;
 
+pub mod aggregation;
 pub mod bridge;
+pub mod hash;
 
 sol! {
     /// The public values encoded as a struct that can be easily deserialized inside Solidity.
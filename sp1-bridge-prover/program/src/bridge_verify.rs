@@ -2,16 +2,22 @@
 //! This program verifies that a transfer order exists and is in the correct state
 
 use alloy_sol_types::SolType;
-use fibonacci_lib::bridge::{
-    BridgeProofPublicValues, MerkleProof, OrderStatus, TransferOrder, hash_order,
+use fibonacci_lib::{
+    bridge::{
+        BridgeProofPublicValues, MerkleProof, OrderStatus, TransferOrder, hash_order, public_values,
+    },
+    hash::HashFunction,
 };
 
 pub fn verify_bridge_order() {
+    // 各阶段用 cycle-tracker-report 标记，基准测试从 ExecutionReport 中按阶段读取周期数
+    println!("cycle-tracker-report-start: read_inputs");
     // 1. 读取输入：订单数据
     let order: TransferOrder = sp1_zkvm::io::read();
     
     // 2. 读取输入：Merkle 证明（证明订单在状态树中）
     let merkle_proof: MerkleProof = sp1_zkvm::io::read();
+    println!("cycle-tracker-report-end: read_inputs");
     
     // 3. 验证订单状态必须是 Pending
     assert_eq!(
@@ -20,8 +26,11 @@ pub fn verify_bridge_order() {
         "Order must be in Pending status"
     );
     
-    // 4. 计算订单哈希
+    // 4. 计算订单哈希（状态树的哈希函数由源链决定，并写入公开值）
+    let hash = HashFunction::for_chain(order.source_chain);
+    println!("cycle-tracker-report-start: hash_order");
     let order_hash = hash_order(&order);
+    println!("cycle-tracker-report-end: hash_order");
     
     // 5. 验证订单哈希与 Merkle proof 的 leaf 匹配
     assert_eq!(
@@ -29,33 +38,29 @@ pub fn verify_bridge_order() {
         "Order hash must match Merkle proof leaf"
     );
     
-    // 6. 验证 Merkle 证明
-    assert!(merkle_proof.verify(), "Merkle proof verification failed");
-    
-    // 7. 验证金额大于 0
-    assert!(order.amount > 0, "Amount must be greater than 0");
+    // 6. Merkle 路径不能为空，否则叶子本身就会被当作状态根
+    assert!(
+        !merkle_proof.proof.is_empty(),
+        "Merkle proof path must not be empty"
+    );
     
-    // 8. 准备公开输出
-    let target_chain = if order.source_chain == 0 { 1 } else { 0 };
+    // 7. 验证 Merkle 证明
+    println!("cycle-tracker-report-start: merkle_verify");
+    assert!(merkle_proof.verify(hash), "Merkle proof verification failed");
+    println!("cycle-tracker-report-end: merkle_verify");
     
-    // 将 u64 转换为 U256
-    let amount_u256 = alloy_sol_types::private::U256::from(order.amount);
+    // 8. 验证金额大于 0
+    assert!(order.amount > 0, "Amount must be greater than 0");
     
-    let public_values = BridgeProofPublicValues {
-        orderId: order.order_id,
-        sourceChain: order.source_chain,
-        targetChain: target_chain,
-        token: order.token.into(),
-        amount: amount_u256,
-        recipient: order.recipient.into(),
-        stateRoot: merkle_proof.root.into(),
-        timestamp: order.created_at,
-    };
+    // 9. 准备公开输出
+    println!("cycle-tracker-report-start: commit");
+    let public_values = public_values(&order, merkle_proof.root);
     
-    // 9. 提交公开值
+    // 10. 提交公开值
     let bytes = BridgeProofPublicValues::abi_encode(&public_values);
     sp1_zkvm::io::commit_slice(&bytes);
     
-    // 10. 同时输出订单哈希供调试
+    // 11. 同时输出订单哈希供调试
     sp1_zkvm::io::commit_slice(&order_hash);
+    println!("cycle-tracker-report-end: commit");
 }
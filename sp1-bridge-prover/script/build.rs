@@ -1,5 +1,6 @@
 use sp1_build::build_program_with_args;
 
 fn main() {
-    build_program_with_args("../program", Default::default())
+    build_program_with_args("../program", Default::default());
+    build_program_with_args("../aggregation", Default::default());
 }
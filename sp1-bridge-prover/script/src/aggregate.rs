@@ -0,0 +1,269 @@
+//! Aggregating compressed single-order proofs into one proof.
+//!
+//! The aggregation guest recursively verifies every proof against the bridge program vkey and
+//! commits the Keccak Merkle root of their public values. The aggregate bundle stores that proof
+//! together with the inclusion proof of every order, which is all a destination chain needs to
+//! release the orders individually.
+
+use std::path::Path;
+
+use alloy_sol_types::SolType;
+use anyhow::Context;
+use fibonacci_lib::{
+    aggregation::{
+        aggregation_leaf, aggregation_tree, vkey_digest_bytes, AggregationPublicValues,
+        AGGREGATION_HASH,
+    },
+    bridge::MerkleProof,
+};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1Stdin, SP1VerifyingKey};
+
+use crate::{
+    artifact::{bincode_base64, unix_now},
+    decode_public_values,
+    input::hex_bytes32,
+    ProofMode,
+};
+
+/// Version of the aggregate bundle layout, bumped on incompatible changes.
+pub const AGGREGATE_VERSION: u32 = 1;
+
+/// Write the aggregation guest inputs: the bridge vkey digest, the public values of every proof
+/// and the proofs themselves for the recursive verifier.
+pub fn aggregation_stdin(
+    vk: &SP1VerifyingKey,
+    proofs: &[SP1ProofWithPublicValues],
+) -> anyhow::Result<SP1Stdin> {
+    anyhow::ensure!(!proofs.is_empty(), "no proofs to aggregate");
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&vk.hash_u32());
+    stdin.write(&public_values_of(proofs));
+    for (index, proof) in proofs.iter().enumerate() {
+        let SP1Proof::Compressed(reduce_proof) = &proof.proof else {
+            anyhow::bail!(
+                "proof {index} is a {} proof, expected compressed",
+                proof.proof
+            );
+        };
+        stdin.write_proof(*reduce_proof.clone(), vk.vk.clone());
+    }
+    Ok(stdin)
+}
+
+fn public_values_of(proofs: &[SP1ProofWithPublicValues]) -> Vec<Vec<u8>> {
+    proofs
+        .iter()
+        .map(|proof| proof.public_values.to_vec())
+        .collect()
+}
+
+/// One order covered by an aggregate proof.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AggregatedOrder {
+    pub order_id: u64,
+    /// Hex encoded public values of the single-order proof.
+    pub public_values: String,
+    #[serde(with = "hex_bytes32")]
+    pub leaf: [u8; 32],
+    /// Siblings from the leaf up to `orders_root`.
+    #[serde(with = "hex_bytes32::vec")]
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// An aggregate proof with the inclusion proof of every order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AggregateBundle {
+    pub version: u32,
+    pub mode: ProofMode,
+    pub sp1_version: String,
+    /// Digest of the bridge program vkey the aggregated proofs were checked against.
+    #[serde(with = "hex_bytes32")]
+    pub vkey_digest: [u8; 32],
+    /// `bytes32` hash of the aggregation program vkey.
+    #[serde(with = "hex_bytes32")]
+    pub aggregation_vkey_hash: [u8; 32],
+    #[serde(with = "hex_bytes32")]
+    pub orders_root: [u8; 32],
+    pub orders: Vec<AggregatedOrder>,
+    pub started_at: u64,
+    pub finished_at: u64,
+    /// Hex encoded public values committed by the aggregation guest.
+    pub public_values: String,
+    /// Hex encoded proof in the encoding on-chain verifiers accept (Plonk and Groth16 only).
+    pub proof_bytes: Option<String>,
+    #[serde(with = "bincode_base64")]
+    pub proof: SP1ProofWithPublicValues,
+}
+
+impl AggregateBundle {
+    /// Bundle an aggregate proof of `proofs`, checking its public values against them.
+    pub fn new(
+        proof: SP1ProofWithPublicValues,
+        mode: ProofMode,
+        proofs: &[SP1ProofWithPublicValues],
+        bridge_vk: &SP1VerifyingKey,
+        aggregation_vk: &SP1VerifyingKey,
+        started_at: u64,
+    ) -> anyhow::Result<Self> {
+        let output = AggregationPublicValues::abi_decode(proof.public_values.as_slice())?;
+        let public_values = public_values_of(proofs);
+        let tree = aggregation_tree(&public_values);
+        let vkey_digest = vkey_digest_bytes(&bridge_vk.hash_u32());
+
+        anyhow::ensure!(
+            output.vkey.0 == vkey_digest,
+            "aggregate proof was made for a different bridge vkey"
+        );
+        anyhow::ensure!(
+            output.count as usize == proofs.len() && output.ordersRoot.0 == tree.root(),
+            "aggregate proof does not commit to the given proofs"
+        );
+
+        let orders = public_values
+            .iter()
+            .enumerate()
+            .map(|(index, bytes)| {
+                let (order, _) = decode_public_values(bytes)?;
+                let inclusion = tree.proof(index).context("missing inclusion proof")?;
+                Ok(AggregatedOrder {
+                    order_id: order.orderId,
+                    public_values: format!("0x{}", hex::encode(bytes)),
+                    leaf: inclusion.leaf,
+                    proof: inclusion.proof,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let proof_bytes = match proof.proof {
+            SP1Proof::Plonk(_) | SP1Proof::Groth16(_) => {
+                Some(format!("0x{}", hex::encode(proof.bytes())))
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            version: AGGREGATE_VERSION,
+            mode,
+            sp1_version: proof.sp1_version.clone(),
+            vkey_digest,
+            aggregation_vkey_hash: aggregation_vk.bytes32_raw(),
+            orders_root: tree.root(),
+            orders,
+            started_at,
+            finished_at: unix_now(),
+            public_values: format!("0x{}", hex::encode(proof.public_values.as_slice())),
+            proof_bytes,
+            proof,
+        })
+    }
+
+    /// Check that every order is included under `orders_root`.
+    pub fn check_inclusion(&self) -> anyhow::Result<()> {
+        for order in &self.orders {
+            let bytes = hex::decode(order.public_values.trim_start_matches("0x"))?;
+            let proof = MerkleProof {
+                leaf: aggregation_leaf(&bytes),
+                proof: order.proof.clone(),
+                root: self.orders_root,
+            };
+            anyhow::ensure!(
+                proof.leaf == order.leaf && proof.verify(AGGREGATION_HASH),
+                "order {} is not included in the aggregate root",
+                order.order_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Write the bundle to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write aggregate bundle {}", path.display()))
+    }
+
+    /// Load a bundle and check the inclusion proofs of its orders.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read aggregate bundle {}", path.display()))?;
+        let bundle: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse aggregate bundle {}", path.display()))?;
+
+        anyhow::ensure!(
+            bundle.version == AGGREGATE_VERSION,
+            "unsupported aggregate bundle version {} (expected {})",
+            bundle.version,
+            AGGREGATE_VERSION
+        );
+        bundle.check_inclusion()?;
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp1_sdk::SP1PublicValues;
+
+    fn bundle(public_values: &[Vec<u8>]) -> AggregateBundle {
+        let tree = aggregation_tree(public_values);
+        let orders = public_values
+            .iter()
+            .enumerate()
+            .map(|(index, bytes)| {
+                let inclusion = tree.proof(index).unwrap();
+                AggregatedOrder {
+                    order_id: index as u64 + 1,
+                    public_values: format!("0x{}", hex::encode(bytes)),
+                    leaf: inclusion.leaf,
+                    proof: inclusion.proof,
+                }
+            })
+            .collect();
+
+        AggregateBundle {
+            version: AGGREGATE_VERSION,
+            mode: ProofMode::Groth16,
+            sp1_version: "v5.0.0".to_string(),
+            vkey_digest: [0x01; 32],
+            aggregation_vkey_hash: [0x02; 32],
+            orders_root: tree.root(),
+            orders,
+            started_at: 1,
+            finished_at: 2,
+            public_values: "0x".to_string(),
+            proof_bytes: None,
+            proof: SP1ProofWithPublicValues {
+                proof: SP1Proof::Core(vec![]),
+                public_values: SP1PublicValues::new(),
+                sp1_version: "v5.0.0".to_string(),
+                tee_proof: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_bundle_roundtrip_checks_inclusion() {
+        let public_values: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; 320]).collect();
+        let bundle = bundle(&public_values);
+
+        let path = std::env::temp_dir().join(format!("aggregate-{}.json", std::process::id()));
+        bundle.save(&path).unwrap();
+        let loaded = AggregateBundle::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.orders.len(), 5);
+        assert_eq!(loaded.orders_root, bundle.orders_root);
+    }
+
+    #[test]
+    fn test_rejects_order_outside_root() {
+        let public_values: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; 320]).collect();
+        let mut bundle = bundle(&public_values);
+        bundle.orders[1].public_values = format!("0x{}", hex::encode([0xff; 320]));
+
+        assert!(bundle.check_inclusion().is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! Loading transfer orders from raw account data of the Anchor bridge program.
+//!
+//! Account layouts, events and addresses come from `solana-evm-bridge-client`, which builds on the
+//! program's own types. Accounts can be captured from a local validator with
+//! `solana account <PDA> --output json --output-file order.json`; the raw binary data and a bare
+//! base64 string are accepted as well. Events emitted by the program are decoded from the
+//! `Program data:` lines of transaction logs.
+
+use std::path::Path;
+
+pub use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::Context;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use fibonacci_lib::bridge::{OrderStatus, TransferOrder};
+use solana_evm_bridge_client::accounts;
+pub use solana_evm_bridge_client::{
+    events::{BridgeEvent, TokensLocked, TokensUnlocked},
+    OrderStatus as AnchorOrderStatus, TokenConfig as AnchorTokenConfig,
+    TransferOrder as AnchorTransferOrder,
+};
+
+/// An account of the Anchor program, identified by its discriminator.
+pub trait AnchorAccount: AccountDeserialize + Discriminator {
+    /// Check the discriminator and decode the account, ignoring trailing padding.
+    fn decode(data: &[u8]) -> anyhow::Result<Self> {
+        Ok(accounts::decode(data)?)
+    }
+
+    /// Read and decode an account dump from a file.
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let data = read_account_data(path)?;
+        Self::decode(&data).with_context(|| format!("invalid account dump {}", path.display()))
+    }
+}
+
+impl<T: AccountDeserialize + Discriminator> AnchorAccount for T {}
+
+/// Convert an order to the form the guest proves, with `token_config` resolved to its mint.
+pub fn guest_order(order: &AnchorTransferOrder, mint: [u8; 32]) -> TransferOrder {
+    let mut recipient = [0u8; 32];
+    recipient[12..].copy_from_slice(&order.recipient);
+
+    TransferOrder {
+        order_id: order.order_id,
+        user: order.user.to_bytes(),
+        source_chain: order.source_chain,
+        token: mint,
+        amount: order.amount,
+        recipient,
+        relayer_fee: order.relayer_fee,
+        created_at: order.created_slot,
+        status: match order.status {
+            AnchorOrderStatus::Pending => OrderStatus::Pending,
+            AnchorOrderStatus::Completed => OrderStatus::Completed,
+        },
+    }
+}
+
+/// The events the program deployed at `program_id` emitted in the log messages of a transaction,
+/// in emission order.
+pub fn events(logs: &[String], program_id: &[u8; 32]) -> Vec<BridgeEvent> {
+    solana_evm_bridge_client::events::parse_program_logs(logs, &Pubkey::new_from_array(*program_id))
+}
+
+/// Read raw account data from a `solana account --output json` dump, a base64 string or raw bytes.
+pub fn read_account_data(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let contents =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    parse_account_data(&contents)
+}
+
+fn parse_account_data(contents: &[u8]) -> anyhow::Result<Vec<u8>> {
+    if let Ok(dump) = serde_json::from_slice::<serde_json::Value>(contents) {
+        return account_data_from_json(&dump);
+    }
+
+    if let Ok(text) = std::str::from_utf8(contents) {
+        if let Ok(data) = STANDARD.decode(text.trim()) {
+            return Ok(data);
+        }
+    }
+
+    Ok(contents.to_vec())
+}
+
+/// Account data of a JSON account as returned by the Solana CLI and RPC (`getAccountInfo`,
+/// `getProgramAccounts`), in base64 encoding.
+pub fn account_data_from_json(dump: &serde_json::Value) -> anyhow::Result<Vec<u8>> {
+    let data = dump
+        .pointer("/account/data")
+        .or_else(|| dump.get("data"))
+        .context("JSON dump has no account data")?;
+    let (encoded, encoding) = match data {
+        serde_json::Value::Array(parts) => (
+            parts.first().and_then(|v| v.as_str()),
+            parts.get(1).and_then(|v| v.as_str()).unwrap_or("base64"),
+        ),
+        serde_json::Value::String(s) => (Some(s.as_str()), "base64"),
+        _ => (None, ""),
+    };
+    let encoded = encoded.context("account data is not a string")?;
+    anyhow::ensure!(
+        encoding == "base64",
+        "unsupported account encoding: {encoding}"
+    );
+    STANDARD
+        .decode(encoded)
+        .context("invalid base64 account data")
+}
+
+/// Parse a base58 Solana address.
+pub fn parse_pubkey(s: &str) -> Result<[u8; 32], String> {
+    let bytes = bs58::decode(s).into_vec().map_err(|e| e.to_string())?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 bytes, got {}", bytes.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{AccountSerialize, Event};
+
+    use super::*;
+
+    fn sample_order() -> AnchorTransferOrder {
+        AnchorTransferOrder {
+            order_id: 3,
+            user: Pubkey::new_from_array([0x11; 32]),
+            status: AnchorOrderStatus::Pending,
+            token_config: Pubkey::new_from_array([0x22; 32]),
+            source_chain: 0,
+            amount: 999_000,
+            recipient: [0x33; 20],
+            relayer_fee: 1_000,
+            created_slot: 4242,
+            proof_hash: [0; 32],
+            completed_by: Pubkey::default(),
+            completed_at: 0,
+            claimed_by: Pubkey::default(),
+            claim_expiry: 0,
+            claim_bond: 0,
+        }
+    }
+
+    fn account_bytes<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        // Anchor allocates more space than the serialized struct needs.
+        data.extend_from_slice(&[0u8; 39]);
+        data
+    }
+
+    #[test]
+    fn test_decode_transfer_order() {
+        let data = account_bytes(&sample_order());
+        let decoded = AnchorTransferOrder::decode(&data).unwrap();
+        let order = guest_order(&decoded, [0x44; 32]);
+
+        assert_eq!(order.order_id, 3);
+        assert_eq!(order.user, [0x11; 32]);
+        assert_eq!(order.token, [0x44; 32]);
+        assert_eq!(order.recipient[..12], [0u8; 12]);
+        assert_eq!(order.recipient[12..], [0x33; 20]);
+        assert_eq!(order.created_at, 4242);
+        assert_eq!(order.status, OrderStatus::Pending);
+    }
+
+    #[test]
+    fn test_rejects_wrong_discriminator() {
+        let config = AnchorTokenConfig {
+            solana_mint: Pubkey::new_from_array([0x44; 32]),
+            evm_token: [0x55; 20],
+            is_native_solana: true,
+            total_locked: 0,
+        };
+        let data = account_bytes(&config);
+
+        assert!(AnchorTransferOrder::decode(&data).is_err());
+        assert_eq!(
+            AnchorTokenConfig::decode(&data).unwrap().solana_mint,
+            Pubkey::new_from_array([0x44; 32])
+        );
+    }
+
+    #[test]
+    fn test_decode_events_from_logs() {
+        let event = TokensLocked {
+            order_id: 7,
+            user: Pubkey::new_from_array([0x11; 32]),
+            amount: 999_000,
+            recipient: [0x33; 20],
+            relayer_fee: 1_000,
+            slot: 4242,
+            mint: Pubkey::new_from_array([0x44; 32]),
+        };
+        let program_id = [0x01; 32];
+
+        let logs = vec![
+            format!(
+                "Program {} invoke [1]",
+                bs58::encode(program_id).into_string()
+            ),
+            "Program log: Instruction: LockTokens".to_string(),
+            // An event of another type.
+            format!("Program data: {}", STANDARD.encode([0u8; 16])),
+            format!("Program data: {}", STANDARD.encode(event.data())),
+            "Program log: Tokens locked".to_string(),
+        ];
+        let decoded = events(&logs, &program_id);
+        assert!(matches!(
+            &decoded[..],
+            [BridgeEvent::TokensLocked(locked)] if locked.order_id == 7 && locked.mint == event.mint
+        ));
+        // Events of the program deployed at another address are not picked up.
+        assert!(events(&logs, &[0x02; 32]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_solana_cli_dump() {
+        let data = account_bytes(&sample_order());
+        let dump = serde_json::json!({
+            "pubkey": "11111111111111111111111111111111",
+            "account": {
+                "lamports": 2_540_160,
+                "data": [STANDARD.encode(&data), "base64"],
+                "owner": "GbtjEQYnuvVKN5DiQjvqoPGA9vS2tsH7mTfS6SJZXgBf",
+                "executable": false,
+                "rentEpoch": 0,
+            }
+        });
+
+        let parsed = parse_account_data(dump.to_string().as_bytes()).unwrap();
+        assert_eq!(parsed, data);
+        assert_eq!(
+            parse_account_data(STANDARD.encode(&data).as_bytes()).unwrap(),
+            data
+        );
+        assert_eq!(parse_account_data(&data).unwrap(), data);
+    }
+}
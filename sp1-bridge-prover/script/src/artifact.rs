@@ -0,0 +1,230 @@
+//! On-disk proof bundles, so proofs can be handed between processes and kept for auditing.
+//!
+//! A bundle is a JSON document holding the proof metadata in plain text next to the bincode
+//! encoded [`SP1ProofWithPublicValues`].
+
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues};
+
+use crate::{decode_public_values, input::hex_bytes32, ProofMode};
+
+/// Version of the bundle layout, bumped on incompatible changes.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Metadata recorded alongside every proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    pub mode: ProofMode,
+    pub sp1_version: String,
+    /// `bytes32` hash of the verifying key of the program that was proven.
+    #[serde(with = "hex_bytes32")]
+    pub vkey_hash: [u8; 32],
+    /// Hash of the serialized guest inputs, see [`crate::input::stdin_hash`].
+    #[serde(with = "hex_bytes32")]
+    pub input_hash: [u8; 32],
+    pub order_id: u64,
+    /// Unix timestamps (seconds) of when proving started and finished.
+    pub started_at: u64,
+    pub finished_at: u64,
+}
+
+/// A proof together with its metadata.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub version: u32,
+    #[serde(flatten)]
+    pub metadata: ProofMetadata,
+    /// Hex encoded public values committed by the guest.
+    pub public_values: String,
+    /// Hex encoded proof in the encoding on-chain verifiers accept (Plonk and Groth16 only).
+    pub proof_bytes: Option<String>,
+    #[serde(with = "bincode_base64")]
+    pub proof: SP1ProofWithPublicValues,
+}
+
+impl ProofBundle {
+    /// Bundle a freshly generated proof. `started_at` is when proving began.
+    pub fn new(
+        proof: SP1ProofWithPublicValues,
+        mode: ProofMode,
+        vkey_hash: [u8; 32],
+        input_hash: [u8; 32],
+        started_at: u64,
+    ) -> anyhow::Result<Self> {
+        let (public_values, _) = decode_public_values(proof.public_values.as_slice())?;
+        let proof_bytes = match proof.proof {
+            SP1Proof::Plonk(_) | SP1Proof::Groth16(_) => {
+                Some(format!("0x{}", hex::encode(proof.bytes())))
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            metadata: ProofMetadata {
+                mode,
+                sp1_version: proof.sp1_version.clone(),
+                vkey_hash,
+                input_hash,
+                order_id: public_values.orderId,
+                started_at,
+                finished_at: unix_now(),
+            },
+            public_values: format!("0x{}", hex::encode(proof.public_values.as_slice())),
+            proof_bytes,
+            proof,
+        })
+    }
+
+    /// Write the bundle to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write proof bundle {}", path.display()))
+    }
+
+    /// Load a bundle and check that its plain text fields match the embedded proof.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read proof bundle {}", path.display()))?;
+        let bundle: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse proof bundle {}", path.display()))?;
+
+        anyhow::ensure!(
+            bundle.version == BUNDLE_VERSION,
+            "unsupported proof bundle version {} (expected {})",
+            bundle.version,
+            BUNDLE_VERSION
+        );
+        anyhow::ensure!(
+            bundle.public_values
+                == format!("0x{}", hex::encode(bundle.proof.public_values.as_slice())),
+            "proof bundle public values do not match the embedded proof"
+        );
+        Ok(bundle)
+    }
+}
+
+/// Current unix time in seconds.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Serde helper storing a value as base64 encoded bincode.
+pub mod bincode_base64 {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{
+        de::DeserializeOwned, de::Error, Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let bytes = bincode::serialize(value).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, T: DeserializeOwned, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = STANDARD.decode(encoded).map_err(D::Error::custom)?;
+        bincode::deserialize(&bytes).map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<T: Serialize, S: Serializer>(
+            value: &Option<T>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => {
+                    let bytes = bincode::serialize(value).map_err(serde::ser::Error::custom)?;
+                    serializer.serialize_some(&STANDARD.encode(bytes))
+                }
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, T: DeserializeOwned, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<T>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|encoded| {
+                    let bytes = STANDARD.decode(encoded).map_err(D::Error::custom)?;
+                    bincode::deserialize(&bytes).map_err(D::Error::custom)
+                })
+                .transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolType;
+    use fibonacci_lib::bridge::{hash_order, public_values, BridgeProofPublicValues};
+    use sp1_sdk::SP1PublicValues;
+
+    use crate::input::demo_order;
+
+    fn mock_proof() -> SP1ProofWithPublicValues {
+        let order = demo_order();
+        let mut bytes = BridgeProofPublicValues::abi_encode(&public_values(&order, [0x0f; 32]));
+        bytes.extend_from_slice(&hash_order(&order));
+
+        SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(vec![]),
+            public_values: SP1PublicValues::from(&bytes),
+            sp1_version: "v5.0.0".to_string(),
+            tee_proof: None,
+        }
+    }
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let bundle =
+            ProofBundle::new(mock_proof(), ProofMode::Core, [0x01; 32], [0x02; 32], 100).unwrap();
+        assert_eq!(bundle.metadata.order_id, 1);
+        assert!(bundle.proof_bytes.is_none());
+
+        let path = std::env::temp_dir().join(format!("bundle-{}.json", std::process::id()));
+        bundle.save(&path).unwrap();
+        let loaded = ProofBundle::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.metadata, bundle.metadata);
+        assert_eq!(loaded.public_values, bundle.public_values);
+        assert_eq!(
+            loaded.proof.public_values.as_slice(),
+            bundle.proof.public_values.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_tampered_public_values() {
+        let mut bundle =
+            ProofBundle::new(mock_proof(), ProofMode::Core, [0x01; 32], [0x02; 32], 100).unwrap();
+        bundle.public_values = format!("0x{}", "00".repeat(320));
+
+        let path = std::env::temp_dir().join(format!("tampered-{}.json", std::process::id()));
+        bundle.save(&path).unwrap();
+        let result = ProofBundle::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,390 @@
+//! Reconciliation of the Solana vaults against the orders on both bridges.
+//!
+//! For every token pair registered with the Anchor program, the auditor reads the vault balance,
+//! `TokenConfig.total_locked` and the order accounts, and takes the releases of Solana orders on
+//! the EVM bridge from the indexer's database. A pair is balanced when:
+//!
+//! - the vault holds exactly `total_locked`;
+//! - `total_locked` is the sum of the pending orders;
+//! - every release on the EVM bridge belongs to an order of the pair and pays out the pair's EVM
+//!   token;
+//! - every release pays out the locked amount, relayer fee included;
+//! - no order released on the EVM bridge was also unlocked from the vault.
+//!
+//! The first two checks are what the program's `reconcile` instruction emits on chain. Releases
+//! the indexer has not seen yet are not missed by any check, so the index may lag the accounts
+//! without false reports; read the accounts at the commitment the indexer reads the history at.
+
+use std::collections::HashMap;
+
+use alloy_sol_types::private::U256;
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::{
+    anchor::{AnchorOrderStatus, AnchorTokenConfig, AnchorTransferOrder, Pubkey},
+    indexer::{evm_address, parse_amount, IndexedOrder},
+    relayer::SolanaRpc,
+};
+use solana_evm_bridge_client::pda;
+
+/// The Solana side of a token pair.
+pub struct PairSnapshot {
+    pub mint: [u8; 32],
+    pub evm_token: [u8; 20],
+    pub vault_balance: u64,
+    pub total_locked: u64,
+    /// Orders locked for this pair, by id.
+    pub orders: Vec<AnchorTransferOrder>,
+}
+
+/// A way the books of a token pair disagree.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Discrepancy {
+    /// The vault does not hold `total_locked`.
+    VaultBalance {
+        vault_balance: u64,
+        total_locked: u64,
+    },
+    /// `total_locked` is not the sum of the pending orders.
+    TotalLocked { total_locked: u64, outstanding: u64 },
+    /// Released on the EVM bridge in the pair's token, but no order with the id was locked.
+    UnbackedRelease { orders: Vec<u64> },
+    /// Released on the EVM bridge in another token than the pair's.
+    WrongToken { orders: Vec<u64> },
+    /// Released on the EVM bridge for another amount than was locked.
+    WrongAmount { orders: Vec<u64> },
+    /// Released on the EVM bridge and unlocked from the vault as well.
+    DoubleRelease { orders: Vec<u64> },
+}
+
+/// The reconciliation of one token pair.
+#[derive(Clone, Debug, Serialize)]
+pub struct PairReport {
+    pub mint: String,
+    pub evm_token: String,
+    pub vault_balance: u64,
+    pub total_locked: u64,
+    /// Sum of the pending orders.
+    pub outstanding: u64,
+    pub pending_orders: u64,
+    /// Paid out on the EVM bridge for orders of the pair, relayer fees included.
+    pub released: String,
+    pub released_orders: u64,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+/// The reconciliation of every token pair.
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditReport {
+    pub pairs: Vec<PairReport>,
+    /// Solana orders released on the EVM bridge in a token no pair maps to.
+    pub unknown_releases: Vec<u64>,
+}
+
+impl AuditReport {
+    pub fn is_balanced(&self) -> bool {
+        self.unknown_releases.is_empty()
+            && self.pairs.iter().all(|pair| pair.discrepancies.is_empty())
+    }
+}
+
+/// Read every token pair of the program with its vault balance and orders.
+pub fn snapshot(solana: &SolanaRpc) -> anyhow::Result<Vec<PairSnapshot>> {
+    let mut orders: HashMap<[u8; 32], Vec<AnchorTransferOrder>> = HashMap::new();
+    for (_, order) in solana.program_accounts::<AnchorTransferOrder>()? {
+        orders
+            .entry(order.token_config.to_bytes())
+            .or_default()
+            .push(order);
+    }
+
+    let mut pairs = solana
+        .program_accounts::<AnchorTokenConfig>()?
+        .into_iter()
+        .map(|(address, config)| {
+            let program_id = Pubkey::new_from_array(*solana.program_id());
+            let vault = pda::program_vault(&config.solana_mint, &program_id)
+                .0
+                .to_bytes();
+            let vault_balance = solana
+                .account(&vault)
+                .and_then(|data| token_amount(&data))
+                .with_context(|| format!("failed to read the vault of {}", config.solana_mint))?;
+            let mut orders = orders.remove(&address).unwrap_or_default();
+            orders.sort_by_key(|order| order.order_id);
+            Ok(PairSnapshot {
+                mint: config.solana_mint.to_bytes(),
+                evm_token: config.evm_token,
+                vault_balance,
+                total_locked: config.total_locked,
+                orders,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    pairs.sort_by_key(|pair| pair.mint);
+    Ok(pairs)
+}
+
+/// The `amount` of an SPL token account, which follows the mint and the owner.
+fn token_amount(data: &[u8]) -> anyhow::Result<u64> {
+    let amount = data.get(64..72).context("not a token account")?;
+    Ok(u64::from_le_bytes(amount.try_into().expect("8 bytes")))
+}
+
+/// Orders of one pair that failed a release check.
+#[derive(Default)]
+struct Findings {
+    released: U256,
+    released_orders: u64,
+    unbacked: Vec<u64>,
+    wrong_token: Vec<u64>,
+    wrong_amount: Vec<u64>,
+    double_release: Vec<u64>,
+}
+
+/// Reconcile the token pairs with `releases`, the Solana orders the indexer saw unlocked on the
+/// EVM bridge.
+pub fn audit(pairs: &[PairSnapshot], releases: &[IndexedOrder]) -> anyhow::Result<AuditReport> {
+    // Order ids are assigned by the bridge, so each belongs to one pair.
+    let locked: HashMap<u64, (usize, &AnchorTransferOrder)> = pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(index, pair)| {
+            pair.orders
+                .iter()
+                .map(move |order| (order.order_id, (index, order)))
+        })
+        .collect();
+    let tokens: Vec<String> = pairs
+        .iter()
+        .map(|pair| evm_address(&pair.evm_token))
+        .collect();
+
+    let mut findings: Vec<Findings> = pairs.iter().map(|_| Findings::default()).collect();
+    let mut unknown_releases = Vec::new();
+    for order in releases {
+        let Some(release) = &order.destination else {
+            continue;
+        };
+        let total = parse_amount(&release.amount)? + parse_amount(&release.relayer_fee)?;
+        let order_id = order.order_id;
+        match locked.get(&order_id) {
+            Some(&(index, locked)) => {
+                let found = &mut findings[index];
+                found.released += total;
+                found.released_orders += 1;
+                if release.token != tokens[index] {
+                    found.wrong_token.push(order_id);
+                }
+                if total != U256::from(locked.amount) {
+                    found.wrong_amount.push(order_id);
+                }
+                if locked.status == AnchorOrderStatus::Completed {
+                    found.double_release.push(order_id);
+                }
+            }
+            None => match tokens.iter().position(|token| *token == release.token) {
+                Some(index) => {
+                    let found = &mut findings[index];
+                    found.released += total;
+                    found.released_orders += 1;
+                    found.unbacked.push(order_id);
+                }
+                None => unknown_releases.push(order_id),
+            },
+        }
+    }
+
+    let pairs = pairs
+        .iter()
+        .zip(findings)
+        .map(|(pair, found)| {
+            let pending: Vec<&AnchorTransferOrder> = pair
+                .orders
+                .iter()
+                .filter(|order| order.status == AnchorOrderStatus::Pending)
+                .collect();
+            let outstanding = pending.iter().map(|order| order.amount).sum();
+
+            let mut discrepancies = Vec::new();
+            if pair.vault_balance != pair.total_locked {
+                discrepancies.push(Discrepancy::VaultBalance {
+                    vault_balance: pair.vault_balance,
+                    total_locked: pair.total_locked,
+                });
+            }
+            if outstanding != pair.total_locked {
+                discrepancies.push(Discrepancy::TotalLocked {
+                    total_locked: pair.total_locked,
+                    outstanding,
+                });
+            }
+            if !found.unbacked.is_empty() {
+                discrepancies.push(Discrepancy::UnbackedRelease {
+                    orders: found.unbacked,
+                });
+            }
+            if !found.wrong_token.is_empty() {
+                discrepancies.push(Discrepancy::WrongToken {
+                    orders: found.wrong_token,
+                });
+            }
+            if !found.wrong_amount.is_empty() {
+                discrepancies.push(Discrepancy::WrongAmount {
+                    orders: found.wrong_amount,
+                });
+            }
+            if !found.double_release.is_empty() {
+                discrepancies.push(Discrepancy::DoubleRelease {
+                    orders: found.double_release,
+                });
+            }
+
+            PairReport {
+                mint: bs58::encode(pair.mint).into_string(),
+                evm_token: evm_address(&pair.evm_token),
+                vault_balance: pair.vault_balance,
+                total_locked: pair.total_locked,
+                outstanding,
+                pending_orders: pending.len() as u64,
+                released: found.released.to_string(),
+                released_orders: found.released_orders,
+                discrepancies,
+            }
+        })
+        .collect();
+    Ok(AuditReport {
+        pairs,
+        unknown_releases,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::{Chain, DestinationLeg, OrderState};
+
+    const TOKEN: [u8; 20] = [0x7c; 20];
+
+    fn order(order_id: u64, amount: u64, status: AnchorOrderStatus) -> AnchorTransferOrder {
+        AnchorTransferOrder {
+            order_id,
+            user: Pubkey::new_from_array([0x11; 32]),
+            status,
+            token_config: Pubkey::new_from_array([0x22; 32]),
+            source_chain: 0,
+            amount,
+            recipient: [0x33; 20],
+            relayer_fee: 1_000,
+            created_slot: 100,
+            proof_hash: [0; 32],
+            completed_by: Pubkey::default(),
+            completed_at: 0,
+            claimed_by: Pubkey::default(),
+            claim_expiry: 0,
+            claim_bond: 0,
+        }
+    }
+
+    fn release(order_id: u64, token: [u8; 20], amount: u64, relayer_fee: u64) -> IndexedOrder {
+        IndexedOrder {
+            source_chain: Chain::Solana,
+            order_id,
+            state: OrderState::Completed,
+            source: None,
+            destination: Some(DestinationLeg {
+                token: evm_address(&token),
+                amount: amount.to_string(),
+                relayer_fee: relayer_fee.to_string(),
+                relayer: evm_address(&[0xf3; 20]),
+                tx: format!("0x{}", "ab".repeat(32)),
+                block: 210,
+                time: None,
+            }),
+        }
+    }
+
+    fn pair(
+        vault_balance: u64,
+        total_locked: u64,
+        orders: Vec<AnchorTransferOrder>,
+    ) -> PairSnapshot {
+        PairSnapshot {
+            mint: [0x44; 32],
+            evm_token: TOKEN,
+            vault_balance,
+            total_locked,
+            orders,
+        }
+    }
+
+    #[test]
+    fn test_balanced_pair() {
+        let pairs = [pair(
+            1_499_000,
+            1_499_000,
+            vec![
+                order(1, 999_000, AnchorOrderStatus::Pending),
+                order(2, 500_000, AnchorOrderStatus::Pending),
+            ],
+        )];
+        let report = audit(&pairs, &[release(1, TOKEN, 949_000, 50_000)]).unwrap();
+
+        assert!(report.is_balanced());
+        let pair = &report.pairs[0];
+        assert_eq!(pair.mint, bs58::encode([0x44; 32]).into_string());
+        assert_eq!((pair.outstanding, pair.pending_orders), (1_499_000, 2));
+        assert_eq!(
+            (pair.released.as_str(), pair.released_orders),
+            ("999000", 1)
+        );
+    }
+
+    #[test]
+    fn test_reports_offending_orders() {
+        let pairs = [pair(
+            1_000_000,
+            1_500_000,
+            vec![
+                order(1, 999_000, AnchorOrderStatus::Pending),
+                order(2, 500_000, AnchorOrderStatus::Completed),
+                order(3, 200_000, AnchorOrderStatus::Pending),
+                order(4, 300_000, AnchorOrderStatus::Pending),
+            ],
+        )];
+        let releases = [
+            release(1, TOKEN, 949_000, 50_000),
+            release(2, TOKEN, 450_000, 50_000),
+            release(3, [0x99; 20], 150_000, 50_000),
+            release(4, TOKEN, 300_000, 50_000),
+            release(9, TOKEN, 100_000, 0),
+            release(10, [0x99; 20], 100_000, 0),
+        ];
+        let report = audit(&pairs, &releases).unwrap();
+
+        assert!(!report.is_balanced());
+        assert_eq!(report.unknown_releases, vec![10]);
+        let pair = &report.pairs[0];
+        assert_eq!(pair.outstanding, 1_499_000);
+        assert_eq!(pair.released_orders, 5);
+        assert_eq!(
+            pair.discrepancies,
+            vec![
+                Discrepancy::VaultBalance {
+                    vault_balance: 1_000_000,
+                    total_locked: 1_500_000,
+                },
+                Discrepancy::TotalLocked {
+                    total_locked: 1_500_000,
+                    outstanding: 1_499_000,
+                },
+                Discrepancy::UnbackedRelease { orders: vec![9] },
+                Discrepancy::WrongToken { orders: vec![3] },
+                Discrepancy::WrongAmount { orders: vec![4] },
+                Discrepancy::DoubleRelease { orders: vec![2] },
+            ]
+        );
+    }
+}
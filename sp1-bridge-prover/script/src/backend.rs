@@ -0,0 +1,474 @@
+//! Pluggable proving backends.
+//!
+//! Binaries talk to a [`ProofBackend`] instead of constructing an SP1 prover themselves, so the
+//! same code runs against instant mock proofs in CI, local CPU proving on a workstation, or a
+//! remote prover service in production. The backend is chosen at runtime by [`backend_from_env`],
+//! or by the `[prover]` section of the [configuration](crate::config) with [`backend_from_config`]:
+//!
+//! - `BRIDGE_PROVER_BACKENDS`: comma separated fallback order of `mock`, `cpu`, `env` and
+//!   `remote` (default `env`, which follows `SP1_PROVER` like `ProverClient::from_env`).
+//! - `BRIDGE_PROVER_URL`: base URL of the remote prover.
+//! - `BRIDGE_PROVER_TIMEOUT_SECS`: how long to wait for a remote proof (default 3600).
+//! - `BRIDGE_PROVER_RETRIES`: retries per backend on transient failures (default 2).
+//! - `BRIDGE_PROVER_RETRY_DELAY_SECS`: pause between retries (default 5).
+//! - `BRIDGE_PROOF_CACHE_DIR`: reuse proofs from this directory, see [`crate::cache`].
+//! - `BRIDGE_PROOF_CACHE_MAX_MB`: size limit of the proof cache (default 1024).
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sp1_prover::components::CpuProverComponents;
+use sp1_sdk::{
+    CpuProver, EnvProver, ExecutionReport, Prover, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1PublicValues, SP1Stdin, SP1VerifyingKey,
+};
+
+use crate::{
+    artifact::bincode_base64,
+    cache::{CachedBackend, ProofCache},
+    config::ProverConfig,
+    ProofMode, AGGREGATION_ELF, BRIDGE_VERIFY_ELF,
+};
+
+/// A guest program known to both the client and the remote prover.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Program {
+    /// Name the remote prover knows the program by.
+    pub name: &'static str,
+    pub elf: &'static [u8],
+}
+
+/// The bridge order verification guest.
+pub const BRIDGE_PROGRAM: Program = Program {
+    name: "bridge",
+    elf: BRIDGE_VERIFY_ELF,
+};
+
+/// The proof aggregation guest.
+pub const AGGREGATION_PROGRAM: Program = Program {
+    name: "aggregation",
+    elf: AGGREGATION_ELF,
+};
+
+impl Program {
+    /// Look up a program by its name.
+    pub fn by_name(name: &str) -> Option<Program> {
+        [BRIDGE_PROGRAM, AGGREGATION_PROGRAM]
+            .into_iter()
+            .find(|program| program.name == name)
+    }
+}
+
+/// Something that can set up, execute, prove and verify the bridge guests.
+pub trait ProofBackend: Send + Sync {
+    /// Name used in logs and error messages.
+    fn name(&self) -> &str;
+
+    /// The proving and verifying keys of a program, cached after the first call.
+    fn keys(&self, program: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)>;
+
+    fn vkey(&self, program: &Program) -> SP1VerifyingKey {
+        self.keys(program).1.clone()
+    }
+
+    fn execute(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)>;
+
+    fn prove(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues>;
+
+    fn verify(&self, proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> anyhow::Result<()>;
+}
+
+/// A failure worth retrying on the same backend, such as a network error or a timeout.
+#[derive(Debug)]
+pub struct TransientError(pub String);
+
+impl fmt::Display for TransientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TransientError {}
+
+type ProverFactory = fn() -> Box<dyn Prover<CpuProverComponents>>;
+
+/// An in-process SP1 prover. The prover is only constructed when first used.
+pub struct LocalBackend {
+    name: &'static str,
+    factory: ProverFactory,
+    prover: OnceLock<Box<dyn Prover<CpuProverComponents>>>,
+    keys: Mutex<HashMap<&'static str, Arc<(SP1ProvingKey, SP1VerifyingKey)>>>,
+}
+
+impl LocalBackend {
+    fn new(name: &'static str, factory: ProverFactory) -> Self {
+        Self {
+            name,
+            factory,
+            prover: OnceLock::new(),
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// SP1 mock prover: executes the guest and returns an unchecked proof instantly.
+    pub fn mock() -> Self {
+        Self::new("mock", || Box::new(CpuProver::mock()))
+    }
+
+    /// Local CPU proving.
+    pub fn cpu() -> Self {
+        Self::new("cpu", || Box::new(CpuProver::new()))
+    }
+
+    /// Whatever `SP1_PROVER` selects, like `ProverClient::from_env`.
+    pub fn env() -> Self {
+        Self::new("env", || Box::new(EnvProver::new()))
+    }
+
+    fn prover(&self) -> &dyn Prover<CpuProverComponents> {
+        self.prover.get_or_init(self.factory).as_ref()
+    }
+}
+
+impl ProofBackend for LocalBackend {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn keys(&self, program: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)> {
+        let mut keys = self.keys.lock().unwrap();
+        keys.entry(program.name)
+            .or_insert_with(|| Arc::new(self.prover().setup(program.elf)))
+            .clone()
+    }
+
+    fn execute(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        self.prover().execute(program.elf, stdin)
+    }
+
+    fn prove(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        let keys = self.keys(program);
+        self.prover().prove(&keys.0, stdin, mode.into())
+    }
+
+    fn verify(&self, proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> anyhow::Result<()> {
+        Ok(self.prover().verify(proof, vk)?)
+    }
+}
+
+/// State of a job on the remote prover.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Executing,
+    Proving,
+    Done,
+    Failed,
+}
+
+/// Body of `POST /jobs` on the remote prover.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteJobRequest {
+    pub program: String,
+    pub mode: ProofMode,
+    #[serde(with = "bincode_base64")]
+    pub stdin: SP1Stdin,
+}
+
+/// Response of `POST /jobs`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteJobCreated {
+    pub id: String,
+}
+
+/// Response of `GET /jobs/{id}`. Other fields sent by the service are ignored.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteJob {
+    pub id: String,
+    pub status: JobStatus,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default, with = "bincode_base64::option")]
+    pub proof: Option<SP1ProofWithPublicValues>,
+}
+
+/// A prover service reached over HTTP: jobs are submitted with `POST {url}/jobs` and polled with
+/// `GET {url}/jobs/{id}` until they are done. Setup, execution and verification stay local.
+pub struct RemoteBackend {
+    url: String,
+    client: reqwest::blocking::Client,
+    timeout: Duration,
+    poll_interval: Duration,
+    local: LocalBackend,
+}
+
+impl RemoteBackend {
+    /// `timeout` bounds the whole job, from submission until the proof is returned.
+    pub fn new(url: impl Into<String>, timeout: Duration, local: LocalBackend) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            client: reqwest::blocking::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("failed to build HTTP client"),
+            timeout,
+            poll_interval: Duration::from_secs(5),
+            local,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    fn submit(&self, request: &RemoteJobRequest) -> anyhow::Result<String> {
+        let response = self
+            .client
+            .post(format!("{}/jobs", self.url))
+            .json(request)
+            .send()
+            .map_err(|e| TransientError(format!("failed to submit job: {e}")))?;
+        let created: RemoteJobCreated = parse_response(response)?;
+        Ok(created.id)
+    }
+
+    fn poll(&self, id: &str) -> anyhow::Result<RemoteJob> {
+        let response = self
+            .client
+            .get(format!("{}/jobs/{id}", self.url))
+            .send()
+            .map_err(|e| TransientError(format!("failed to poll job {id}: {e}")))?;
+        parse_response(response)
+    }
+}
+
+/// Decode a JSON response; server errors and throttling are transient, other errors are not.
+fn parse_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::blocking::Response,
+) -> anyhow::Result<T> {
+    let status = response.status();
+    if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(TransientError(format!("remote prover returned {status}")).into());
+    }
+    anyhow::ensure!(
+        status.is_success(),
+        "remote prover returned {status}: {}",
+        response.text().unwrap_or_default()
+    );
+    response
+        .json()
+        .context("invalid response from remote prover")
+}
+
+impl ProofBackend for RemoteBackend {
+    fn name(&self) -> &str {
+        "remote"
+    }
+
+    fn keys(&self, program: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)> {
+        self.local.keys(program)
+    }
+
+    fn execute(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        self.local.execute(program, stdin)
+    }
+
+    fn prove(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        let deadline = Instant::now() + self.timeout;
+        let id = self.submit(&RemoteJobRequest {
+            program: program.name.to_string(),
+            mode,
+            stdin: stdin.clone(),
+        })?;
+        tracing::info!("submitted remote job {id} to {}", self.url);
+
+        loop {
+            let job = self.poll(&id)?;
+            match job.status {
+                JobStatus::Done => {
+                    return job
+                        .proof
+                        .with_context(|| format!("remote job {id} finished without a proof"));
+                }
+                JobStatus::Failed => {
+                    anyhow::bail!("remote job {id} failed: {}", job.error.unwrap_or_default())
+                }
+                _ => {}
+            }
+            if Instant::now() >= deadline {
+                return Err(TransientError(format!(
+                    "remote job {id} did not finish within {:?}",
+                    self.timeout
+                ))
+                .into());
+            }
+            thread::sleep(self.poll_interval);
+        }
+    }
+
+    fn verify(&self, proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> anyhow::Result<()> {
+        self.local.verify(proof, vk)
+    }
+}
+
+/// Tries backends in order, retrying transient failures on each before moving to the next. Fails
+/// with a [`TransientError`] if every backend only failed transiently.
+///
+/// Setup, execution and verification use the first backend.
+pub struct FallbackBackend {
+    backends: Vec<Box<dyn ProofBackend>>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl FallbackBackend {
+    pub fn new(backends: Vec<Box<dyn ProofBackend>>, retries: u32, retry_delay: Duration) -> Self {
+        assert!(!backends.is_empty(), "at least one backend is required");
+        Self {
+            backends,
+            retries,
+            retry_delay,
+        }
+    }
+
+    fn primary(&self) -> &dyn ProofBackend {
+        self.backends[0].as_ref()
+    }
+}
+
+impl ProofBackend for FallbackBackend {
+    fn name(&self) -> &str {
+        self.primary().name()
+    }
+
+    fn keys(&self, program: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)> {
+        self.primary().keys(program)
+    }
+
+    fn execute(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        self.primary().execute(program, stdin)
+    }
+
+    fn prove(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        let mut errors = Vec::new();
+        // The chain as a whole only failed transiently if every backend did.
+        let mut all_transient = true;
+        for backend in &self.backends {
+            for attempt in 0..=self.retries {
+                match backend.prove(program, stdin, mode) {
+                    Ok(proof) => return Ok(proof),
+                    Err(e) => {
+                        let transient = e.is::<TransientError>();
+                        all_transient &= transient;
+                        tracing::warn!(
+                            "{} backend failed (attempt {}): {e:#}",
+                            backend.name(),
+                            attempt + 1
+                        );
+                        errors.push(format!("{}: {e:#}", backend.name()));
+                        if !transient {
+                            break;
+                        }
+                        if attempt < self.retries {
+                            thread::sleep(self.retry_delay);
+                        }
+                    }
+                }
+            }
+        }
+        let error = format!("all proving backends failed: {}", errors.join("; "));
+        if all_transient {
+            return Err(TransientError(error).into());
+        }
+        anyhow::bail!(error)
+    }
+
+    fn verify(&self, proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> anyhow::Result<()> {
+        self.primary().verify(proof, vk)
+    }
+}
+
+/// Build the backend chain described by the `BRIDGE_PROVER_*` environment variables.
+pub fn backend_from_env() -> anyhow::Result<Box<dyn ProofBackend>> {
+    let mut config = ProverConfig::default();
+    config.apply_env()?;
+    backend_from_config(&config)
+}
+
+/// Build the backend chain described by the `[prover]` section of the configuration.
+pub fn backend_from_config(config: &ProverConfig) -> anyhow::Result<Box<dyn ProofBackend>> {
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let retry_delay = Duration::from_secs(config.retry_delay_secs);
+
+    let mut backends: Vec<Box<dyn ProofBackend>> = Vec::new();
+    for name in config.backend_names() {
+        backends.push(match name {
+            "mock" => Box::new(LocalBackend::mock()),
+            "cpu" => Box::new(LocalBackend::cpu()),
+            "env" => Box::new(LocalBackend::env()),
+            "remote" => {
+                let url = config
+                    .url
+                    .clone()
+                    .context("BRIDGE_PROVER_URL is required for the remote backend")?;
+                Box::new(RemoteBackend::new(url, timeout, LocalBackend::cpu()))
+            }
+            other => anyhow::bail!("unknown proving backend: {other}"),
+        });
+    }
+    anyhow::ensure!(!backends.is_empty(), "BRIDGE_PROVER_BACKENDS is empty");
+
+    let backend = Box::new(FallbackBackend::new(backends, config.retries, retry_delay));
+    match &config.cache_dir {
+        Some(dir) => Ok(Box::new(CachedBackend::new(
+            backend,
+            ProofCache::open(dir, config.cache_max_mb * 1024 * 1024)?,
+        ))),
+        None => Ok(backend),
+    }
+}
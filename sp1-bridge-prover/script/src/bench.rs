@@ -0,0 +1,235 @@
+//! Cycle-count benchmarks of the bridge guest over parameterised workloads.
+//!
+//! A workload fixes the Merkle depth, the number of orders executed back to back and the hash
+//! function of the state tree. Results hold the total cycles, the cycles of each phase marked
+//! with `cycle-tracker-report` in the guest and the syscall counts, and can be compared against a
+//! previously saved baseline.
+
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::Context;
+use fibonacci_lib::{
+    bridge::{hash_order, MerkleProof, TransferOrder},
+    hash::HashFunction,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::ExecutionReport;
+
+use crate::input::{demo_order, BridgeInput};
+
+/// One benchmark workload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Workload {
+    /// Hash function of the order leaves and Merkle nodes.
+    pub hash: HashFunction,
+    /// Number of siblings in the Merkle proof.
+    pub depth: usize,
+    /// Number of orders executed.
+    pub batch: usize,
+}
+
+impl Workload {
+    /// The guest inputs of the workload, one per order, each with a proof of `depth` siblings.
+    ///
+    /// The guest picks the hash function from the source chain, so orders come from Solana for
+    /// SHA-256 and from the EVM for Keccak-256.
+    pub fn inputs(&self) -> Vec<BridgeInput> {
+        let siblings = bench_siblings(self.depth);
+        let source_chain = match self.hash {
+            HashFunction::Sha256 => 0,
+            HashFunction::Keccak256 => 1,
+        };
+        (0..self.batch as u64)
+            .map(|i| {
+                let order = TransferOrder {
+                    order_id: i + 1,
+                    source_chain,
+                    ..demo_order()
+                };
+                let leaf = hash_order(&order);
+                let merkle_proof = MerkleProof::from_siblings(self.hash, leaf, siblings.clone());
+                BridgeInput {
+                    order,
+                    merkle_proof,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Deterministic siblings for a proof of the given depth.
+pub fn bench_siblings(depth: usize) -> Vec<[u8; 32]> {
+    (0..depth)
+        .map(|i| Sha256::digest(format!("sibling{}", i + 1)).into())
+        .collect()
+}
+
+/// Measurements of one workload, summed over all orders of the batch.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub hash: String,
+    pub depth: usize,
+    pub batch: usize,
+    pub total_cycles: u64,
+    pub cycles_per_order: u64,
+    pub total_syscalls: u64,
+    /// Cycles per guest phase.
+    pub phases: BTreeMap<String, u64>,
+    /// Invocations per syscall, zero counts omitted.
+    pub syscalls: BTreeMap<String, u64>,
+}
+
+impl BenchResult {
+    pub fn new(workload: &Workload) -> Self {
+        Self {
+            hash: workload.hash.to_string(),
+            depth: workload.depth,
+            batch: workload.batch,
+            ..Default::default()
+        }
+    }
+
+    /// Add the report of one guest execution.
+    pub fn record(&mut self, report: &ExecutionReport) {
+        self.total_cycles += report.total_instruction_count();
+        self.total_syscalls += report.total_syscall_count();
+        self.cycles_per_order = self.total_cycles / self.batch.max(1) as u64;
+
+        for (phase, cycles) in &report.cycle_tracker {
+            *self.phases.entry(phase.clone()).or_default() += cycles;
+        }
+        for (code, count) in report.syscall_counts.iter() {
+            if *count > 0 {
+                *self.syscalls.entry(code.to_string()).or_default() += count;
+            }
+        }
+    }
+
+    fn key(&self) -> (&str, usize, usize) {
+        (&self.hash, self.depth, self.batch)
+    }
+}
+
+/// The results of one benchmark run.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub sp1_version: String,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchReport {
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write benchmark report {}", path.display()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read benchmark report {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse benchmark report {}", path.display()))
+    }
+
+    /// Compare every workload that also appears in `baseline`.
+    pub fn compare(&self, baseline: &BenchReport) -> Vec<Comparison> {
+        self.results
+            .iter()
+            .filter_map(|current| {
+                let base = baseline
+                    .results
+                    .iter()
+                    .find(|base| base.key() == current.key())?;
+                Some(Comparison {
+                    hash: current.hash.clone(),
+                    depth: current.depth,
+                    batch: current.batch,
+                    baseline_cycles: base.total_cycles,
+                    current_cycles: current.total_cycles,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Total cycles of a workload against the baseline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    pub hash: String,
+    pub depth: usize,
+    pub batch: usize,
+    pub baseline_cycles: u64,
+    pub current_cycles: u64,
+}
+
+impl Comparison {
+    /// Relative change in percent, positive when the guest got slower.
+    pub fn change_percent(&self) -> f64 {
+        if self.baseline_cycles == 0 {
+            return 0.0;
+        }
+        (self.current_cycles as f64 - self.baseline_cycles as f64) * 100.0
+            / self.baseline_cycles as f64
+    }
+
+    /// Whether the change exceeds `tolerance` percent.
+    pub fn is_regression(&self, tolerance: f64) -> bool {
+        self.change_percent() > tolerance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(depth: usize, total_cycles: u64) -> BenchResult {
+        BenchResult {
+            hash: "sha256".to_string(),
+            depth,
+            batch: 1,
+            total_cycles,
+            cycles_per_order: total_cycles,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_workload_inputs_verify() {
+        for hash in [HashFunction::Sha256, HashFunction::Keccak256] {
+            let workload = Workload {
+                hash,
+                depth: 32,
+                batch: 3,
+            };
+            let inputs = workload.inputs();
+
+            assert_eq!(inputs.len(), 3);
+            for input in &inputs {
+                assert_eq!(HashFunction::for_chain(input.order.source_chain), hash);
+                assert_eq!(input.merkle_proof.proof.len(), 32);
+                assert_eq!(input.merkle_proof.leaf, hash_order(&input.order));
+                assert!(input.merkle_proof.verify(hash));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_flags_regressions() {
+        let baseline = BenchReport {
+            sp1_version: "v5.0.0".to_string(),
+            results: vec![result(1, 1000), result(8, 2000)],
+        };
+        let current = BenchReport {
+            sp1_version: "v5.0.0".to_string(),
+            results: vec![result(1, 1020), result(8, 2500), result(16, 4000)],
+        };
+
+        let comparisons = current.compare(&baseline);
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].is_regression(5.0));
+        assert!(comparisons[1].is_regression(5.0));
+        assert_eq!(comparisons[1].change_percent(), 25.0);
+    }
+}
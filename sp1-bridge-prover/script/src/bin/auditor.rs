@@ -0,0 +1,126 @@
+//! Reconciliation of the Solana vaults against the orders on both bridges.
+//!
+//! The auditor reads the EVM releases from the database of a running indexer. You can audit a
+//! local test validator using:
+//! ```shell
+//! cargo run --release --bin auditor -- --database indexer.sqlite
+//! ```
+//! It exits with status 1 when any token pair is out of balance, or when a token pair listed in the
+//! `--config` file is not registered on the bridge.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use fibonacci_script::{
+    anchor::parse_pubkey,
+    audit::{audit, snapshot},
+    config::Config,
+    indexer::{Chain, IndexerStore},
+    relayer::SolanaRpc,
+};
+
+/// The arguments for the command.
+#[derive(Parser, Debug)]
+#[command(
+    name = "auditor",
+    author,
+    version,
+    about = "Reconcile bridge vaults with the orders on both chains"
+)]
+struct Args {
+    /// Configuration file of the chains and token pairs.
+    #[arg(long, env = "BRIDGE_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Solana JSON-RPC endpoint, overrides `solana.rpc_url`.
+    #[arg(long)]
+    solana_rpc: Option<String>,
+
+    /// Address of the Anchor bridge program, overrides `solana.program_id`.
+    #[arg(long, value_parser = parse_pubkey)]
+    program_id: Option<[u8; 32]>,
+
+    /// Commitment level the accounts are read at; use the one the indexer reads at. Overrides
+    /// `solana.finality`.
+    #[arg(long)]
+    commitment: Option<String>,
+
+    /// SQLite database of the indexer.
+    #[arg(long, default_value = "indexer.sqlite")]
+    database: PathBuf,
+
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+
+    let store = IndexerStore::open(&args.database)?;
+    let solana = SolanaRpc::new(
+        args.solana_rpc.unwrap_or(config.solana.rpc_url),
+        args.program_id.unwrap_or(config.solana.program_id),
+        args.commitment.unwrap_or(config.solana.finality),
+    );
+    let pairs = snapshot(&solana)?;
+    let report = audit(&pairs, &store.unlocked(Chain::Solana)?)?;
+
+    // Token pairs of the configuration missing on chain, or registered with another EVM token.
+    let mut misconfigured = Vec::new();
+    for pair in &config.token_pairs {
+        let registered = pairs.iter().find(|snapshot| snapshot.mint == pair.mint);
+        if registered.map(|snapshot| snapshot.evm_token) != Some(pair.evm_token) {
+            misconfigured.push(format!(
+                "{} ↔ 0x{}",
+                bs58::encode(pair.mint).into_string(),
+                hex::encode(pair.evm_token)
+            ));
+        }
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for pair in &report.pairs {
+            let mark = if pair.discrepancies.is_empty() {
+                "✅"
+            } else {
+                "❌"
+            };
+            println!("{mark} {} ↔ {}", pair.mint, pair.evm_token);
+            println!("   Vault balance: {}", pair.vault_balance);
+            println!("   Total locked: {}", pair.total_locked);
+            println!(
+                "   Outstanding: {} in {} pending order(s)",
+                pair.outstanding, pair.pending_orders
+            );
+            println!(
+                "   Released on EVM: {} in {} order(s)",
+                pair.released, pair.released_orders
+            );
+            for discrepancy in &pair.discrepancies {
+                println!("   ⚠️  {}", serde_json::to_string(discrepancy)?);
+            }
+        }
+        if !report.unknown_releases.is_empty() {
+            println!(
+                "❌ Released on EVM in a token without a pair: orders {:?}",
+                report.unknown_releases
+            );
+        }
+    }
+    for pair in &misconfigured {
+        eprintln!("❌ Configured token pair {pair} is not registered on the bridge");
+    }
+
+    if !report.is_balanced() || !misconfigured.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,141 @@
+//! Cycle-count benchmark of the bridge guest.
+//!
+//! Executes the guest over every combination of hash function, Merkle depth and batch size, writes
+//! the cycles and syscall counts per phase to JSON and compares them against a baseline:
+//! ```shell
+//! cargo run --release --bin bench -- --save-baseline
+//! cargo run --release --bin bench -- --depths 1,8,16,32 --batches 1,8
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use fibonacci_lib::hash::HashFunction;
+use fibonacci_script::{
+    backend::{LocalBackend, ProofBackend, BRIDGE_PROGRAM},
+    bench::{BenchReport, BenchResult, Workload},
+};
+use sp1_sdk::SP1_CIRCUIT_VERSION;
+
+#[derive(Parser, Debug)]
+#[command(name = "bench", about = "Cycle-count benchmark of the bridge guest")]
+struct Args {
+    /// Hash functions of the state tree to benchmark.
+    #[arg(long, value_delimiter = ',', default_value = "sha256,keccak256")]
+    hashes: Vec<HashFunction>,
+
+    /// Merkle proof depths to benchmark.
+    #[arg(long, value_delimiter = ',', default_value = "1,2,4,8,16,32")]
+    depths: Vec<usize>,
+
+    /// Number of orders executed per workload.
+    #[arg(long, value_delimiter = ',', default_value = "1,4")]
+    batches: Vec<usize>,
+
+    /// Where to write the results.
+    #[arg(long, default_value = "bench-results.json")]
+    output: PathBuf,
+
+    /// Baseline to compare against. Without `--save-baseline` it must exist.
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/bench/baseline.json"))]
+    baseline: PathBuf,
+
+    /// Overwrite the baseline with the results of this run.
+    #[arg(long)]
+    save_baseline: bool,
+
+    /// Allowed increase of the total cycles over the baseline, in percent.
+    #[arg(long, default_value_t = 2.0)]
+    tolerance: f64,
+}
+
+fn main() -> anyhow::Result<()> {
+    sp1_sdk::utils::setup_logger();
+    let args = Args::parse();
+
+    // Checked before running, which takes a while.
+    let baseline = if args.save_baseline {
+        None
+    } else {
+        anyhow::ensure!(
+            args.baseline.exists(),
+            "no baseline at {}, run with --save-baseline to create one",
+            args.baseline.display()
+        );
+        Some(BenchReport::load(&args.baseline)?)
+    };
+
+    let backend = LocalBackend::cpu();
+    let mut report = BenchReport {
+        sp1_version: SP1_CIRCUIT_VERSION.to_string(),
+        results: Vec::new(),
+    };
+
+    for &hash in &args.hashes {
+        for &depth in &args.depths {
+            for &batch in &args.batches {
+                let workload = Workload { hash, depth, batch };
+                let mut result = BenchResult::new(&workload);
+                for input in workload.inputs() {
+                    let (_, execution) = backend
+                        .execute(&BRIDGE_PROGRAM, &input.stdin())
+                        .with_context(|| format!("guest execution failed for {workload:?}"))?;
+                    result.record(&execution);
+                }
+
+                println!(
+                    "⏱️  {} depth {:>2} batch {:>3}: {:>10} cycles ({} per order)",
+                    result.hash, depth, batch, result.total_cycles, result.cycles_per_order
+                );
+                for (phase, cycles) in &result.phases {
+                    println!("      {phase}: {cycles}");
+                }
+                report.results.push(result);
+            }
+        }
+    }
+
+    report.save(&args.output)?;
+    println!("💾 Results written to {}", args.output.display());
+
+    let Some(baseline) = baseline else {
+        if let Some(dir) = args.baseline.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        report.save(&args.baseline)?;
+        println!("💾 Baseline written to {}", args.baseline.display());
+        return Ok(());
+    };
+    let comparisons = report.compare(&baseline);
+    // A workload missing from the baseline would otherwise pass unchecked.
+    anyhow::ensure!(
+        comparisons.len() == report.results.len(),
+        "{} workload(s) are not in {}, run with --save-baseline to add them",
+        report.results.len() - comparisons.len(),
+        args.baseline.display()
+    );
+    let mut regressions = 0;
+    println!("📊 Compared with {}:", args.baseline.display());
+    for comparison in &comparisons {
+        let regressed = comparison.is_regression(args.tolerance);
+        regressions += usize::from(regressed);
+        println!(
+            "   {} {} depth {:>2} batch {:>3}: {:>10} -> {:>10} ({:+.2}%)",
+            if regressed { "❌" } else { "✅" },
+            comparison.hash,
+            comparison.depth,
+            comparison.batch,
+            comparison.baseline_cycles,
+            comparison.current_cycles,
+            comparison.change_percent()
+        );
+    }
+
+    anyhow::ensure!(
+        regressions == 0,
+        "{regressions} workload(s) regressed by more than {}%",
+        args.tolerance
+    );
+    Ok(())
+}
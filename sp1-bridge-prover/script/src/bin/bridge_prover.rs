@@ -0,0 +1,540 @@
+//! Command line tool for executing, proving and verifying bridge orders with SP1.
+//!
+//! You can run this script using the following commands:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- execute
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- prove --mode groth16 --output proof.json
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- verify --proof proof.json
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- vkey --format solana
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- vkey --record v1 --pin
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- export-fixture --system plonk
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- export-solana --proof proof.json
+//! RUST_LOG=info cargo run --release --bin bridge-prover -- aggregate --proof a.json --proof b.json
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::{Parser, Subcommand, ValueEnum};
+use fibonacci_lib::bridge::hash_order;
+use fibonacci_script::{
+    aggregate::{aggregation_stdin, AggregateBundle},
+    artifact::{unix_now, ProofBundle},
+    backend::{backend_from_config, Program, ProofBackend, AGGREGATION_PROGRAM, BRIDGE_PROGRAM},
+    config::Config,
+    decode_public_values,
+    input::{stdin_hash, BridgeInput, InputArgs},
+    print_public_values,
+    snapshot::InputSnapshot,
+    solana::{SolanaProofExport, SolanaProofPayload},
+    vkeys::{check_pinned, registry_path, VkeyEntry, VkeyExport, VkeyRegistry},
+    ProofMode,
+};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{HashableKey, SP1ProofWithPublicValues, SP1VerifyingKey};
+
+/// The arguments for the command.
+#[derive(Parser, Debug)]
+#[command(
+    name = "bridge-prover",
+    author,
+    version,
+    about = "Bridge order verification with SP1"
+)]
+struct Cli {
+    /// Configuration file, for its proving backend.
+    #[arg(long, global = true, env = "BRIDGE_CONFIG")]
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Execute the guest without generating a proof.
+    Execute {
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Write the exact guest inputs to an input snapshot.
+        #[arg(long, value_name = "FILE")]
+        dump_input: Option<PathBuf>,
+    },
+    /// Generate a proof and save it as a proof bundle.
+    Prove {
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[arg(long, value_enum, default_value = "core")]
+        mode: ProofMode,
+
+        #[arg(long, default_value = "proof.json")]
+        output: PathBuf,
+
+        /// Write the exact guest inputs to an input snapshot.
+        #[arg(long, value_name = "FILE")]
+        dump_input: Option<PathBuf>,
+    },
+    /// Re-verify a proof bundle previously saved by `prove`.
+    Verify {
+        #[arg(long, default_value = "proof.json")]
+        proof: PathBuf,
+    },
+    /// Print the verification key of a program and optionally record it in the vkey registry.
+    Vkey {
+        #[arg(long, value_enum, default_value = "solidity")]
+        format: VkeyFormat,
+
+        /// Program whose vkey to print.
+        #[arg(long, default_value = "bridge", value_parser = parse_program)]
+        program: Program,
+
+        /// Record the vkey in the registry under this guest version.
+        #[arg(long, value_name = "VERSION")]
+        record: Option<String>,
+
+        /// Pin the recorded version as the one deployed on chain.
+        #[arg(long, requires = "record")]
+        pin: bool,
+    },
+    /// Aggregate compressed proof bundles into a single proof.
+    Aggregate {
+        /// Compressed proof bundles written by `prove --mode compressed`.
+        #[arg(long = "proof", value_name = "FILE", required = true)]
+        proofs: Vec<PathBuf>,
+
+        #[arg(long, value_enum, default_value = "groth16")]
+        mode: ProofMode,
+
+        #[arg(long, default_value = "aggregate.json")]
+        output: PathBuf,
+    },
+    /// Generate an EVM proof and write a fixture for the Solidity tests.
+    ExportFixture {
+        #[command(flatten)]
+        input: InputArgs,
+
+        #[arg(long, value_enum, default_value = "groth16")]
+        system: ProofSystem,
+
+        /// Directory to write the fixture to, defaults to `contracts/src/fixtures`.
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+    /// Convert a Groth16 proof bundle to the instruction payload verified on Solana.
+    ExportSolana {
+        #[arg(long, default_value = "proof.json")]
+        proof: PathBuf,
+
+        #[arg(long, default_value = "solana-proof.json")]
+        output: PathBuf,
+    },
+}
+
+/// Output formats of the `vkey` command.
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+enum VkeyFormat {
+    /// `bytes32` hash for the Solidity verifier.
+    Solidity,
+    /// `[u8; 32]` constant for the Solana Groth16 verifier.
+    Solana,
+    /// Every format as JSON.
+    Json,
+}
+
+fn parse_program(name: &str) -> Result<Program, String> {
+    Program::by_name(name).ok_or_else(|| format!("unknown program: {name}"))
+}
+
+/// Enum representing the available proof systems
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum ProofSystem {
+    Plonk,
+    Groth16,
+}
+
+/// A fixture that can be used to test the verification of bridge proofs inside Solidity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SP1BridgeProofFixture {
+    order_id: u64,
+    amount: String,
+    recipient: String,
+    state_root: String,
+    vkey: String,
+    public_values: String,
+    proof: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    // Parse the command line arguments.
+    let cli = Cli::parse();
+
+    let config = Config::load(cli.config.as_deref())?;
+    let backend = backend_from_config(&config.prover)?;
+    let backend = backend.as_ref();
+    let registry = &registry_path(&config.prover);
+
+    match cli.command {
+        Command::Execute { input, dump_input } => execute(backend, &input, dump_input.as_deref()),
+        Command::Prove {
+            input,
+            mode,
+            output,
+            dump_input,
+        } => prove(
+            backend,
+            registry,
+            &input,
+            mode,
+            &output,
+            dump_input.as_deref(),
+        ),
+        Command::Verify { proof } => verify(backend, registry, &proof),
+        Command::Vkey {
+            format,
+            program,
+            record,
+            pin,
+        } => vkey(backend, registry, &program, format, record.as_deref(), pin),
+        Command::Aggregate {
+            proofs,
+            mode,
+            output,
+        } => aggregate(backend, registry, &proofs, mode, &output),
+        Command::ExportFixture {
+            input,
+            system,
+            out_dir,
+        } => export_fixture(backend, registry, &input, system, out_dir),
+        Command::ExportSolana { proof, output } => export_solana(&proof, &output),
+    }
+}
+
+fn execute(
+    backend: &dyn ProofBackend,
+    args: &InputArgs,
+    dump_input: Option<&Path>,
+) -> anyhow::Result<()> {
+    let input = args.load()?;
+    if let Some(path) = dump_input {
+        save_snapshot(&input, path)?;
+    }
+    let order_hash = hash_order(&input.order);
+    println!("🔑 Order Hash: 0x{}", hex::encode(order_hash));
+    println!("🌳 Merkle Root: 0x{}", hex::encode(input.merkle_proof.root));
+
+    let (output, report) = backend
+        .execute(&BRIDGE_PROGRAM, &input.stdin())
+        .context("guest execution failed")?;
+    println!("✅ Execution successful!");
+    println!("   Cycles: {}", report.total_instruction_count());
+
+    let (public_values, committed_hash) = decode_public_values(output.as_slice())?;
+    print_public_values(&public_values);
+
+    anyhow::ensure!(
+        committed_hash == order_hash,
+        "committed order hash 0x{} does not match 0x{}",
+        hex::encode(committed_hash),
+        hex::encode(order_hash)
+    );
+    Ok(())
+}
+
+fn prove(
+    backend: &dyn ProofBackend,
+    registry: &Path,
+    args: &InputArgs,
+    mode: ProofMode,
+    output: &Path,
+    dump_input: Option<&Path>,
+) -> anyhow::Result<()> {
+    let input = args.load()?;
+    if let Some(path) = dump_input {
+        save_snapshot(&input, path)?;
+    }
+    let stdin = input.stdin();
+
+    let vk = backend.vkey(&BRIDGE_PROGRAM);
+    check_pinned(registry, &BRIDGE_PROGRAM, &vk)?;
+
+    println!(
+        "🔐 Generating {:?} proof with the {} backend (this may take a while)...",
+        mode,
+        backend.name()
+    );
+    let started_at = unix_now();
+    let proof = backend
+        .prove(&BRIDGE_PROGRAM, &stdin, mode)
+        .context("failed to generate proof")?;
+    println!("✅ Proof generated successfully!");
+
+    backend
+        .verify(&proof, &vk)
+        .context("failed to verify proof")?;
+    println!("✅ Proof verified successfully!");
+
+    let bundle = ProofBundle::new(
+        proof,
+        mode,
+        vk.bytes32_raw(),
+        stdin_hash(&stdin),
+        started_at,
+    )?;
+    bundle.save(output)?;
+    println!("💾 Proof bundle saved to {}", output.display());
+    Ok(())
+}
+
+fn vkey(
+    backend: &dyn ProofBackend,
+    registry: &Path,
+    program: &Program,
+    format: VkeyFormat,
+    record: Option<&str>,
+    pin: bool,
+) -> anyhow::Result<()> {
+    let vk = backend.vkey(program);
+    let export = VkeyExport::new(program, &vk);
+    match format {
+        VkeyFormat::Solidity => println!("0x{}", hex::encode(export.bytes32)),
+        VkeyFormat::Solana => println!("{}", export.solana_const()),
+        VkeyFormat::Json => println!("{}", serde_json::to_string_pretty(&export)?),
+    }
+
+    if let Some(version) = record {
+        let path = registry;
+        let mut registry = VkeyRegistry::load_or_default(path)?;
+        registry.record(VkeyEntry::new(program, version, &vk))?;
+        if pin {
+            registry.pin(program.name, version)?;
+        }
+        registry.save(path)?;
+        eprintln!(
+            "📒 Recorded {} version {version}{} in {}",
+            program.name,
+            if pin { " (pinned)" } else { "" },
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn save_snapshot(input: &BridgeInput, path: &Path) -> anyhow::Result<()> {
+    let snapshot = InputSnapshot::capture(input);
+    snapshot.save(path)?;
+    println!(
+        "📸 Input snapshot 0x{} saved to {}",
+        hex::encode(snapshot.input_hash),
+        path.display()
+    );
+    Ok(())
+}
+
+fn verify(backend: &dyn ProofBackend, registry: &Path, path: &Path) -> anyhow::Result<()> {
+    let bundle = ProofBundle::load(path)?;
+    let metadata = &bundle.metadata;
+    println!("📄 Proof bundle {}", path.display());
+    println!("   Mode: {:?}", metadata.mode);
+    println!("   SP1 Version: {}", metadata.sp1_version);
+    println!("   VKey Hash: 0x{}", hex::encode(metadata.vkey_hash));
+    println!("   Input Hash: 0x{}", hex::encode(metadata.input_hash));
+    println!(
+        "   Proved: {} -> {}",
+        metadata.started_at, metadata.finished_at
+    );
+
+    let vk = backend.vkey(&BRIDGE_PROGRAM);
+    anyhow::ensure!(
+        vk.bytes32_raw() == metadata.vkey_hash,
+        "proof bundle was generated for vkey 0x{}, current program is {}",
+        hex::encode(metadata.vkey_hash),
+        vk.bytes32()
+    );
+    check_pinned(registry, &BRIDGE_PROGRAM, &vk)?;
+    backend
+        .verify(&bundle.proof, &vk)
+        .context("failed to verify proof")?;
+    println!("✅ Proof verified successfully!");
+
+    // Check the bytes submitted on chain with the SDK-free verifier, the same check the relayer
+    // runs before submitting.
+    if let Some(proof_bytes) = &bundle.proof_bytes {
+        let proof_bytes = hex::decode(proof_bytes.trim_start_matches("0x"))?;
+        let system = bridge_verifier::verify_proof(
+            &proof_bytes,
+            bundle.proof.public_values.as_slice(),
+            &metadata.vkey_hash,
+        )
+        .map_err(|e| anyhow::anyhow!("on-chain proof bytes do not verify: {e}"))?;
+        println!("✅ On-chain {system:?} proof bytes verified!");
+    }
+
+    let (public_values, _) = decode_public_values(bundle.proof.public_values.as_slice())?;
+    print_public_values(&public_values);
+    Ok(())
+}
+
+fn aggregate(
+    backend: &dyn ProofBackend,
+    registry: &Path,
+    paths: &[PathBuf],
+    mode: ProofMode,
+    output: &Path,
+) -> anyhow::Result<()> {
+    let bridge_vk = backend.vkey(&BRIDGE_PROGRAM);
+    check_pinned(registry, &BRIDGE_PROGRAM, &bridge_vk)?;
+
+    let mut proofs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let bundle = ProofBundle::load(path)?;
+        anyhow::ensure!(
+            bundle.metadata.mode == ProofMode::Compressed,
+            "{} is a {:?} proof, aggregation needs compressed proofs",
+            path.display(),
+            bundle.metadata.mode
+        );
+        anyhow::ensure!(
+            bundle.metadata.vkey_hash == bridge_vk.bytes32_raw(),
+            "{} was generated for vkey 0x{}, current program is {}",
+            path.display(),
+            hex::encode(bundle.metadata.vkey_hash),
+            bridge_vk.bytes32()
+        );
+        proofs.push(bundle.proof);
+    }
+    let stdin = aggregation_stdin(&bridge_vk, &proofs)?;
+
+    let vk = backend.vkey(&AGGREGATION_PROGRAM);
+    check_pinned(registry, &AGGREGATION_PROGRAM, &vk)?;
+    println!(
+        "🔐 Aggregating {} proofs into a {:?} proof (this may take a while)...",
+        proofs.len(),
+        mode
+    );
+    let started_at = unix_now();
+    let proof = backend
+        .prove(&AGGREGATION_PROGRAM, &stdin, mode)
+        .context("failed to generate aggregate proof")?;
+    backend
+        .verify(&proof, &vk)
+        .context("failed to verify aggregate proof")?;
+    println!("✅ Aggregate proof verified successfully!");
+
+    let bundle = AggregateBundle::new(proof, mode, &proofs, &bridge_vk, &vk, started_at)?;
+    println!("🌳 Orders Root: 0x{}", hex::encode(bundle.orders_root));
+    for order in &bundle.orders {
+        println!(
+            "   Order {} leaf 0x{}",
+            order.order_id,
+            hex::encode(order.leaf)
+        );
+    }
+    bundle.save(output)?;
+    println!("💾 Aggregate bundle saved to {}", output.display());
+    Ok(())
+}
+
+fn export_fixture(
+    backend: &dyn ProofBackend,
+    registry: &Path,
+    args: &InputArgs,
+    system: ProofSystem,
+    out_dir: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let input = args.load()?;
+
+    let vk = backend.vkey(&BRIDGE_PROGRAM);
+    check_pinned(registry, &BRIDGE_PROGRAM, &vk)?;
+
+    // Generate the proof based on the selected proof system.
+    let mode = match system {
+        ProofSystem::Plonk => ProofMode::Plonk,
+        ProofSystem::Groth16 => ProofMode::Groth16,
+    };
+    let proof = backend
+        .prove(&BRIDGE_PROGRAM, &input.stdin(), mode)
+        .context("failed to generate proof")?;
+
+    let fixture = create_proof_fixture(&proof, &vk)?;
+    println!("Verification Key: {}", fixture.vkey);
+    println!("Public Values: {}", fixture.public_values);
+    println!("Proof Bytes: {}", fixture.proof);
+
+    // Save the fixture to a file.
+    let fixture_path = out_dir.unwrap_or_else(|| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../contracts/src/fixtures")
+    });
+    std::fs::create_dir_all(&fixture_path).context("failed to create fixture path")?;
+    let file = fixture_path.join(format!("bridge-{:?}-fixture.json", system).to_lowercase());
+    std::fs::write(&file, serde_json::to_string_pretty(&fixture)?)
+        .context("failed to write fixture")?;
+    println!("💾 Fixture written to {}", file.display());
+    Ok(())
+}
+
+fn export_solana(path: &Path, output: &Path) -> anyhow::Result<()> {
+    let bundle = ProofBundle::load(path)?;
+    let payload = SolanaProofPayload::new(&bundle.proof, bundle.metadata.vkey_hash)
+        .with_context(|| format!("cannot export {}", path.display()))?;
+
+    // The program runs the same pairing check, so catch bad conversions before submitting.
+    let proof_bytes = payload
+        .points()
+        .to_sp1_bytes(bridge_verifier::ProofSystem::Groth16.prefix());
+    bridge_verifier::verify_groth16(
+        &proof_bytes,
+        &payload.public_values,
+        &bundle.metadata.vkey_hash,
+    )
+    .map_err(|e| anyhow::anyhow!("converted proof does not verify: {e}"))?;
+
+    let export = SolanaProofExport::from(&payload);
+    println!(
+        "   Public Inputs: {}, {}",
+        export.public_inputs[0], export.public_inputs[1]
+    );
+    println!(
+        "   Instruction Data: {} bytes",
+        payload.to_instruction_data().len()
+    );
+    export.save(output)?;
+    println!("💾 Solana proof written to {}", output.display());
+    Ok(())
+}
+
+/// Create a fixture for the given proof.
+fn create_proof_fixture(
+    proof: &SP1ProofWithPublicValues,
+    vk: &SP1VerifyingKey,
+) -> anyhow::Result<SP1BridgeProofFixture> {
+    let bytes = proof.public_values.as_slice();
+    let (public_values, _) = decode_public_values(bytes)?;
+
+    Ok(SP1BridgeProofFixture {
+        order_id: public_values.orderId,
+        amount: public_values.amount.to_string(),
+        recipient: format!("0x{}", hex::encode(public_values.recipient)),
+        state_root: format!("0x{}", hex::encode(public_values.stateRoot)),
+        vkey: vk.bytes32().to_string(),
+        public_values: format!("0x{}", hex::encode(bytes)),
+        proof: format!("0x{}", hex::encode(proof.bytes())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+}
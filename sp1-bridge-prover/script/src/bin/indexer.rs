@@ -0,0 +1,115 @@
+//! Indexer of orders on both bridges, with an HTTP query API.
+//!
+//! You can run the indexer against a local test validator and Anvil node using:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin indexer -- --evm-bridge 0x5FbDB2315678afecb367f032d93F642f64180aa3
+//! ```
+//! The chains are read from the file given with `--config`, see `fibonacci_script::config`; the
+//! command line arguments take precedence over it.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use clap::Parser;
+use fibonacci_script::{
+    anchor::parse_pubkey,
+    config::Config,
+    indexer::{Indexer, IndexerConfig, IndexerStore},
+    relayer::{parse_address, EvmRpc, SolanaRpc},
+};
+use tiny_http::Server;
+
+/// The arguments for the command.
+#[derive(Parser, Debug)]
+#[command(
+    name = "indexer",
+    author,
+    version,
+    about = "Cross-chain bridge order indexer"
+)]
+struct Args {
+    /// Address the HTTP API listens on.
+    #[arg(long, default_value = "127.0.0.1:8090")]
+    listen: String,
+
+    /// Configuration file of the chains.
+    #[arg(long, env = "BRIDGE_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Solana JSON-RPC endpoint, overrides `solana.rpc_url`.
+    #[arg(long)]
+    solana_rpc: Option<String>,
+
+    /// Address of the Anchor bridge program, overrides `solana.program_id`.
+    #[arg(long, value_parser = parse_pubkey)]
+    program_id: Option<[u8; 32]>,
+
+    /// Commitment level the Solana history is read at, overrides `solana.finality`.
+    #[arg(long)]
+    commitment: Option<String>,
+
+    /// EVM JSON-RPC endpoint, overrides `evm.rpc_url`.
+    #[arg(long)]
+    evm_rpc: Option<String>,
+
+    /// Address of the `EVMSolanaBridge` contract, overrides `evm.bridge`.
+    #[arg(long, value_parser = parse_address)]
+    evm_bridge: Option<[u8; 20]>,
+
+    /// Blocks a log must be deep, including its own, before it is indexed. Overrides
+    /// `evm.confirmations`.
+    #[arg(long)]
+    evm_confirmations: Option<u64>,
+
+    /// Block the EVM bridge was deployed in, overrides `evm.start_block`.
+    #[arg(long)]
+    evm_start_block: Option<u64>,
+
+    /// SQLite database holding the indexed orders.
+    #[arg(long, default_value = "indexer.sqlite")]
+    database: PathBuf,
+
+    /// Seconds between polls of the two chains.
+    #[arg(long, default_value_t = 2)]
+    poll_interval_secs: u64,
+}
+
+fn main() -> anyhow::Result<()> {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+    let evm_bridge = match args.evm_bridge {
+        Some(bridge) => bridge,
+        None => config.evm.bridge()?,
+    };
+
+    let store = IndexerStore::open(&args.database)?;
+    let program_id = args.program_id.unwrap_or(config.solana.program_id);
+    let solana = SolanaRpc::new(
+        args.solana_rpc.unwrap_or(config.solana.rpc_url),
+        program_id,
+        args.commitment.unwrap_or(config.solana.finality),
+    );
+    let evm = EvmRpc::read_only(args.evm_rpc.unwrap_or(config.evm.rpc_url), evm_bridge)
+        .with_confirmations(args.evm_confirmations.unwrap_or(config.evm.confirmations));
+    evm.check_chain_id(config.evm.chain_id)?;
+    let indexer = Arc::new(Indexer::new(
+        Box::new(solana),
+        Box::new(evm),
+        store,
+        IndexerConfig {
+            program_id,
+            evm_start_block: args.evm_start_block.unwrap_or(config.evm.start_block),
+            poll_interval: Duration::from_secs(args.poll_interval_secs),
+        },
+    ));
+    indexer.spawn_sync();
+
+    let server = Server::http(&args.listen)
+        .map_err(|e| anyhow::anyhow!("failed to listen on {}: {e}", args.listen))?;
+    println!("🚀 Indexer listening on {}", args.listen);
+    indexer.serve(&server);
+    Ok(())
+}
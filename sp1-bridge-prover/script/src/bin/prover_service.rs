@@ -0,0 +1,110 @@
+//! Long-running prover service with a persistent job queue.
+//!
+//! You can run the service using the following command:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin prover-service -- --listen 0.0.0.0:8080 --workers 2
+//! ```
+//! Jobs are proven with the backend of the `[prover]` section of the `--config` file, or the one
+//! selected by the `BRIDGE_PROVER_*` environment variables.
+//! Prometheus metrics are served at `/metrics` on the same address.
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use clap::Parser;
+use fibonacci_script::{
+    backend::{backend_from_config, BRIDGE_PROGRAM},
+    config::Config,
+    service::{JobStore, Service, ServiceConfig},
+    vkeys::{check_pinned, registry_path, require_pinned},
+};
+use sp1_sdk::HashableKey;
+use tiny_http::Server;
+
+/// The arguments for the command.
+#[derive(Parser, Debug)]
+#[command(
+    name = "prover-service",
+    author,
+    version,
+    about = "Bridge prover job service"
+)]
+struct Args {
+    /// Configuration file, for its proving backend.
+    #[arg(long, env = "BRIDGE_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Address the HTTP API listens on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: String,
+
+    /// SQLite database holding the job queue.
+    #[arg(long, default_value = "prover-jobs.sqlite")]
+    database: PathBuf,
+
+    /// Number of jobs proven concurrently.
+    #[arg(long, default_value_t = 1)]
+    workers: usize,
+
+    /// How often a job is attempted before it is marked as failed.
+    #[arg(long, default_value_t = 3)]
+    max_attempts: u32,
+
+    /// Directory of `{order_id}.json` order records, for jobs submitted by order id.
+    #[arg(long, value_name = "DIR")]
+    orders_dir: Option<PathBuf>,
+
+    /// Start even if the vkey registry is missing or pins no bridge version. The proofs may then
+    /// be rejected on chain.
+    #[arg(long)]
+    allow_unpinned_vkey: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+
+    let store = JobStore::open(&args.database)?;
+    let recovered = store.recover()?;
+    if recovered > 0 {
+        tracing::info!("re-queued {recovered} interrupted job(s)");
+    }
+
+    let backend = backend_from_config(&config.prover)?;
+    tracing::info!("setting up the bridge program");
+    let vk = backend.vkey(&BRIDGE_PROGRAM);
+    let registry = registry_path(&config.prover);
+    if args.allow_unpinned_vkey {
+        tracing::warn!("not requiring a pinned vkey in {}", registry.display());
+        check_pinned(&registry, &BRIDGE_PROGRAM, &vk)?;
+    } else {
+        require_pinned(&registry, &BRIDGE_PROGRAM, &vk)?;
+    }
+    let vkey_hash = vk.bytes32_raw();
+
+    let service = Arc::new(Service::new(
+        store,
+        backend,
+        vkey_hash,
+        ServiceConfig {
+            workers: args.workers,
+            max_attempts: args.max_attempts,
+            poll_interval: Duration::from_secs(1),
+            orders_dir: args.orders_dir,
+        },
+    ));
+    service.spawn_workers();
+
+    let server = Server::http(&args.listen)
+        .map_err(|e| anyhow::anyhow!("failed to listen on {}: {e}", args.listen))?;
+    tracing::info!(
+        listen = args.listen,
+        workers = args.workers,
+        "prover service listening"
+    );
+    service.serve(&server);
+    Ok(())
+}
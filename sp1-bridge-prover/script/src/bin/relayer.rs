@@ -0,0 +1,175 @@
+//! Relayer daemon moving orders locked on Solana to the EVM bridge.
+//!
+//! You can run the relayer against a local test validator and Anvil node using:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin relayer -- --evm-bridge 0x5FbDB2315678afecb367f032d93F642f64180aa3
+//! ```
+//! The chains, relayer keys and proving backend are read from the file given with `--config`,
+//! see `fibonacci_script::config`; the command line arguments take precedence over it.
+//! Prometheus metrics are served at `http://127.0.0.1:9100/metrics`.
+
+use std::{path::PathBuf, sync::Arc, thread, time::Duration};
+
+use clap::Parser;
+use fibonacci_script::{
+    anchor::parse_pubkey,
+    backend::{backend_from_config, BRIDGE_PROGRAM},
+    config::Config,
+    relayer::{parse_address, EvmRpc, Relayer, RelayerConfig, RelayerStore, SolanaRpc},
+    vkeys::{check_pinned, registry_path, require_pinned},
+    ProofMode,
+};
+use sp1_sdk::HashableKey;
+use tiny_http::Server;
+
+/// The arguments for the command.
+#[derive(Parser, Debug)]
+#[command(
+    name = "relayer",
+    author,
+    version,
+    about = "Solana to EVM bridge relayer"
+)]
+struct Args {
+    /// Configuration file of the chains, token pairs, keys and proving backend.
+    #[arg(long, env = "BRIDGE_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Solana JSON-RPC endpoint, overrides `solana.rpc_url`.
+    #[arg(long)]
+    solana_rpc: Option<String>,
+
+    /// Address of the Anchor bridge program, overrides `solana.program_id`.
+    #[arg(long, value_parser = parse_pubkey)]
+    program_id: Option<[u8; 32]>,
+
+    /// Commitment level used to read the Solana program, overrides `solana.commitment`.
+    #[arg(long)]
+    commitment: Option<String>,
+
+    /// Commitment level a slot must reach before its orders are proven, overrides
+    /// `solana.finality`.
+    #[arg(long)]
+    finality: Option<String>,
+
+    /// EVM JSON-RPC endpoint, overrides `evm.rpc_url`.
+    #[arg(long)]
+    evm_rpc: Option<String>,
+
+    /// Address of the `EVMSolanaBridge` contract, overrides `evm.bridge`.
+    #[arg(long, value_parser = parse_address)]
+    evm_bridge: Option<[u8; 20]>,
+
+    /// Unlocked account sending the transactions, overrides `relayer.evm_from`.
+    #[arg(long, value_parser = parse_address)]
+    evm_from: Option<[u8; 20]>,
+
+    /// Blocks an unlock transaction must be deep, including its own, before it counts as final.
+    /// Overrides `evm.confirmations`.
+    #[arg(long)]
+    evm_confirmations: Option<u64>,
+
+    /// SQLite database recording the relayer's progress.
+    #[arg(long, default_value = "relayer.sqlite")]
+    database: PathBuf,
+
+    #[arg(long, value_enum, default_value = "groth16")]
+    mode: ProofMode,
+
+    /// Seconds between polls of the Solana program.
+    #[arg(long, default_value_t = 2)]
+    poll_interval_secs: u64,
+
+    /// How often an order is attempted before it is marked as failed.
+    #[arg(long, default_value_t = 3)]
+    max_attempts: u32,
+
+    /// Address the Prometheus metrics are served on.
+    #[arg(long, default_value = "127.0.0.1:9100")]
+    metrics_listen: String,
+
+    /// Start even if the vkey registry is missing or pins no bridge version. The proofs may then
+    /// be rejected on chain.
+    #[arg(long)]
+    allow_unpinned_vkey: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+    let program_id = args.program_id.unwrap_or(config.solana.program_id);
+    let evm_bridge = match args.evm_bridge {
+        Some(bridge) => bridge,
+        None => config.evm.bridge()?,
+    };
+
+    let store = RelayerStore::open(&args.database)?;
+    let pending = store.unfinished()?;
+    if !pending.is_empty() {
+        tracing::info!("resuming {} unfinished order(s)", pending.len());
+        for order in &pending {
+            tracing::info!(order_id = order.order_id, status = ?order.status, "unfinished");
+        }
+    }
+
+    let backend = backend_from_config(&config.prover)?;
+    tracing::info!("setting up the bridge program");
+    let vk = backend.vkey(&BRIDGE_PROGRAM);
+    let registry = registry_path(&config.prover);
+    if args.allow_unpinned_vkey {
+        tracing::warn!("not requiring a pinned vkey in {}", registry.display());
+        check_pinned(&registry, &BRIDGE_PROGRAM, &vk)?;
+    } else {
+        require_pinned(&registry, &BRIDGE_PROGRAM, &vk)?;
+    }
+
+    let source = SolanaRpc::new(
+        args.solana_rpc.unwrap_or(config.solana.rpc_url),
+        program_id,
+        args.commitment.unwrap_or(config.solana.commitment),
+    )
+    .with_finality(args.finality.unwrap_or(config.solana.finality));
+    let destination = EvmRpc::new(
+        args.evm_rpc.unwrap_or(config.evm.rpc_url),
+        evm_bridge,
+        args.evm_from.or(config.relayer.evm_from),
+    )?
+    .with_confirmations(args.evm_confirmations.unwrap_or(config.evm.confirmations));
+    destination.check_chain_id(config.evm.chain_id)?;
+    let relayer = Relayer::new(
+        Box::new(source),
+        Box::new(destination),
+        backend,
+        store,
+        vk.bytes32_raw(),
+        RelayerConfig {
+            mode: args.mode,
+            poll_interval: Duration::from_secs(args.poll_interval_secs),
+            max_attempts: args.max_attempts,
+        },
+    );
+
+    let server = Server::http(&args.metrics_listen)
+        .map_err(|e| anyhow::anyhow!("failed to listen on {}: {e}", args.metrics_listen))?;
+    let metrics = Arc::clone(relayer.metrics());
+    thread::spawn(move || metrics.serve(&server));
+
+    if let Some(pubkey) = config.relayer.solana_pubkey()? {
+        tracing::info!(
+            relayer = bs58::encode(pubkey).into_string(),
+            "loaded relayer keypair"
+        );
+    }
+    tracing::info!(
+        program_id = bs58::encode(program_id).into_string(),
+        evm_bridge = format!("0x{}", hex::encode(evm_bridge)),
+        metrics = args.metrics_listen,
+        "relaying orders"
+    );
+    relayer.run();
+    Ok(())
+}
@@ -0,0 +1,391 @@
+//! Content-addressed cache of generated proofs.
+//!
+//! Proofs are keyed by the hash of the program vkey, the serialized guest inputs and the proof
+//! mode, so asking for the same proof again (for example after a relayer restart) returns the
+//! stored one instead of proving for minutes. Entries carry a checksum that is checked on every
+//! load, and [`CachedBackend`] verifies loaded proofs against the vkey; corrupted or invalid
+//! entries are dropped. The least recently used entries are evicted once the cache
+//! grows past its size limit.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{
+    ExecutionReport, HashableKey, SP1Proof, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1PublicValues, SP1Stdin, SP1VerifyingKey,
+};
+
+use crate::{
+    artifact::{bincode_base64, unix_now},
+    backend::{Program, ProofBackend},
+    input::{hex_bytes32, stdin_hash},
+    ProofMode,
+};
+
+/// Version of the cache entry layout, bumped on incompatible changes.
+pub const CACHE_VERSION: u32 = 1;
+
+/// A cached proof.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    version: u32,
+    #[serde(with = "hex_bytes32")]
+    key: [u8; 32],
+    mode: ProofMode,
+    created_at: u64,
+    /// SHA-256 of the bincode encoded proof.
+    #[serde(with = "hex_bytes32")]
+    checksum: [u8; 32],
+    #[serde(with = "bincode_base64")]
+    proof: SP1ProofWithPublicValues,
+}
+
+/// A directory of cached proofs, one JSON file per key.
+pub struct ProofCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl ProofCache {
+    /// Open the cache in `dir`, creating it if needed. `max_bytes` bounds its total size.
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64) -> anyhow::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create proof cache {}", dir.display()))?;
+        Ok(Self { dir, max_bytes })
+    }
+
+    /// Cache key of a proof of the program with `vkey_hash` over `stdin` in `mode`.
+    pub fn key(vkey_hash: [u8; 32], stdin: &SP1Stdin, mode: ProofMode) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(vkey_hash);
+        hasher.update(stdin_hash(stdin));
+        hasher.update(format!("{mode:?}"));
+        hasher.finalize().into()
+    }
+
+    fn path(&self, key: &[u8; 32]) -> PathBuf {
+        self.dir.join(format!("{}.json", hex::encode(key)))
+    }
+
+    /// Look up a proof. Entries that fail the integrity check are removed and count as a miss.
+    pub fn get(&self, key: &[u8; 32], mode: ProofMode) -> Option<SP1ProofWithPublicValues> {
+        let path = self.path(key);
+        if !path.exists() {
+            return None;
+        }
+        match read_entry(&path, key, mode) {
+            Ok(proof) => {
+                // The modification time doubles as the last access time for eviction.
+                if let Err(e) = fs::File::options()
+                    .append(true)
+                    .open(&path)
+                    .and_then(|file| file.set_modified(SystemTime::now()))
+                {
+                    tracing::warn!("failed to touch cache entry {}: {e}", path.display());
+                }
+                Some(proof)
+            }
+            Err(e) => {
+                tracing::warn!("dropping cache entry {}: {e:#}", path.display());
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Remove the entry of `key`, if any.
+    pub fn remove(&self, key: &[u8; 32]) -> anyhow::Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Store a proof and evict old entries if the cache is over its size limit.
+    pub fn put(
+        &self,
+        key: &[u8; 32],
+        mode: ProofMode,
+        proof: &SP1ProofWithPublicValues,
+    ) -> anyhow::Result<()> {
+        let entry = CacheEntry {
+            version: CACHE_VERSION,
+            key: *key,
+            mode,
+            created_at: unix_now(),
+            checksum: checksum(proof)?,
+            proof: proof.clone(),
+        };
+
+        // Write to a temporary file first so readers never see a partial entry.
+        let path = self.path(key);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(&entry)?)
+            .with_context(|| format!("failed to write cache entry {}", tmp.display()))?;
+        fs::rename(&tmp, &path)?;
+
+        self.evict()?;
+        Ok(())
+    }
+
+    /// Remove the least recently used entries until the cache fits in its size limit. Returns the
+    /// number of removed entries.
+    pub fn evict(&self) -> anyhow::Result<usize> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let metadata = entry.metadata()?;
+                entries.push((metadata.modified()?, metadata.len(), path));
+            }
+        }
+
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        entries.sort();
+        let mut removed = 0;
+        for (_, len, path) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(&path)?;
+            total -= len;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+fn read_entry(
+    path: &Path,
+    key: &[u8; 32],
+    mode: ProofMode,
+) -> anyhow::Result<SP1ProofWithPublicValues> {
+    let entry: CacheEntry = serde_json::from_slice(&fs::read(path)?)?;
+    anyhow::ensure!(
+        entry.version == CACHE_VERSION,
+        "unsupported cache entry version {}",
+        entry.version
+    );
+    anyhow::ensure!(
+        entry.key == *key && entry.mode == mode,
+        "entry does not match its key"
+    );
+    anyhow::ensure!(
+        checksum(&entry.proof)? == entry.checksum,
+        "checksum mismatch"
+    );
+    Ok(entry.proof)
+}
+
+fn checksum(proof: &SP1ProofWithPublicValues) -> anyhow::Result<[u8; 32]> {
+    Ok(Sha256::digest(bincode::serialize(proof)?).into())
+}
+
+/// Whether `proof` is a placeholder from a mock prover rather than a real proof.
+///
+/// Decided from the proof itself, since a backend chain may fall back to a mock prover or follow
+/// `SP1_PROVER` without its name saying so.
+pub fn is_mock_proof(proof: &SP1ProofWithPublicValues) -> bool {
+    match &proof.proof {
+        SP1Proof::Core(shards) => shards.is_empty(),
+        SP1Proof::Compressed(reduce) => reduce.proof.opened_values.chips.is_empty(),
+        SP1Proof::Plonk(plonk) => plonk.encoded_proof.is_empty(),
+        SP1Proof::Groth16(groth16) => groth16.encoded_proof.is_empty(),
+    }
+}
+
+/// Consults a [`ProofCache`] before proving with the wrapped backend.
+///
+/// Mock proofs are never cached, so they cannot be served to a real prover later, and cached
+/// proofs are only served once they verify against the program's vkey.
+pub struct CachedBackend {
+    inner: Box<dyn ProofBackend>,
+    cache: ProofCache,
+}
+
+impl CachedBackend {
+    pub fn new(inner: Box<dyn ProofBackend>, cache: ProofCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+impl ProofBackend for CachedBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn keys(&self, program: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)> {
+        self.inner.keys(program)
+    }
+
+    fn execute(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        self.inner.execute(program, stdin)
+    }
+
+    fn prove(
+        &self,
+        program: &Program,
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        let vk = self.vkey(program);
+        let key = ProofCache::key(vk.bytes32_raw(), stdin, mode);
+        if let Some(proof) = self.cache.get(&key, mode) {
+            match self.inner.verify(&proof, &vk) {
+                Ok(()) if !is_mock_proof(&proof) => {
+                    tracing::info!("using cached proof {}", hex::encode(key));
+                    return Ok(proof);
+                }
+                result => {
+                    let reason = result
+                        .err()
+                        .map_or("mock proof".to_string(), |e| format!("{e:#}"));
+                    tracing::warn!("dropping cached proof {}: {reason}", hex::encode(key));
+                    if let Err(e) = self.cache.remove(&key) {
+                        tracing::warn!("failed to remove cache entry: {e:#}");
+                    }
+                }
+            }
+        }
+
+        let proof = self.inner.prove(program, stdin, mode)?;
+        if is_mock_proof(&proof) {
+            return Ok(proof);
+        }
+        if let Err(e) = self.cache.put(&key, mode, &proof) {
+            tracing::warn!("failed to cache proof: {e:#}");
+        }
+        Ok(proof)
+    }
+
+    fn verify(&self, proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> anyhow::Result<()> {
+        self.inner.verify(proof, vk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proof(tag: u8) -> SP1ProofWithPublicValues {
+        SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(vec![]),
+            public_values: SP1PublicValues::from(&[tag; 64]),
+            sp1_version: "v5.0.0".to_string(),
+            tee_proof: None,
+        }
+    }
+
+    fn stdin(value: u64) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&value);
+        stdin
+    }
+
+    fn cache(name: &str, max_bytes: u64) -> ProofCache {
+        let dir = std::env::temp_dir().join(format!("proof-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        ProofCache::open(dir, max_bytes).unwrap()
+    }
+
+    #[test]
+    fn test_key_covers_vkey_stdin_and_mode() {
+        let key = ProofCache::key([1; 32], &stdin(1), ProofMode::Core);
+        assert_eq!(key, ProofCache::key([1; 32], &stdin(1), ProofMode::Core));
+        assert_ne!(key, ProofCache::key([2; 32], &stdin(1), ProofMode::Core));
+        assert_ne!(key, ProofCache::key([1; 32], &stdin(2), ProofMode::Core));
+        assert_ne!(key, ProofCache::key([1; 32], &stdin(1), ProofMode::Groth16));
+    }
+
+    #[test]
+    fn test_roundtrip_and_integrity_check() {
+        let cache = cache("integrity", u64::MAX);
+        let key = ProofCache::key([1; 32], &stdin(1), ProofMode::Core);
+        assert!(cache.get(&key, ProofMode::Core).is_none());
+
+        cache.put(&key, ProofMode::Core, &proof(7)).unwrap();
+        let cached = cache.get(&key, ProofMode::Core).unwrap();
+        assert_eq!(cached.public_values.as_slice(), &[7; 64]);
+
+        // Swap in a different proof without updating the checksum.
+        let path = cache.path(&key);
+        let mut entry: CacheEntry = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        entry.proof = proof(8);
+        fs::write(&path, serde_json::to_vec(&entry).unwrap()).unwrap();
+
+        assert!(cache.get(&key, ProofMode::Core).is_none());
+        assert!(!path.exists());
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = cache("evict", u64::MAX);
+        let keys: Vec<_> = (0..3)
+            .map(|i| ProofCache::key([1; 32], &stdin(i), ProofMode::Core))
+            .collect();
+        for (i, key) in keys.iter().enumerate() {
+            cache.put(key, ProofMode::Core, &proof(i as u8)).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        // Reading the first entry makes the second one the least recently used.
+        cache.get(&keys[0], ProofMode::Core).unwrap();
+
+        let entry_len = fs::metadata(cache.path(&keys[0])).unwrap().len();
+        let cache = ProofCache {
+            max_bytes: entry_len * 2,
+            ..cache
+        };
+        assert_eq!(cache.evict().unwrap(), 1);
+        assert!(cache.get(&keys[0], ProofMode::Core).is_some());
+        assert!(cache.get(&keys[1], ProofMode::Core).is_none());
+        assert!(cache.get(&keys[2], ProofMode::Core).is_some());
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_detects_mock_proofs() {
+        assert!(is_mock_proof(&proof(1)));
+
+        // Groth16 and Plonk proofs are only built by the gnark wrapper, so go through serde.
+        let groth16 = |encoded_proof: &str| SP1ProofWithPublicValues {
+            proof: serde_json::from_value(serde_json::json!({
+                "Groth16": {
+                    "public_inputs": ["1", "2"],
+                    "encoded_proof": encoded_proof,
+                    "raw_proof": "",
+                    "groth16_vkey_hash": vec![0; 32],
+                }
+            }))
+            .unwrap(),
+            ..proof(1)
+        };
+        assert!(is_mock_proof(&groth16("")));
+        assert!(!is_mock_proof(&groth16("0a0b0c")));
+    }
+
+    #[test]
+    fn test_remove() {
+        let cache = cache("remove", u64::MAX);
+        let key = ProofCache::key([1; 32], &stdin(1), ProofMode::Core);
+        cache.put(&key, ProofMode::Core, &proof(1)).unwrap();
+        cache.remove(&key).unwrap();
+        assert!(cache.get(&key, ProofMode::Core).is_none());
+        // Removing a missing entry is not an error.
+        cache.remove(&key).unwrap();
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+}
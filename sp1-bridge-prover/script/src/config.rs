@@ -0,0 +1,563 @@
+//! Configuration file shared by the host binaries.
+//!
+//! The relayer, indexer, auditor, prover service and bridge prover read the chains, token pairs,
+//! relayer keys and proving backend from one TOML file passed with `--config` (or
+//! `BRIDGE_CONFIG`). Every setting has a default matching a local test validator and Anvil node,
+//! so the file only lists what differs:
+//!
+//! ```toml
+//! [solana]
+//! rpc_url = "https://api.devnet.solana.com"
+//! program_id = "GbtjEQYnuvVKN5DiQjvqoPGA9vS2tsH7mTfS6SJZXgBf"
+//!
+//! [evm]
+//! rpc_url = "https://sepolia.example.org"
+//! chain_id = 11155111
+//! bridge = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+//!
+//! [[token_pairs]]
+//! mint = "So11111111111111111111111111111111111111112"
+//! evm_token = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512"
+//!
+//! [prover]
+//! backends = "remote,cpu"
+//! url = "http://prover.internal:8080"
+//! ```
+//!
+//! Settings are resolved in the order: command line arguments, environment variables, the file,
+//! the defaults. The environment variables are listed in [`Config::apply_env`].
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Deserializer};
+
+use crate::{anchor::parse_pubkey, relayer::parse_address, vkeys::DEFAULT_REGISTRY};
+
+/// Address of the Anchor bridge program, as declared in `solana-evm-bridge`.
+pub const PROGRAM_ID: &str = "GbtjEQYnuvVKN5DiQjvqoPGA9vS2tsH7mTfS6SJZXgBf";
+
+const COMMITMENTS: [&str; 3] = ["processed", "confirmed", "finalized"];
+const BACKENDS: [&str; 4] = ["mock", "cpu", "env", "remote"];
+
+/// The bridge configuration.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub solana: SolanaConfig,
+    pub evm: EvmConfig,
+    pub token_pairs: Vec<TokenPair>,
+    pub relayer: RelayerConfig,
+    pub prover: ProverConfig,
+}
+
+/// The Solana cluster and the Anchor bridge program.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SolanaConfig {
+    pub rpc_url: String,
+    /// Commitment level the relayer reads the program at.
+    pub commitment: String,
+    /// Commitment level a slot must reach before its orders are proven or indexed.
+    pub finality: String,
+    #[serde(deserialize_with = "pubkey")]
+    pub program_id: [u8; 32],
+}
+
+impl Default for SolanaConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: "http://127.0.0.1:8899".to_string(),
+            commitment: "confirmed".to_string(),
+            finality: "finalized".to_string(),
+            program_id: parse_pubkey(PROGRAM_ID).expect("valid program id"),
+        }
+    }
+}
+
+/// The EVM chain and the `EVMSolanaBridge` contract.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EvmConfig {
+    pub rpc_url: String,
+    /// Expected `eth_chainId` of the node, checked on startup when set.
+    pub chain_id: Option<u64>,
+    #[serde(deserialize_with = "optional_address")]
+    pub bridge: Option<[u8; 20]>,
+    /// Blocks a transaction or log must be deep, including its own, before it counts as final.
+    pub confirmations: u64,
+    /// Block the bridge was deployed in.
+    pub start_block: u64,
+}
+
+impl Default for EvmConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: "http://127.0.0.1:8545".to_string(),
+            chain_id: None,
+            bridge: None,
+            confirmations: 1,
+            start_block: 0,
+        }
+    }
+}
+
+impl EvmConfig {
+    /// The bridge contract, which has no default.
+    pub fn bridge(&self) -> anyhow::Result<[u8; 20]> {
+        self.bridge.context(
+            "the EVM bridge address is not configured, set evm.bridge, BRIDGE_EVM_BRIDGE or \
+             --evm-bridge",
+        )
+    }
+}
+
+/// A token registered on both bridges.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenPair {
+    #[serde(deserialize_with = "pubkey")]
+    pub mint: [u8; 32],
+    #[serde(deserialize_with = "address")]
+    pub evm_token: [u8; 20],
+}
+
+/// The keys of the relayer.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RelayerConfig {
+    /// Solana keypair file of the relayer, in the format written by `solana-keygen`.
+    pub solana_keypair: Option<PathBuf>,
+    /// Unlocked account sending the EVM transactions, defaults to the node's first account.
+    #[serde(deserialize_with = "optional_address")]
+    pub evm_from: Option<[u8; 20]>,
+}
+
+impl RelayerConfig {
+    /// The public key of the Solana keypair, if one is configured.
+    pub fn solana_pubkey(&self) -> anyhow::Result<Option<[u8; 32]>> {
+        let Some(path) = &self.solana_keypair else {
+            return Ok(None);
+        };
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read the keypair {}", path.display()))?;
+        let bytes: Vec<u8> = serde_json::from_str(&json)
+            .with_context(|| format!("{} is not a keypair file", path.display()))?;
+        anyhow::ensure!(
+            bytes.len() == 64,
+            "{} holds {} bytes, a keypair has 64",
+            path.display(),
+            bytes.len()
+        );
+        Ok(Some(bytes[32..].try_into().expect("32 bytes")))
+    }
+}
+
+/// The proving backend, see [`crate::backend::backend_from_config`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProverConfig {
+    /// Comma separated fallback order of `mock`, `cpu`, `env` and `remote`.
+    pub backends: String,
+    /// Base URL of the remote prover.
+    pub url: Option<String>,
+    pub timeout_secs: u64,
+    pub retries: u32,
+    pub retry_delay_secs: u64,
+    /// Reuse proofs from this directory, see [`crate::cache`].
+    pub cache_dir: Option<PathBuf>,
+    pub cache_max_mb: u64,
+    /// Registry of the pinned guest vkeys, see [`crate::vkeys::registry_path`]. Relative to the
+    /// configuration file.
+    pub vkey_registry: Option<PathBuf>,
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self {
+            backends: "env".to_string(),
+            url: None,
+            timeout_secs: 3600,
+            retries: 2,
+            retry_delay_secs: 5,
+            cache_dir: None,
+            cache_max_mb: 1024,
+            vkey_registry: None,
+        }
+    }
+}
+
+impl ProverConfig {
+    /// The configured backend names, in fallback order.
+    pub fn backend_names(&self) -> impl Iterator<Item = &str> {
+        self.backends
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+    }
+
+    /// Override the settings with the `BRIDGE_PROVER_*`, `BRIDGE_PROOF_CACHE_*` and
+    /// `BRIDGE_VKEY_REGISTRY` environment variables.
+    pub fn apply_env(&mut self) -> anyhow::Result<()> {
+        if let Some(backends) = env("BRIDGE_PROVER_BACKENDS", text)? {
+            self.backends = backends;
+        }
+        if let Some(url) = env("BRIDGE_PROVER_URL", text)? {
+            self.url = Some(url);
+        }
+        if let Some(timeout) = env("BRIDGE_PROVER_TIMEOUT_SECS", number)? {
+            self.timeout_secs = timeout;
+        }
+        if let Some(retries) = env("BRIDGE_PROVER_RETRIES", number)? {
+            self.retries = retries;
+        }
+        if let Some(delay) = env("BRIDGE_PROVER_RETRY_DELAY_SECS", number)? {
+            self.retry_delay_secs = delay;
+        }
+        if let Some(dir) = env("BRIDGE_PROOF_CACHE_DIR", text)? {
+            self.cache_dir = Some(dir.into());
+        }
+        if let Some(max_mb) = env("BRIDGE_PROOF_CACHE_MAX_MB", number)? {
+            self.cache_max_mb = max_mb;
+        }
+        if let Some(path) = env("BRIDGE_VKEY_REGISTRY", text)? {
+            self.vkey_registry = Some(path.into());
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.backend_names().next().is_some(),
+            "prover.backends is empty"
+        );
+        for name in self.backend_names() {
+            anyhow::ensure!(
+                BACKENDS.contains(&name),
+                "prover.backends: unknown proving backend {name}, expected one of {BACKENDS:?}"
+            );
+        }
+        if self.backend_names().any(|name| name == "remote") {
+            let url = self
+                .url
+                .as_deref()
+                .context("prover.url is required for the remote backend")?;
+            check_url("prover.url", url)?;
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Read `path` if given, apply the environment overrides and validate the result.
+    ///
+    /// The vkey registry of a configuration file is resolved against the file's directory, and
+    /// defaults to [`crate::vkeys::DEFAULT_REGISTRY`] next to it.
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let mut config = match path {
+            Some(path) => {
+                let mut config = Self::parse(
+                    &fs::read_to_string(path)
+                        .with_context(|| format!("failed to read {}", path.display()))?,
+                )
+                .with_context(|| format!("invalid configuration {}", path.display()))?;
+                let registry = config
+                    .prover
+                    .vkey_registry
+                    .get_or_insert_with(|| DEFAULT_REGISTRY.into());
+                *registry = path.parent().unwrap_or(Path::new("")).join(&*registry);
+                config
+            }
+            None => Self::default(),
+        };
+        config.apply_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse a configuration file, without environment overrides or validation.
+    pub fn parse(toml: &str) -> anyhow::Result<Self> {
+        Ok(basic_toml::from_str(toml)?)
+    }
+
+    /// Override the settings with the environment variables:
+    ///
+    /// - `SOLANA_RPC_URL`, `BRIDGE_SOLANA_COMMITMENT`, `BRIDGE_SOLANA_FINALITY` and
+    ///   `BRIDGE_PROGRAM_ID` for `[solana]`.
+    /// - `EVM_RPC_URL`, `BRIDGE_EVM_CHAIN_ID`, `BRIDGE_EVM_BRIDGE`, `BRIDGE_EVM_CONFIRMATIONS`
+    ///   and `BRIDGE_EVM_START_BLOCK` for `[evm]`.
+    /// - `BRIDGE_RELAYER_KEYPAIR` and `BRIDGE_RELAYER_EVM_FROM` for `[relayer]`.
+    /// - The variables of [`ProverConfig::apply_env`] for `[prover]`.
+    pub fn apply_env(&mut self) -> anyhow::Result<()> {
+        if let Some(url) = env("SOLANA_RPC_URL", text)? {
+            self.solana.rpc_url = url;
+        }
+        if let Some(commitment) = env("BRIDGE_SOLANA_COMMITMENT", text)? {
+            self.solana.commitment = commitment;
+        }
+        if let Some(finality) = env("BRIDGE_SOLANA_FINALITY", text)? {
+            self.solana.finality = finality;
+        }
+        if let Some(program_id) = env("BRIDGE_PROGRAM_ID", parse_pubkey)? {
+            self.solana.program_id = program_id;
+        }
+
+        if let Some(url) = env("EVM_RPC_URL", text)? {
+            self.evm.rpc_url = url;
+        }
+        if let Some(chain_id) = env("BRIDGE_EVM_CHAIN_ID", number)? {
+            self.evm.chain_id = Some(chain_id);
+        }
+        if let Some(bridge) = env("BRIDGE_EVM_BRIDGE", parse_address)? {
+            self.evm.bridge = Some(bridge);
+        }
+        if let Some(confirmations) = env("BRIDGE_EVM_CONFIRMATIONS", number)? {
+            self.evm.confirmations = confirmations;
+        }
+        if let Some(start_block) = env("BRIDGE_EVM_START_BLOCK", number)? {
+            self.evm.start_block = start_block;
+        }
+
+        if let Some(keypair) = env("BRIDGE_RELAYER_KEYPAIR", text)? {
+            self.relayer.solana_keypair = Some(keypair.into());
+        }
+        if let Some(from) = env("BRIDGE_RELAYER_EVM_FROM", parse_address)? {
+            self.relayer.evm_from = Some(from);
+        }
+
+        self.prover.apply_env()
+    }
+
+    /// Check the settings are consistent, naming the first offending setting.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        check_url("solana.rpc_url", &self.solana.rpc_url)?;
+        check_commitment("solana.commitment", &self.solana.commitment)?;
+        check_commitment("solana.finality", &self.solana.finality)?;
+        anyhow::ensure!(
+            commitment_rank(&self.solana.finality) >= commitment_rank(&self.solana.commitment),
+            "solana.finality ({}) is weaker than solana.commitment ({})",
+            self.solana.finality,
+            self.solana.commitment
+        );
+
+        check_url("evm.rpc_url", &self.evm.rpc_url)?;
+        anyhow::ensure!(self.evm.chain_id != Some(0), "evm.chain_id must not be 0");
+        anyhow::ensure!(
+            self.evm.confirmations >= 1,
+            "evm.confirmations must be at least 1"
+        );
+
+        let mut mints = HashSet::new();
+        let mut evm_tokens = HashSet::new();
+        for pair in &self.token_pairs {
+            anyhow::ensure!(
+                mints.insert(pair.mint),
+                "token_pairs: mint {} is listed twice",
+                bs58::encode(pair.mint).into_string()
+            );
+            anyhow::ensure!(
+                evm_tokens.insert(pair.evm_token),
+                "token_pairs: EVM token 0x{} is listed twice",
+                hex::encode(pair.evm_token)
+            );
+        }
+
+        self.relayer
+            .solana_pubkey()
+            .context("relayer.solana_keypair")?;
+        self.prover.validate()
+    }
+
+    /// The configured EVM token of `mint`.
+    pub fn evm_token(&self, mint: &[u8; 32]) -> Option<[u8; 20]> {
+        self.token_pairs
+            .iter()
+            .find(|pair| &pair.mint == mint)
+            .map(|pair| pair.evm_token)
+    }
+}
+
+fn env<T>(name: &str, parse: fn(&str) -> Result<T, String>) -> anyhow::Result<Option<T>> {
+    match std::env::var(name) {
+        Ok(value) => parse(&value)
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("invalid {name}: {e}")),
+        Err(_) => Ok(None),
+    }
+}
+
+fn text(value: &str) -> Result<String, String> {
+    Ok(value.to_string())
+}
+
+fn number<T: std::str::FromStr<Err = std::num::ParseIntError>>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|e: std::num::ParseIntError| e.to_string())
+}
+
+fn check_url(name: &str, url: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        url.starts_with("http://") || url.starts_with("https://"),
+        "{name} must be an http(s) URL, got {url:?}"
+    );
+    Ok(())
+}
+
+fn check_commitment(name: &str, commitment: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        COMMITMENTS.contains(&commitment),
+        "{name} must be one of {COMMITMENTS:?}, got {commitment:?}"
+    );
+    Ok(())
+}
+
+fn commitment_rank(commitment: &str) -> usize {
+    COMMITMENTS
+        .iter()
+        .position(|level| *level == commitment)
+        .unwrap_or(0)
+}
+
+fn pubkey<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_pubkey(&s).map_err(serde::de::Error::custom)
+}
+
+fn address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 20], D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_address(&s).map_err(serde::de::Error::custom)
+}
+
+fn optional_address<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<[u8; 20]>, D::Error> {
+    address(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_configuration() {
+        let config = Config::parse(
+            r#"
+            [solana]
+            rpc_url = "https://api.devnet.solana.com"
+
+            [evm]
+            chain_id = 11155111
+            bridge = "0x5FbDB2315678afecb367f032d93F642f64180aa3"
+            confirmations = 12
+
+            [[token_pairs]]
+            mint = "So11111111111111111111111111111111111111112"
+            evm_token = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512"
+
+            [prover]
+            backends = "remote, cpu"
+            url = "http://prover.internal:8080"
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.solana.rpc_url, "https://api.devnet.solana.com");
+        assert_eq!(config.solana.commitment, "confirmed");
+        assert_eq!(
+            bs58::encode(config.solana.program_id).into_string(),
+            PROGRAM_ID
+        );
+        assert_eq!(config.evm.rpc_url, "http://127.0.0.1:8545");
+        assert_eq!(config.evm.chain_id, Some(11155111));
+        assert_eq!(
+            config.evm.bridge().unwrap(),
+            parse_address("0x5FbDB2315678afecb367f032d93F642f64180aa3").unwrap()
+        );
+        assert_eq!(config.evm.confirmations, 12);
+        assert_eq!(
+            config.evm_token(&parse_pubkey("So11111111111111111111111111111111111111112").unwrap()),
+            Some(parse_address("0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512").unwrap())
+        );
+        assert_eq!(
+            config.prover.backend_names().collect::<Vec<_>>(),
+            ["remote", "cpu"]
+        );
+        assert_eq!(config.prover.timeout_secs, 3600);
+    }
+
+    #[test]
+    fn test_parses_example_configuration() {
+        let config = Config::parse(include_str!("../../bridge.example.toml")).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.evm.chain_id, Some(31337));
+        assert_eq!(config.token_pairs.len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_invalid_configuration() {
+        let invalid = |toml: &str| {
+            let error = Config::parse(toml).and_then(|config| config.validate());
+            format!("{:#}", error.unwrap_err())
+        };
+
+        assert!(invalid("[solana]\nrpc_urll = \"http://localhost\"").contains("rpc_urll"));
+        assert!(invalid("[solana]\nprogram_id = \"0x1234\"").contains("program_id"));
+        assert!(invalid("[evm]\nrpc_url = \"localhost:8545\"").contains("evm.rpc_url"));
+        assert!(invalid("[evm]\nconfirmations = 0").contains("evm.confirmations"));
+        assert!(
+            invalid("[solana]\ncommitment = \"finalized\"\nfinality = \"confirmed\"")
+                .contains("solana.finality")
+        );
+        assert!(invalid("[prover]\nbackends = \"gpu\"").contains("gpu"));
+        assert!(invalid("[prover]\nbackends = \"remote\"").contains("prover.url"));
+
+        let pair = "[[token_pairs]]\nmint = \"So11111111111111111111111111111111111111112\"\n\
+                    evm_token = \"0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512\"\n";
+        assert!(invalid(&format!("{pair}{pair}")).contains("listed twice"));
+    }
+
+    #[test]
+    fn test_reads_relayer_keypair() {
+        let dir = std::env::temp_dir().join(format!("bridge-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("relayer.json");
+        let keypair: Vec<u8> = (0..64).collect();
+        fs::write(&path, serde_json::to_string(&keypair).unwrap()).unwrap();
+
+        let relayer = RelayerConfig {
+            solana_keypair: Some(path.clone()),
+            evm_from: None,
+        };
+        let expected: [u8; 32] = (32..64).collect::<Vec<u8>>().try_into().unwrap();
+        assert_eq!(relayer.solana_pubkey().unwrap(), Some(expected));
+
+        fs::write(&path, "[1, 2, 3]").unwrap();
+        assert!(relayer.solana_pubkey().is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolves_vkey_registry_next_to_the_file() {
+        let dir = std::env::temp_dir().join(format!("bridge-registry-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bridge.toml");
+
+        fs::write(&path, "").unwrap();
+        let config = Config::load(Some(&path)).unwrap();
+        assert_eq!(config.prover.vkey_registry, Some(dir.join("vkeys.json")));
+
+        fs::write(&path, "[prover]\nvkey_registry = \"keys/pinned.json\"\n").unwrap();
+        let config = Config::load(Some(&path)).unwrap();
+        assert_eq!(
+            config.prover.vkey_registry,
+            Some(dir.join("keys/pinned.json"))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
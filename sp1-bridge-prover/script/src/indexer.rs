@@ -0,0 +1,995 @@
+//! Indexer following orders across both bridges.
+//!
+//! The indexer reads the `TokensLocked` and `TokensUnlocked` events of the Anchor program from its
+//! transaction logs, and the events of the same name from the logs of the EVM bridge. Every order
+//! has a source leg, the lock on the chain it was created on, and a destination leg, the unlock
+//! the relayer submits with the same order id on the other chain. Both legs are stored in one
+//! SQLite row keyed by source chain and order id, whichever is seen first.
+//!
+//! Only final history is indexed: Solana transactions are read at the configured commitment and
+//! EVM logs once their block is deep enough, so a row never refers to an abandoned fork. The
+//! position on each chain is stored with the rows, so a restarted indexer continues where it
+//! stopped.
+//!
+//! The HTTP API answers:
+//!
+//! - `GET /orders/{chain}/{id}`, an order by source chain (`solana` or `evm`) and id.
+//! - `GET /orders?user={address}` or `GET /orders?recipient={address}`, orders of an account.
+//! - `GET /orders/pending?older_than={seconds}`, orders locked longer ago and not unlocked yet.
+//! - `GET /volume`, locked and released amounts per token.
+//!
+//! Solana addresses are base58, EVM addresses lowercase `0x` hex.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use alloy_sol_types::{
+    private::{B256, U256},
+    SolEvent,
+};
+use anyhow::Context;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{
+    anchor::{events, parse_pubkey, BridgeEvent},
+    artifact::unix_now,
+    config::PROGRAM_ID,
+    relayer::{parse_quantity, EvmRpc, SolanaRpc},
+    service::{error, from_text, to_text},
+};
+
+/// Events of the `EVMSolanaBridge` contract.
+mod evm {
+    alloy_sol_types::sol! {
+        event TokensLocked(
+            uint64 indexed orderId,
+            address indexed user,
+            address indexed token,
+            uint256 amount,
+            uint256 relayerFee,
+            bytes32 recipient
+        );
+
+        event TokensUnlocked(
+            uint64 indexed orderId,
+            address indexed relayer,
+            address indexed user,
+            address token,
+            uint256 amount,
+            uint256 relayerFee
+        );
+    }
+}
+
+/// Most blocks requested in one `eth_getLogs` call.
+const LOG_RANGE: u64 = 10_000;
+
+/// The chains of the bridge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+    Solana,
+    Evm,
+}
+
+/// A transaction of the Anchor program, as returned by `getTransaction`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramTransaction {
+    pub slot: u64,
+    pub block_time: Option<u64>,
+    pub transaction: TransactionBody,
+    pub meta: Option<TransactionMeta>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TransactionBody {
+    pub signatures: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMeta {
+    pub err: Option<Value>,
+    pub log_messages: Option<Vec<String>>,
+}
+
+impl ProgramTransaction {
+    pub fn signature(&self) -> &str {
+        self.transaction
+            .signatures
+            .first()
+            .map_or("", String::as_str)
+    }
+
+    /// Log messages of a successful transaction; failed transactions emit no events.
+    pub fn logs(&self) -> &[String] {
+        match &self.meta {
+            Some(TransactionMeta {
+                err: None,
+                log_messages: Some(logs),
+            }) => logs,
+            _ => &[],
+        }
+    }
+}
+
+/// A log of the EVM bridge, as returned by `eth_getLogs`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmLog {
+    pub topics: Vec<String>,
+    pub data: String,
+    pub block_number: String,
+    pub transaction_hash: String,
+    /// Not every node includes it; [`EvmRpc`] fills it in from the block.
+    #[serde(default)]
+    pub block_timestamp: Option<String>,
+    /// Set for logs of blocks that were reorganized away.
+    #[serde(default)]
+    pub removed: bool,
+}
+
+/// Transactions of the Anchor program.
+pub trait SolanaHistory: Send + Sync {
+    /// Transactions after `cursor`, oldest first, and the cursor to continue from. `None` starts
+    /// at the beginning of the history.
+    fn transactions(
+        &self,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<(Vec<ProgramTransaction>, Option<String>)>;
+}
+
+/// Logs of the EVM bridge.
+pub trait EvmHistory: Send + Sync {
+    /// Final logs in blocks from `from_block` on, oldest first, and the block to continue from.
+    fn logs(&self, from_block: u64) -> anyhow::Result<(Vec<EvmLog>, u64)>;
+}
+
+impl SolanaHistory for SolanaRpc {
+    fn transactions(
+        &self,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<(Vec<ProgramTransaction>, Option<String>)> {
+        let signatures = self.signatures(cursor)?;
+        let next = signatures
+            .first()
+            .map(|info| info.signature.clone())
+            .or_else(|| cursor.map(str::to_string));
+        let transactions = signatures
+            .iter()
+            .rev()
+            .filter(|info| info.err.is_none())
+            .map(|info| {
+                let transaction = self.transaction(&info.signature)?;
+                serde_json::from_value(transaction)
+                    .with_context(|| format!("invalid transaction {}", info.signature))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok((transactions, next))
+    }
+}
+
+impl EvmHistory for EvmRpc {
+    fn logs(&self, from_block: u64) -> anyhow::Result<(Vec<EvmLog>, u64)> {
+        let to = self.final_block_number()?;
+        let mut logs = Vec::new();
+        let mut start = from_block;
+        while start <= to {
+            let end = to.min(start + LOG_RANGE - 1);
+            for log in self.bridge_logs(start, end)? {
+                logs.push(serde_json::from_value::<EvmLog>(log).context("invalid log")?);
+            }
+            start = end + 1;
+        }
+
+        let mut timestamps = HashMap::new();
+        for log in logs.iter_mut().filter(|log| log.block_timestamp.is_none()) {
+            let block = parse_quantity(&log.block_number)?;
+            let timestamp = match timestamps.get(&block) {
+                Some(&timestamp) => timestamp,
+                None => {
+                    let timestamp = self.block_timestamp(block)?;
+                    timestamps.insert(block, timestamp);
+                    timestamp
+                }
+            };
+            log.block_timestamp = Some(format!("{timestamp:#x}"));
+        }
+        Ok((logs, start))
+    }
+}
+
+/// The lock of an order on its source chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLeg {
+    pub user: String,
+    pub recipient: String,
+    pub token: String,
+    /// Locked amount after the relayer fee, in token units.
+    pub amount: String,
+    pub relayer_fee: String,
+    pub tx: String,
+    /// Slot or block number.
+    pub block: u64,
+    /// Unix time of the block, if known.
+    pub time: Option<u64>,
+}
+
+/// The unlock of an order on the other chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DestinationLeg {
+    /// Token released on the destination chain.
+    pub token: String,
+    /// Amount paid to the user.
+    pub amount: String,
+    /// Amount paid to the relayer.
+    pub relayer_fee: String,
+    pub relayer: String,
+    pub tx: String,
+    pub block: u64,
+    pub time: Option<u64>,
+}
+
+/// An event decoded from either chain, keyed by the order's source chain and id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Leg {
+    Source {
+        chain: Chain,
+        order_id: u64,
+        leg: SourceLeg,
+    },
+    Destination {
+        source_chain: Chain,
+        order_id: u64,
+        leg: DestinationLeg,
+    },
+}
+
+/// Where an indexed order stands.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderState {
+    /// Locked and not unlocked yet.
+    Pending,
+    /// Locked and unlocked.
+    Completed,
+    /// Unlocked, but no lock with this id was indexed.
+    Unmatched,
+}
+
+/// An order with the legs indexed so far.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndexedOrder {
+    pub source_chain: Chain,
+    pub order_id: u64,
+    pub state: OrderState,
+    pub source: Option<SourceLeg>,
+    pub destination: Option<DestinationLeg>,
+}
+
+/// Locked and released amounts of one token.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenVolume {
+    pub chain: Chain,
+    pub token: String,
+    pub orders: u64,
+    pub completed: u64,
+    pub locked: String,
+    pub released: String,
+}
+
+/// The legs emitted by a transaction of the program deployed at `program_id`, in emission order.
+pub fn solana_legs(transaction: &ProgramTransaction, program_id: &[u8; 32]) -> Vec<Leg> {
+    let tx = transaction.signature().to_string();
+    events(transaction.logs(), program_id)
+        .into_iter()
+        .filter_map(|event| match event {
+            BridgeEvent::TokensLocked(event) => Some(Leg::Source {
+                chain: Chain::Solana,
+                order_id: event.order_id,
+                leg: SourceLeg {
+                    user: event.user.to_string(),
+                    recipient: evm_address(&event.recipient),
+                    token: event.mint.to_string(),
+                    amount: event.amount.to_string(),
+                    relayer_fee: event.relayer_fee.to_string(),
+                    tx: tx.clone(),
+                    block: transaction.slot,
+                    time: transaction.block_time,
+                },
+            }),
+            BridgeEvent::TokensUnlocked(event) => Some(Leg::Destination {
+                source_chain: Chain::Evm,
+                order_id: event.order_id,
+                leg: DestinationLeg {
+                    token: event.mint.to_string(),
+                    amount: event.amount.to_string(),
+                    relayer_fee: event.relayer_fee.to_string(),
+                    relayer: event.relayer.to_string(),
+                    tx: tx.clone(),
+                    block: transaction.slot,
+                    time: transaction.block_time,
+                },
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The leg of an EVM bridge log, `None` for other events.
+pub fn evm_leg(log: &EvmLog) -> anyhow::Result<Option<Leg>> {
+    let topics = log
+        .topics
+        .iter()
+        .map(|topic| {
+            let mut word = [0u8; 32];
+            hex::decode_to_slice(topic.trim_start_matches("0x"), &mut word)
+                .with_context(|| format!("invalid topic {topic}"))?;
+            Ok(B256::from(word))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let data = hex::decode(log.data.trim_start_matches("0x")).context("invalid log data")?;
+    let block = parse_quantity(&log.block_number)?;
+    let time = log
+        .block_timestamp
+        .as_deref()
+        .map(parse_quantity)
+        .transpose()?;
+    let tx = log.transaction_hash.to_lowercase();
+
+    let leg = match topics.first() {
+        Some(topic) if *topic == evm::TokensLocked::SIGNATURE_HASH => {
+            let event = evm::TokensLocked::decode_raw_log(topics, &data)
+                .context("failed to decode TokensLocked")?;
+            Leg::Source {
+                chain: Chain::Evm,
+                order_id: event.orderId,
+                leg: SourceLeg {
+                    user: evm_address(event.user.as_slice()),
+                    recipient: bs58::encode(event.recipient).into_string(),
+                    token: evm_address(event.token.as_slice()),
+                    amount: event.amount.to_string(),
+                    relayer_fee: event.relayerFee.to_string(),
+                    tx,
+                    block,
+                    time,
+                },
+            }
+        }
+        Some(topic) if *topic == evm::TokensUnlocked::SIGNATURE_HASH => {
+            let event = evm::TokensUnlocked::decode_raw_log(topics, &data)
+                .context("failed to decode TokensUnlocked")?;
+            Leg::Destination {
+                source_chain: Chain::Solana,
+                order_id: event.orderId,
+                leg: DestinationLeg {
+                    token: evm_address(event.token.as_slice()),
+                    amount: event.amount.to_string(),
+                    relayer_fee: event.relayerFee.to_string(),
+                    relayer: evm_address(event.relayer.as_slice()),
+                    tx,
+                    block,
+                    time,
+                },
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(leg))
+}
+
+pub(crate) fn evm_address(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS orders (
+    source_chain TEXT NOT NULL,
+    order_id INTEGER NOT NULL,
+    user TEXT,
+    recipient TEXT,
+    token TEXT,
+    amount TEXT,
+    relayer_fee TEXT,
+    source_tx TEXT,
+    source_block INTEGER,
+    locked_at INTEGER,
+    released_token TEXT,
+    released TEXT,
+    released_fee TEXT,
+    relayer TEXT,
+    destination_tx TEXT,
+    destination_block INTEGER,
+    unlocked_at INTEGER,
+    PRIMARY KEY (source_chain, order_id)
+);
+CREATE INDEX IF NOT EXISTS orders_user ON orders (user);
+CREATE INDEX IF NOT EXISTS orders_recipient ON orders (recipient);
+CREATE INDEX IF NOT EXISTS orders_pending ON orders (destination_tx, locked_at);
+CREATE TABLE IF NOT EXISTS cursors (
+    chain TEXT PRIMARY KEY,
+    position TEXT NOT NULL
+);
+";
+
+/// The indexer's SQLite database.
+pub struct IndexerStore {
+    conn: Mutex<Connection>,
+}
+
+impl IndexerStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open indexer database {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    /// A store that lives in memory only, for tests.
+    pub fn open_in_memory() -> anyhow::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> anyhow::Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("failed to create the indexer tables")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Position in the history of `chain` already indexed.
+    pub fn cursor(&self, chain: Chain) -> anyhow::Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row(
+                "SELECT position FROM cursors WHERE chain = ?1",
+                [to_text(&chain)],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Record the legs read from `chain` and move its cursor past them in one transaction.
+    /// Recording a leg again overwrites it. Returns the number of legs.
+    pub fn ingest(
+        &self,
+        chain: Chain,
+        legs: &[Leg],
+        cursor: Option<&str>,
+    ) -> anyhow::Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for leg in legs {
+            match leg {
+                Leg::Source {
+                    chain,
+                    order_id,
+                    leg,
+                } => tx.execute(
+                    "INSERT INTO orders
+                     (source_chain, order_id, user, recipient, token, amount, relayer_fee,
+                      source_tx, source_block, locked_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                     ON CONFLICT (source_chain, order_id) DO UPDATE SET
+                         user = excluded.user, recipient = excluded.recipient,
+                         token = excluded.token, amount = excluded.amount,
+                         relayer_fee = excluded.relayer_fee, source_tx = excluded.source_tx,
+                         source_block = excluded.source_block, locked_at = excluded.locked_at",
+                    params![
+                        to_text(chain),
+                        order_id,
+                        leg.user,
+                        leg.recipient,
+                        leg.token,
+                        leg.amount,
+                        leg.relayer_fee,
+                        leg.tx,
+                        leg.block,
+                        leg.time,
+                    ],
+                )?,
+                Leg::Destination {
+                    source_chain,
+                    order_id,
+                    leg,
+                } => tx.execute(
+                    "INSERT INTO orders
+                     (source_chain, order_id, released_token, released, released_fee, relayer,
+                      destination_tx, destination_block, unlocked_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                     ON CONFLICT (source_chain, order_id) DO UPDATE SET
+                         released_token = excluded.released_token,
+                         released = excluded.released, released_fee = excluded.released_fee,
+                         relayer = excluded.relayer,
+                         destination_tx = excluded.destination_tx,
+                         destination_block = excluded.destination_block,
+                         unlocked_at = excluded.unlocked_at",
+                    params![
+                        to_text(source_chain),
+                        order_id,
+                        leg.token,
+                        leg.amount,
+                        leg.relayer_fee,
+                        leg.relayer,
+                        leg.tx,
+                        leg.block,
+                        leg.time,
+                    ],
+                )?,
+            };
+        }
+        if let Some(cursor) = cursor {
+            tx.execute(
+                "INSERT INTO cursors (chain, position) VALUES (?1, ?2)
+                 ON CONFLICT (chain) DO UPDATE SET position = excluded.position",
+                params![to_text(&chain), cursor],
+            )?;
+        }
+        tx.commit()?;
+        Ok(legs.len())
+    }
+
+    pub fn order(&self, chain: Chain, order_id: u64) -> anyhow::Result<Option<IndexedOrder>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row(
+                "SELECT * FROM orders WHERE source_chain = ?1 AND order_id = ?2",
+                params![to_text(&chain), order_id],
+                order_from_row,
+            )
+            .optional()?)
+    }
+
+    /// Orders locked by `user`, by source chain and id.
+    pub fn by_user(&self, user: &str) -> anyhow::Result<Vec<IndexedOrder>> {
+        self.query(
+            "SELECT * FROM orders WHERE user = ?1 ORDER BY source_chain, order_id",
+            [user],
+        )
+    }
+
+    /// Orders sent to `recipient`, by source chain and id.
+    pub fn by_recipient(&self, recipient: &str) -> anyhow::Result<Vec<IndexedOrder>> {
+        self.query(
+            "SELECT * FROM orders WHERE recipient = ?1 ORDER BY source_chain, order_id",
+            [recipient],
+        )
+    }
+
+    /// Orders locked at or before unix time `locked_before` that are not unlocked yet, oldest
+    /// first.
+    pub fn pending_since(&self, locked_before: u64) -> anyhow::Result<Vec<IndexedOrder>> {
+        self.query(
+            "SELECT * FROM orders
+             WHERE source_tx IS NOT NULL AND destination_tx IS NULL AND locked_at <= ?1
+             ORDER BY locked_at, source_chain, order_id",
+            [locked_before],
+        )
+    }
+
+    /// Orders from `source_chain` that were unlocked on the other chain, by id.
+    pub fn unlocked(&self, source_chain: Chain) -> anyhow::Result<Vec<IndexedOrder>> {
+        self.query(
+            "SELECT * FROM orders
+             WHERE source_chain = ?1 AND destination_tx IS NOT NULL
+             ORDER BY order_id",
+            [to_text(&source_chain)],
+        )
+    }
+
+    /// Locked and released amounts per source chain and token.
+    pub fn volume(&self) -> anyhow::Result<Vec<TokenVolume>> {
+        let orders = self.query("SELECT * FROM orders WHERE source_tx IS NOT NULL", [])?;
+        let mut volumes: BTreeMap<(Chain, String), (u64, u64, U256, U256)> = BTreeMap::new();
+        for order in orders {
+            let Some(source) = order.source else {
+                continue;
+            };
+            let entry = volumes
+                .entry((order.source_chain, source.token))
+                .or_default();
+            entry.0 += 1;
+            entry.2 += parse_amount(&source.amount)?;
+            if let Some(destination) = order.destination {
+                entry.1 += 1;
+                entry.3 += parse_amount(&destination.amount)?;
+            }
+        }
+        Ok(volumes
+            .into_iter()
+            .map(
+                |((chain, token), (orders, completed, locked, released))| TokenVolume {
+                    chain,
+                    token,
+                    orders,
+                    completed,
+                    locked: locked.to_string(),
+                    released: released.to_string(),
+                },
+            )
+            .collect())
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> anyhow::Result<Vec<IndexedOrder>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(sql)?;
+        let orders = statement
+            .query_map(params, order_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(orders)
+    }
+}
+
+pub(crate) fn parse_amount(amount: &str) -> anyhow::Result<U256> {
+    amount
+        .parse()
+        .with_context(|| format!("invalid amount {amount}"))
+}
+
+fn order_from_row(row: &Row<'_>) -> rusqlite::Result<IndexedOrder> {
+    let source = match row.get::<_, Option<String>>("source_tx")? {
+        Some(tx) => Some(SourceLeg {
+            user: row.get("user")?,
+            recipient: row.get("recipient")?,
+            token: row.get("token")?,
+            amount: row.get("amount")?,
+            relayer_fee: row.get("relayer_fee")?,
+            tx,
+            block: row.get("source_block")?,
+            time: row.get("locked_at")?,
+        }),
+        None => None,
+    };
+    let destination = match row.get::<_, Option<String>>("destination_tx")? {
+        Some(tx) => Some(DestinationLeg {
+            token: row.get("released_token")?,
+            amount: row.get("released")?,
+            relayer_fee: row.get("released_fee")?,
+            relayer: row.get("relayer")?,
+            tx,
+            block: row.get("destination_block")?,
+            time: row.get("unlocked_at")?,
+        }),
+        None => None,
+    };
+    let state = match (&source, &destination) {
+        (_, None) => OrderState::Pending,
+        (Some(_), Some(_)) => OrderState::Completed,
+        (None, Some(_)) => OrderState::Unmatched,
+    };
+    Ok(IndexedOrder {
+        source_chain: from_text(row.get("source_chain")?)?,
+        order_id: row.get("order_id")?,
+        state,
+        source,
+        destination,
+    })
+}
+
+/// Settings of the indexer.
+#[derive(Clone, Debug)]
+pub struct IndexerConfig {
+    /// Address of the Anchor bridge program, whose events are read from the transactions.
+    pub program_id: [u8; 32],
+    /// First EVM block to read logs from, the block the bridge was deployed in.
+    pub evm_start_block: u64,
+    /// Time between polls of the two chains.
+    pub poll_interval: Duration,
+}
+
+impl Default for IndexerConfig {
+    fn default() -> Self {
+        Self {
+            program_id: parse_pubkey(PROGRAM_ID).expect("valid program id"),
+            evm_start_block: 0,
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Reads both chains into the store and answers queries about it.
+pub struct Indexer {
+    solana: Box<dyn SolanaHistory>,
+    evm: Box<dyn EvmHistory>,
+    store: IndexerStore,
+    config: IndexerConfig,
+}
+
+impl Indexer {
+    pub fn new(
+        solana: Box<dyn SolanaHistory>,
+        evm: Box<dyn EvmHistory>,
+        store: IndexerStore,
+        config: IndexerConfig,
+    ) -> Self {
+        Self {
+            solana,
+            evm,
+            store,
+            config,
+        }
+    }
+
+    pub fn store(&self) -> &IndexerStore {
+        &self.store
+    }
+
+    /// Index the history added to both chains since the last sync. Returns the number of legs
+    /// recorded.
+    pub fn sync(&self) -> anyhow::Result<usize> {
+        let cursor = self.store.cursor(Chain::Solana)?;
+        let (transactions, next) = self.solana.transactions(cursor.as_deref())?;
+        let legs: Vec<Leg> = transactions
+            .iter()
+            .flat_map(|transaction| solana_legs(transaction, &self.config.program_id))
+            .collect();
+        let mut recorded = self.store.ingest(Chain::Solana, &legs, next.as_deref())?;
+
+        let from_block = match self.store.cursor(Chain::Evm)? {
+            Some(block) => block.parse().context("invalid EVM cursor")?,
+            None => self.config.evm_start_block,
+        };
+        let (logs, next) = self.evm.logs(from_block)?;
+        let legs: Vec<Leg> = logs
+            .iter()
+            .filter(|log| !log.removed)
+            .filter_map(|log| match evm_leg(log) {
+                Ok(leg) => leg,
+                Err(e) => {
+                    tracing::warn!("skipping log of {}: {e:#}", log.transaction_hash);
+                    None
+                }
+            })
+            .collect();
+        recorded += self
+            .store
+            .ingest(Chain::Evm, &legs, Some(&next.to_string()))?;
+        Ok(recorded)
+    }
+
+    /// Start syncing in the background. It runs until the process exits.
+    pub fn spawn_sync(self: &Arc<Self>) -> JoinHandle<()> {
+        let indexer = self.clone();
+        thread::spawn(move || loop {
+            match indexer.sync() {
+                Ok(0) => {}
+                Ok(recorded) => tracing::info!("indexed {recorded} event(s)"),
+                Err(e) => tracing::warn!("failed to index the chains: {e:#}"),
+            }
+            thread::sleep(indexer.config.poll_interval);
+        })
+    }
+
+    /// Answer HTTP requests until the server is closed.
+    pub fn serve(&self, server: &Server) {
+        for request in server.incoming_requests() {
+            let (status, body) = self.handle(request.method(), request.url());
+            let response = Response::from_string(body)
+                .with_status_code(status)
+                .with_header(
+                    Header::from_bytes("Content-Type", "application/json").expect("valid header"),
+                );
+            if let Err(e) = request.respond(response) {
+                tracing::warn!("failed to send response: {e}");
+            }
+        }
+    }
+
+    fn handle(&self, method: &Method, url: &str) -> (u16, String) {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let segments: Vec<&str> = path.trim_end_matches('/').split('/').skip(1).collect();
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+        };
+
+        let result = match (method, segments.as_slice()) {
+            (Method::Get, ["orders"]) => match (param("user"), param("recipient")) {
+                (Some(user), _) => self.store.by_user(&normalize_address(user)).map(json),
+                (None, Some(recipient)) => self
+                    .store
+                    .by_recipient(&normalize_address(recipient))
+                    .map(json),
+                (None, None) => return error(400, "expected a user or recipient".to_string()),
+            },
+            (Method::Get, ["orders", "pending"]) => {
+                let Some(older_than) = param("older_than").and_then(|secs| secs.parse().ok())
+                else {
+                    return error(400, "expected older_than in seconds".to_string());
+                };
+                self.store
+                    .pending_since(unix_now().saturating_sub(older_than))
+                    .map(json)
+            }
+            (Method::Get, ["orders", chain, id]) => {
+                let (Ok(source_chain), Ok(order_id)) = (
+                    serde_json::from_value::<Chain>(Value::String(chain.to_string())),
+                    id.parse::<u64>(),
+                ) else {
+                    return error(404, format!("order {chain}/{id} not found"));
+                };
+                match self.store.order(source_chain, order_id) {
+                    Ok(Some(order)) => Ok(json(order)),
+                    Ok(None) => return error(404, format!("order {chain}/{id} not found")),
+                    Err(e) => Err(e),
+                }
+            }
+            (Method::Get, ["volume"]) => self.store.volume().map(json),
+            _ => return error(404, "not found".to_string()),
+        };
+        match result {
+            Ok(body) => (200, body),
+            Err(e) => error(500, format!("{e:#}")),
+        }
+    }
+}
+
+fn json<T: Serialize>(value: T) -> String {
+    serde_json::to_string(&value).expect("in-memory JSON serialization cannot fail")
+}
+
+/// EVM addresses are stored in lowercase; base58 is case sensitive and kept as is.
+fn normalize_address(address: &str) -> String {
+    if address.starts_with("0x") {
+        address.to_lowercase()
+    } else {
+        address.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::private::Address;
+
+    fn log(event: impl SolEvent, block: u64) -> EvmLog {
+        let data = event.encode_log_data();
+        EvmLog {
+            topics: data
+                .topics()
+                .iter()
+                .map(|topic| format!("0x{}", hex::encode(topic)))
+                .collect(),
+            data: format!("0x{}", hex::encode(&data.data)),
+            block_number: format!("{block:#x}"),
+            transaction_hash: format!("0x{}", "AB".repeat(32)),
+            block_timestamp: Some("0x64".to_string()),
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn test_decodes_evm_logs() {
+        let locked = evm::TokensLocked {
+            orderId: 4,
+            user: Address::from([0x11; 20]),
+            token: Address::from([0x22; 20]),
+            amount: U256::from(999_000u64),
+            relayerFee: U256::from(1_000u64),
+            recipient: B256::from([0x33; 32]),
+        };
+        let Some(Leg::Source {
+            chain,
+            order_id,
+            leg,
+        }) = evm_leg(&log(locked.clone(), 0x20)).unwrap()
+        else {
+            panic!("expected a source leg");
+        };
+        assert_eq!((chain, order_id), (Chain::Evm, 4));
+        assert_eq!(leg.user, format!("0x{}", "11".repeat(20)));
+        assert_eq!(leg.recipient, bs58::encode([0x33; 32]).into_string());
+        assert_eq!(leg.amount, "999000");
+        assert_eq!(leg.tx, format!("0x{}", "ab".repeat(32)));
+        assert_eq!((leg.block, leg.time), (0x20, Some(100)));
+
+        let unlocked = evm::TokensUnlocked {
+            orderId: 7,
+            relayer: Address::from([0xf3; 20]),
+            user: Address::from([0x11; 20]),
+            token: Address::from([0x22; 20]),
+            amount: U256::from(949_000u64),
+            relayerFee: U256::from(50_000u64),
+        };
+        let Some(Leg::Destination {
+            source_chain,
+            order_id,
+            leg,
+        }) = evm_leg(&log(unlocked, 0x21)).unwrap()
+        else {
+            panic!("expected a destination leg");
+        };
+        assert_eq!((source_chain, order_id), (Chain::Solana, 7));
+        assert_eq!(leg.relayer, format!("0x{}", "f3".repeat(20)));
+        assert_eq!(leg.token, format!("0x{}", "22".repeat(20)));
+        assert_eq!(
+            (leg.amount.as_str(), leg.relayer_fee.as_str()),
+            ("949000", "50000")
+        );
+
+        // Events of other types are ignored.
+        let mut other = log(locked, 0x22);
+        other.topics[0] = format!("0x{}", "00".repeat(32));
+        assert_eq!(evm_leg(&other).unwrap(), None);
+    }
+
+    #[test]
+    fn test_order_state_follows_legs() {
+        let store = IndexerStore::open_in_memory().unwrap();
+        let source = SourceLeg {
+            user: "user".to_string(),
+            recipient: "0xrecipient".to_string(),
+            token: "mint".to_string(),
+            amount: "999000".to_string(),
+            relayer_fee: "1000".to_string(),
+            tx: "lock".to_string(),
+            block: 10,
+            time: Some(1_000),
+        };
+        let destination = DestinationLeg {
+            token: "0xtoken".to_string(),
+            amount: "949000".to_string(),
+            relayer_fee: "50000".to_string(),
+            relayer: "0xrelayer".to_string(),
+            tx: "unlock".to_string(),
+            block: 20,
+            time: Some(2_000),
+        };
+
+        // The unlock is indexed before the lock it belongs to.
+        let unlock = Leg::Destination {
+            source_chain: Chain::Solana,
+            order_id: 1,
+            leg: destination.clone(),
+        };
+        store.ingest(Chain::Evm, &[unlock], Some("21")).unwrap();
+        let order = store.order(Chain::Solana, 1).unwrap().unwrap();
+        assert_eq!(order.state, OrderState::Unmatched);
+        assert!(store.volume().unwrap().is_empty());
+
+        let locks: Vec<Leg> = (1..=2)
+            .map(|order_id| Leg::Source {
+                chain: Chain::Solana,
+                order_id,
+                leg: source.clone(),
+            })
+            .collect();
+        store.ingest(Chain::Solana, &locks, Some("sig")).unwrap();
+        let order = store.order(Chain::Solana, 1).unwrap().unwrap();
+        assert_eq!(order.state, OrderState::Completed);
+        assert_eq!(order.source, Some(source));
+        assert_eq!(order.destination, Some(destination));
+        assert_eq!(
+            store.order(Chain::Solana, 2).unwrap().unwrap().state,
+            OrderState::Pending
+        );
+
+        assert_eq!(store.pending_since(999).unwrap().len(), 0);
+        assert_eq!(store.pending_since(1_000).unwrap()[0].order_id, 2);
+        assert_eq!(store.cursor(Chain::Evm).unwrap().as_deref(), Some("21"));
+        assert_eq!(
+            store.volume().unwrap(),
+            vec![TokenVolume {
+                chain: Chain::Solana,
+                token: "mint".to_string(),
+                orders: 2,
+                completed: 1,
+                locked: "1998000".to_string(),
+                released: "949000".to_string(),
+            }]
+        );
+    }
+}
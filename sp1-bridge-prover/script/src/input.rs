@@ -0,0 +1,444 @@
+//! Guest inputs (transfer order and Merkle proof) taken from files or command line flags.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::Args;
+use fibonacci_lib::{
+    bridge::{hash_order, MerkleProof, OrderStatus, TransferOrder},
+    hash::HashFunction,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::SP1Stdin;
+
+use crate::{
+    anchor::{guest_order, parse_pubkey, AnchorAccount, AnchorTokenConfig, AnchorTransferOrder},
+    snapshot::InputSnapshot,
+};
+
+/// The inputs the bridge guest reads, in order.
+#[derive(Clone, Debug)]
+pub struct BridgeInput {
+    pub order: TransferOrder,
+    pub merkle_proof: MerkleProof,
+}
+
+impl BridgeInput {
+    /// Write the inputs to an [`SP1Stdin`] in the order the guest reads them.
+    pub fn stdin(&self) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&self.order);
+        stdin.write(&self.merkle_proof);
+        stdin
+    }
+
+    /// Build the inputs from an order and its Merkle proof file. A missing leaf is the order hash,
+    /// a missing root is computed from the siblings.
+    pub fn from_files(order: OrderFile, merkle: MerkleFile) -> Self {
+        let order = TransferOrder::from(order);
+        let leaf = merkle.leaf.unwrap_or_else(|| hash_order(&order));
+        let hash = HashFunction::for_chain(order.source_chain);
+        let mut merkle_proof = MerkleProof::from_siblings(hash, leaf, merkle.proof);
+        if let Some(root) = merkle.root {
+            merkle_proof.root = root;
+        }
+        Self {
+            order,
+            merkle_proof,
+        }
+    }
+}
+
+/// Hash of the serialized guest inputs, recorded in proof bundles to tie a proof to its inputs.
+pub fn stdin_hash(stdin: &SP1Stdin) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for buffer in &stdin.buffer {
+        hasher.update((buffer.len() as u64).to_le_bytes());
+        hasher.update(buffer);
+    }
+    hasher.finalize().into()
+}
+
+/// Command line arguments describing the order and its Merkle proof.
+///
+/// Values from `--order` / `--merkle` files are used as the base, individual flags override them.
+/// Without any arguments the demo order used by the old scripts is proven.
+#[derive(Args, Debug, Clone, Default)]
+pub struct InputArgs {
+    /// Replay the exact inputs recorded in an input snapshot.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["order", "order_account", "merkle", "siblings", "root"]
+    )]
+    pub input_snapshot: Option<PathBuf>,
+
+    /// JSON file describing the transfer order.
+    #[arg(long, value_name = "FILE", conflicts_with = "order_account")]
+    pub order: Option<PathBuf>,
+
+    /// Dump of the Anchor `TransferOrder` PDA (`solana account --output json`, base64 or raw).
+    #[arg(long, value_name = "FILE")]
+    pub order_account: Option<PathBuf>,
+
+    /// Dump of the order's `TokenConfig` account, used to resolve the token mint.
+    #[arg(long, value_name = "FILE", conflicts_with = "mint")]
+    pub token_config_account: Option<PathBuf>,
+
+    /// Token mint (base58) of an order loaded with `--order-account`.
+    #[arg(long, value_parser = parse_pubkey)]
+    pub mint: Option<[u8; 32]>,
+
+    /// JSON file describing the Merkle proof (`proof` siblings, optional `leaf` and `root`).
+    #[arg(long, value_name = "FILE")]
+    pub merkle: Option<PathBuf>,
+
+    #[arg(long)]
+    pub order_id: Option<u64>,
+
+    #[arg(long, value_parser = parse_bytes32)]
+    pub user: Option<[u8; 32]>,
+
+    #[arg(long)]
+    pub source_chain: Option<u8>,
+
+    #[arg(long, value_parser = parse_bytes32)]
+    pub token: Option<[u8; 32]>,
+
+    #[arg(long)]
+    pub amount: Option<u64>,
+
+    /// Recipient address; 20-byte EVM addresses are left padded to 32 bytes.
+    #[arg(long, value_parser = parse_bytes32)]
+    pub recipient: Option<[u8; 32]>,
+
+    #[arg(long)]
+    pub relayer_fee: Option<u64>,
+
+    #[arg(long)]
+    pub created_at: Option<u64>,
+
+    /// Prove an order that is already completed (the guest is expected to reject it).
+    #[arg(long)]
+    pub completed: bool,
+
+    /// Merkle sibling hash, repeat for each level from the leaf upwards.
+    #[arg(long = "sibling", value_name = "HEX", value_parser = parse_bytes32)]
+    pub siblings: Vec<[u8; 32]>,
+
+    /// Override the Merkle root instead of computing it from the siblings.
+    #[arg(long, value_parser = parse_bytes32)]
+    pub root: Option<[u8; 32]>,
+}
+
+impl InputArgs {
+    /// Resolve the arguments into the guest inputs.
+    pub fn load(&self) -> anyhow::Result<BridgeInput> {
+        if let Some(path) = &self.input_snapshot {
+            return InputSnapshot::load(path)?.input();
+        }
+
+        let mut order = match (&self.order, &self.order_account) {
+            (Some(path), _) => read_json::<OrderFile>(path)?.into(),
+            (None, Some(path)) => {
+                let account = AnchorTransferOrder::load(path)?;
+                let mint = match (&self.token_config_account, self.mint) {
+                    (Some(path), _) => AnchorTokenConfig::load(path)?.solana_mint.to_bytes(),
+                    (None, Some(mint)) => mint,
+                    (None, None) => anyhow::bail!(
+                        "--order-account requires --token-config-account or --mint to resolve the token"
+                    ),
+                };
+                guest_order(&account, mint)
+            }
+            (None, None) => demo_order(),
+        };
+
+        if let Some(order_id) = self.order_id {
+            order.order_id = order_id;
+        }
+        if let Some(user) = self.user {
+            order.user = user;
+        }
+        if let Some(source_chain) = self.source_chain {
+            order.source_chain = source_chain;
+        }
+        if let Some(token) = self.token {
+            order.token = token;
+        }
+        if let Some(amount) = self.amount {
+            order.amount = amount;
+        }
+        if let Some(recipient) = self.recipient {
+            order.recipient = recipient;
+        }
+        if let Some(relayer_fee) = self.relayer_fee {
+            order.relayer_fee = relayer_fee;
+        }
+        if let Some(created_at) = self.created_at {
+            order.created_at = created_at;
+        }
+        if self.completed {
+            order.status = OrderStatus::Completed;
+        }
+
+        let merkle = match &self.merkle {
+            Some(path) => read_json::<MerkleFile>(path)?,
+            None => MerkleFile::default(),
+        };
+
+        let leaf = merkle.leaf.unwrap_or_else(|| hash_order(&order));
+        let siblings = if !self.siblings.is_empty() {
+            self.siblings.clone()
+        } else if self.merkle.is_some() {
+            merkle.proof
+        } else {
+            demo_siblings()
+        };
+
+        let hash = HashFunction::for_chain(order.source_chain);
+        let mut merkle_proof = MerkleProof::from_siblings(hash, leaf, siblings);
+        if let Some(root) = self.root.or(merkle.root) {
+            merkle_proof.root = root;
+        }
+
+        Ok(BridgeInput {
+            order,
+            merkle_proof,
+        })
+    }
+}
+
+/// On-disk JSON representation of a transfer order, with byte fields as hex strings.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrderFile {
+    pub order_id: u64,
+    #[serde(with = "hex_bytes32")]
+    pub user: [u8; 32],
+    pub source_chain: u8,
+    #[serde(with = "hex_bytes32")]
+    pub token: [u8; 32],
+    pub amount: u64,
+    #[serde(with = "hex_bytes32")]
+    pub recipient: [u8; 32],
+    pub relayer_fee: u64,
+    pub created_at: u64,
+    pub status: OrderStatus,
+}
+
+impl From<OrderFile> for TransferOrder {
+    fn from(file: OrderFile) -> Self {
+        TransferOrder {
+            order_id: file.order_id,
+            user: file.user,
+            source_chain: file.source_chain,
+            token: file.token,
+            amount: file.amount,
+            recipient: file.recipient,
+            relayer_fee: file.relayer_fee,
+            created_at: file.created_at,
+            status: file.status,
+        }
+    }
+}
+
+impl From<&TransferOrder> for OrderFile {
+    fn from(order: &TransferOrder) -> Self {
+        OrderFile {
+            order_id: order.order_id,
+            user: order.user,
+            source_chain: order.source_chain,
+            token: order.token,
+            amount: order.amount,
+            recipient: order.recipient,
+            relayer_fee: order.relayer_fee,
+            created_at: order.created_at,
+            status: order.status.clone(),
+        }
+    }
+}
+
+/// On-disk JSON representation of a Merkle proof.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MerkleFile {
+    #[serde(default, with = "hex_bytes32::option")]
+    pub leaf: Option<[u8; 32]>,
+    #[serde(default, with = "hex_bytes32::vec")]
+    pub proof: Vec<[u8; 32]>,
+    #[serde(default, with = "hex_bytes32::option")]
+    pub root: Option<[u8; 32]>,
+}
+
+impl From<&MerkleProof> for MerkleFile {
+    fn from(proof: &MerkleProof) -> Self {
+        MerkleFile {
+            leaf: Some(proof.leaf),
+            proof: proof.proof.clone(),
+            root: Some(proof.root),
+        }
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+/// The test order the original `prove_bridge` script used.
+pub fn demo_order() -> TransferOrder {
+    TransferOrder {
+        order_id: 1,
+        user: [0x01; 32],
+        source_chain: 0, // Solana
+        token: [0x02; 32],
+        amount: 1_000_000, // 1 USDC (6 decimals)
+        recipient: [0x03; 32],
+        relayer_fee: 10_000, // 0.01 USDC
+        created_at: 1699000000,
+        status: OrderStatus::Pending,
+    }
+}
+
+/// Placeholder siblings standing in for the rest of the state tree.
+pub fn demo_siblings() -> Vec<[u8; 32]> {
+    [b"sibling1", b"sibling2"]
+        .iter()
+        .map(|seed| Sha256::digest(seed).into())
+        .collect()
+}
+
+/// Parse a hex string of at most 32 bytes, left padding shorter values with zeros.
+pub fn parse_bytes32(s: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    if bytes.len() > 32 {
+        return Err(format!("expected at most 32 bytes, got {}", bytes.len()));
+    }
+
+    let mut out = [0u8; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Serde helpers encoding `[u8; 32]` values as `0x` prefixed hex strings.
+pub mod hex_bytes32 {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        super::parse_bytes32(&s).map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            bytes: &Option<[u8; 32]>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match bytes {
+                Some(bytes) => super::serialize(bytes, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<[u8; 32]>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] [u8; 32]);
+
+            let wrapper = Option::<Wrapper>::deserialize(deserializer)?;
+            Ok(wrapper.map(|Wrapper(bytes)| bytes))
+        }
+    }
+
+    pub mod vec {
+        use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            values: &[[u8; 32]],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(values.len()))?;
+            for value in values {
+                seq.serialize_element(&format!("0x{}", hex::encode(value)))?;
+            }
+            seq.end()
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<[u8; 32]>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapper(#[serde(with = "super")] [u8; 32]);
+
+            let values = Vec::<Wrapper>::deserialize(deserializer)?;
+            Ok(values.into_iter().map(|Wrapper(bytes)| bytes).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bytes32_pads_evm_address() {
+        let parsed = parse_bytes32("0x00112233445566778899aabbccddeeff00112233").unwrap();
+        assert_eq!(parsed[..12], [0u8; 12]);
+        assert_eq!(parsed[12], 0x00);
+        assert_eq!(parsed[31], 0x33);
+        assert!(parse_bytes32(&"11".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn test_default_input_matches_demo() {
+        let input = InputArgs::default().load().unwrap();
+        assert_eq!(input.order.order_id, 1);
+        assert_eq!(input.merkle_proof.leaf, hash_order(&input.order));
+        assert_eq!(input.merkle_proof.proof, demo_siblings());
+        assert!(input.merkle_proof.verify(HashFunction::Sha256));
+    }
+
+    #[test]
+    fn test_flags_override_order_and_root() {
+        let args = InputArgs {
+            amount: Some(42),
+            root: Some([0xaa; 32]),
+            ..Default::default()
+        };
+        let input = args.load().unwrap();
+        assert_eq!(input.order.amount, 42);
+        assert_eq!(input.merkle_proof.root, [0xaa; 32]);
+        assert!(!input.merkle_proof.verify(HashFunction::Sha256));
+    }
+
+    #[test]
+    fn test_order_file_roundtrip() {
+        let json = serde_json::json!({
+            "order_id": 7,
+            "user": "0x01",
+            "source_chain": 0,
+            "token": "0x02",
+            "amount": 5,
+            "recipient": "0x00112233445566778899aabbccddeeff00112233",
+            "relayer_fee": 1,
+            "created_at": 10,
+            "status": "Pending",
+        });
+        let file: OrderFile = serde_json::from_value(json).unwrap();
+        let order: TransferOrder = file.clone().into();
+        assert_eq!(order.order_id, 7);
+        assert_eq!(order.user[31], 0x01);
+
+        let encoded = serde_json::to_string(&file).unwrap();
+        let decoded: OrderFile = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(decoded.recipient, file.recipient);
+    }
+}
@@ -0,0 +1,86 @@
+//! Shared host-side helpers for the bridge prover binaries.
+
+use alloy_sol_types::SolType;
+use clap::ValueEnum;
+use fibonacci_lib::{bridge::BridgeProofPublicValues, hash::HashFunction};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{include_elf, SP1ProofMode};
+
+pub mod aggregate;
+pub mod anchor;
+pub mod artifact;
+pub mod audit;
+pub mod backend;
+pub mod bench;
+pub mod cache;
+pub mod config;
+pub mod indexer;
+pub mod input;
+pub mod metrics;
+pub mod relayer;
+pub mod service;
+pub mod snapshot;
+pub mod solana;
+pub mod vkeys;
+
+/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
+pub const BRIDGE_VERIFY_ELF: &[u8] = include_elf!("fibonacci-program");
+
+/// The ELF of the guest aggregating compressed bridge proofs.
+pub const AGGREGATION_ELF: &[u8] = include_elf!("aggregation-program");
+
+/// Size of the ABI encoded `BridgeProofPublicValues` committed by the guest.
+pub const PUBLIC_VALUES_LEN: usize = 288;
+
+/// The proof modes supported by the bridge prover.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProofMode {
+    Core,
+    Compressed,
+    Plonk,
+    Groth16,
+}
+
+impl From<ProofMode> for SP1ProofMode {
+    fn from(mode: ProofMode) -> Self {
+        match mode {
+            ProofMode::Core => SP1ProofMode::Core,
+            ProofMode::Compressed => SP1ProofMode::Compressed,
+            ProofMode::Plonk => SP1ProofMode::Plonk,
+            ProofMode::Groth16 => SP1ProofMode::Groth16,
+        }
+    }
+}
+
+/// Decode the guest output into the public values struct and the committed order hash.
+pub fn decode_public_values(bytes: &[u8]) -> anyhow::Result<(BridgeProofPublicValues, [u8; 32])> {
+    anyhow::ensure!(
+        bytes.len() == PUBLIC_VALUES_LEN + 32,
+        "unexpected public values length: {} bytes",
+        bytes.len()
+    );
+
+    let public_values = BridgeProofPublicValues::abi_decode(&bytes[..PUBLIC_VALUES_LEN])?;
+    let mut order_hash = [0u8; 32];
+    order_hash.copy_from_slice(&bytes[PUBLIC_VALUES_LEN..]);
+
+    Ok((public_values, order_hash))
+}
+
+/// Print the decoded public values in a human readable form.
+pub fn print_public_values(public_values: &BridgeProofPublicValues) {
+    println!("📦 Decoded Public Values:");
+    println!("   Order ID: {}", public_values.orderId);
+    println!("   Source Chain: {}", public_values.sourceChain);
+    println!("   Target Chain: {}", public_values.targetChain);
+    println!("   Token: 0x{}", hex::encode(public_values.token));
+    println!("   Amount: {}", public_values.amount);
+    println!("   Recipient: 0x{}", hex::encode(public_values.recipient));
+    println!("   State Root: 0x{}", hex::encode(public_values.stateRoot));
+    println!("   Timestamp: {}", public_values.timestamp);
+    match HashFunction::try_from(public_values.hashFunction) {
+        Ok(hash) => println!("   Hash Function: {hash}"),
+        Err(id) => println!("   Hash Function: unknown ({id})"),
+    }
+}
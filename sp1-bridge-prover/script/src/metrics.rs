@@ -0,0 +1,196 @@
+//! Prometheus metrics of the relayer and the prover service.
+//!
+//! Both processes count the orders (or jobs) reaching each stage, time their proofs and report how
+//! much work is waiting. The metrics are exposed in the OpenMetrics text format at
+//! `GET /metrics`:
+//!
+//! - `bridge_orders_total{stage}`: orders observed, proved, submitted, confirmed, retracted or
+//!   failed by the relayer, and jobs queued, proved or failed by the prover service.
+//! - `bridge_proof_duration_seconds{mode}`: time spent generating a proof.
+//! - `bridge_proof_cycles`: cycles of the guest execution before a proof.
+//! - `bridge_queue_depth`: unfinished orders of the relayer, queued jobs of the prover service.
+//! - `bridge_submission_failures_total{reason}`: failed destination chain transactions, by
+//!   `claim`, `send` or `reverted`.
+//!
+//! A stuck bridge shows as a growing queue depth while `bridge_orders_total{stage="confirmed"}`
+//! stays flat.
+
+use std::{io::Cursor, time::Duration};
+
+use prometheus_client::{
+    encoding::{text::encode, EncodeLabelSet},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, Histogram},
+    },
+    registry::Registry,
+};
+use tiny_http::{Header, Response, Server};
+
+use crate::{service::to_text, ProofMode};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct StageLabels {
+    stage: &'static str,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ModeLabels {
+    mode: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct ReasonLabels {
+    reason: &'static str,
+}
+
+/// The metrics of one process.
+pub struct Metrics {
+    registry: Registry,
+    orders: Family<StageLabels, Counter>,
+    proof_duration: Family<ModeLabels, Histogram, fn() -> Histogram>,
+    proof_cycles: Histogram,
+    queue_depth: Gauge,
+    submission_failures: Family<ReasonLabels, Counter>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let orders = Family::<StageLabels, Counter>::default();
+        // Mock proofs take milliseconds, Groth16 proofs on a CPU up to hours.
+        let proof_duration: Family<ModeLabels, Histogram, fn() -> Histogram> =
+            Family::new_with_constructor(|| Histogram::new(exponential_buckets(0.01, 2.0, 22)));
+        let proof_cycles = Histogram::new(exponential_buckets(10_000.0, 4.0, 12));
+        let queue_depth = Gauge::default();
+        let submission_failures = Family::<ReasonLabels, Counter>::default();
+
+        let mut registry = Registry::default();
+        registry.register(
+            "bridge_orders",
+            "Orders or jobs that reached a stage",
+            orders.clone(),
+        );
+        registry.register(
+            "bridge_proof_duration_seconds",
+            "Time spent generating a proof",
+            proof_duration.clone(),
+        );
+        registry.register(
+            "bridge_proof_cycles",
+            "Cycles of the guest execution",
+            proof_cycles.clone(),
+        );
+        registry.register(
+            "bridge_queue_depth",
+            "Orders or jobs waiting to be finished",
+            queue_depth.clone(),
+        );
+        registry.register(
+            "bridge_submission_failures",
+            "Failed destination chain transactions",
+            submission_failures.clone(),
+        );
+
+        Self {
+            registry,
+            orders,
+            proof_duration,
+            proof_cycles,
+            queue_depth,
+            submission_failures,
+        }
+    }
+
+    /// Count `count` orders reaching `stage`.
+    pub fn reached(&self, stage: &'static str, count: u64) {
+        self.orders
+            .get_or_create(&StageLabels { stage })
+            .inc_by(count);
+    }
+
+    pub fn proved(&self, mode: ProofMode, duration: Duration) {
+        self.reached("proved", 1);
+        self.proof_duration
+            .get_or_create(&ModeLabels {
+                mode: to_text(&mode),
+            })
+            .observe(duration.as_secs_f64());
+    }
+
+    pub fn executed(&self, cycles: u64) {
+        self.proof_cycles.observe(cycles as f64);
+    }
+
+    pub fn set_queue_depth(&self, depth: usize) {
+        self.queue_depth.set(depth as i64);
+    }
+
+    pub fn submission_failed(&self, reason: &'static str) {
+        self.submission_failures
+            .get_or_create(&ReasonLabels { reason })
+            .inc();
+    }
+
+    /// The metrics in the OpenMetrics text format.
+    pub fn encode(&self) -> String {
+        let mut text = String::new();
+        encode(&mut text, &self.registry).expect("writing to a string cannot fail");
+        text
+    }
+
+    /// The answer to `GET /metrics`.
+    pub fn response(&self) -> Response<Cursor<Vec<u8>>> {
+        Response::from_string(self.encode()).with_header(
+            Header::from_bytes(
+                "Content-Type",
+                "application/openmetrics-text; version=1.0.0; charset=utf-8",
+            )
+            .expect("valid header"),
+        )
+    }
+
+    /// Answer `GET /metrics` until the server is closed.
+    pub fn serve(&self, server: &Server) {
+        for request in server.incoming_requests() {
+            let response = match request.url() {
+                "/metrics" => self.response(),
+                _ => Response::from_string("not found").with_status_code(404),
+            };
+            if let Err(e) = request.respond(response) {
+                tracing::warn!("failed to send response: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encodes_metrics() {
+        let metrics = Metrics::new();
+        metrics.reached("observed", 2);
+        metrics.proved(ProofMode::Groth16, Duration::from_secs(90));
+        metrics.executed(1_500_000);
+        metrics.set_queue_depth(3);
+        metrics.submission_failed("reverted");
+
+        let text = metrics.encode();
+        assert!(text.contains("bridge_orders_total{stage=\"observed\"} 2"));
+        assert!(text.contains("bridge_orders_total{stage=\"proved\"} 1"));
+        assert!(text.contains("bridge_proof_duration_seconds_count{mode=\"groth16\"} 1"));
+        assert!(text.contains("bridge_proof_cycles_sum 1500000.0"));
+        assert!(text.contains("bridge_queue_depth 3"));
+        assert!(text.contains("bridge_submission_failures_total{reason=\"reverted\"} 1"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
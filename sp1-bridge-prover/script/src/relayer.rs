@@ -0,0 +1,1610 @@
+//! Relayer carrying orders locked on Solana to the EVM bridge.
+//!
+//! The relayer polls the transaction history of the Anchor bridge program for `TokensLocked`
+//! events and reads the account of every new order. Each order hash is added to the relayer's order
+//! tree, and the order is proven against the tree root. The proof hash is then submitted with
+//! `unlockTokens` on the EVM bridge.
+//!
+//! Orders are read at the configured commitment but only proven once their slot is final. Until
+//! then the relayer re-reads the hash of the block each order was locked in, and retracts orders
+//! whose block left the canonical fork. Submitted transactions count as confirmed once they are
+//! the configured number of blocks deep.
+//!
+//! Before proving an order, the relayer claims it on the destination contract, which keeps the
+//! order exclusive to this relayer for a window of blocks. Orders claimed by another relayer are
+//! left alone until that claim expires.
+//!
+//! Every order moves through `observed` → `proving` → `proved` → `submitted` → `confirmed`, or
+//! ends in `failed` after too many failed attempts. Each state is persisted in SQLite before the
+//! next side effect, so a relayer killed at any point resumes from the last recorded state. Before
+//! submitting, the relayer asks the destination contract whether the order is already completed,
+//! reading the latest block, so a transaction sent just before a crash is never sent twice; the
+//! order is confirmed once that completion has enough confirmations. The position in the source
+//! transaction history is stored as well, so a restarted relayer neither misses nor re-reads
+//! orders.
+//!
+//! Each step runs in a `step` tracing span carrying the order id and status, and its outcome is
+//! counted in the relayer's [`Metrics`].
+//!
+//! Both chains are reached through the [`SourceChain`] and [`DestinationChain`] traits. The
+//! JSON-RPC implementations target a local test validator and an Anvil node, whose unlocked dev
+//! accounts sign the transactions.
+
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use alloy_sol_types::private::U256;
+use alloy_sol_types::SolCall;
+use anchor_lang::Discriminator;
+use anyhow::Context;
+use fibonacci_lib::{
+    bridge::{hash_order, MerkleProof, MerkleTree, TransferOrder},
+    hash::{HashFunction, Keccak256Hasher, Sha256Hasher},
+};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues};
+
+use crate::{
+    anchor::{
+        account_data_from_json, events, guest_order, parse_pubkey, AnchorAccount,
+        AnchorTokenConfig, AnchorTransferOrder, BridgeEvent, TokensLocked,
+    },
+    artifact::unix_now,
+    backend::{ProofBackend, BRIDGE_PROGRAM},
+    decode_public_values,
+    input::BridgeInput,
+    metrics::Metrics,
+    service::{decode_blob, from_text, to_text},
+    ProofMode,
+};
+
+/// Calls of the `EVMSolanaBridge` contract.
+mod evm {
+    alloy_sol_types::sol! {
+        struct TransferOrder {
+            uint64 orderId;
+            address user;
+            uint8 sourceChain;
+            address tokenConfig;
+            uint256 amount;
+            bytes32 recipient;
+            uint256 relayerFee;
+            uint256 createdBlock;
+            uint8 status;
+            bytes32 proofHash;
+            address completedBy;
+            uint256 completedAt;
+            address claimedBy;
+            uint256 claimExpiry;
+            uint256 claimBond;
+        }
+
+        function claimOrder(uint64 orderId) external payable;
+        function unlockTokens(uint64 orderId, bytes32 proofHash) external;
+        function getTransferOrder(uint64 orderId) external view returns (TransferOrder memory order);
+        function claimBond() external view returns (uint256);
+        function claimWindowBlocks() external view returns (uint64);
+    }
+
+    /// `OrderStatus.Completed` in the contract.
+    pub const COMPLETED: u8 = 1;
+}
+
+/// Largest page `getSignaturesForAddress` returns.
+const SIGNATURE_PAGE: usize = 1000;
+
+/// A `TokensLocked` event and the transaction that emitted it.
+pub struct LockedOrder {
+    pub event: TokensLocked,
+    pub signature: String,
+    pub slot: u64,
+    /// Hash of the block at `slot` when the order was read.
+    pub block_hash: String,
+}
+
+/// The chain orders are locked on.
+pub trait SourceChain: Send + Sync {
+    /// Orders locked in transactions after `cursor`, oldest first, and the cursor to continue
+    /// from. `None` starts at the beginning of the history.
+    fn locked_orders(
+        &self,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<(Vec<LockedOrder>, Option<String>)>;
+
+    /// The order as the guest proves it.
+    fn order(&self, order_id: u64) -> anyhow::Result<TransferOrder>;
+
+    /// Hash of the block at `slot` on the current fork, `None` if the fork has no block there.
+    fn block_hash(&self, slot: u64) -> anyhow::Result<Option<String>>;
+
+    /// Newest slot that can no longer be rolled back.
+    fn finalized_slot(&self) -> anyhow::Result<u64>;
+}
+
+/// A relayer's exclusive claim on an order, ending at block `expiry`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Claim {
+    /// Held by this relayer.
+    Ours { expiry: u64 },
+    /// Held by another relayer.
+    Theirs { relayer: [u8; 20], expiry: u64 },
+}
+
+/// The chain orders are completed on.
+pub trait DestinationChain: Send + Sync {
+    /// Whether the order is completed in the latest block, by this relayer or another one. Read
+    /// before anything is sent for the order, so a completion that is not final yet still counts.
+    fn is_completed(&self, order_id: u64) -> anyhow::Result<bool>;
+
+    /// Whether the order is completed in the newest block with enough confirmations.
+    fn is_completed_final(&self, order_id: u64) -> anyhow::Result<bool>;
+
+    /// Claim the order unless a claim on it is still active and wait for the claim to be mined.
+    /// Returns the active claim, which is ours if this relayer held it already or its claim went
+    /// through, and fails if the claim reverted without another relayer holding the order.
+    fn claim(&self, order_id: u64) -> anyhow::Result<Claim>;
+
+    /// Send `unlockTokens` for the order and return the transaction hash without waiting for it to
+    /// be mined.
+    fn submit(&self, order_id: u64, proof_hash: [u8; 32]) -> anyhow::Result<[u8; 32]>;
+
+    /// Outcome of a submitted transaction: `None` until it is mined and final, otherwise whether
+    /// it succeeded.
+    fn receipt(&self, tx_hash: [u8; 32]) -> anyhow::Result<Option<bool>>;
+}
+
+/// An error object returned by a JSON-RPC node.
+#[derive(Clone, Debug, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// A minimal blocking JSON-RPC 2.0 client.
+pub struct JsonRpc {
+    url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl JsonRpc {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    pub fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> anyhow::Result<T> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .json()
+            .with_context(|| format!("invalid {method} response"))?;
+        if let Some(error) = response.get("error") {
+            let Ok(error) = serde_json::from_value::<RpcError>(error.clone()) else {
+                anyhow::bail!("{method} failed: {error}");
+            };
+            return Err(anyhow::Error::new(error).context(format!("{method} failed")));
+        }
+        serde_json::from_value(response.get("result").cloned().unwrap_or(Value::Null))
+            .with_context(|| format!("unexpected {method} result"))
+    }
+}
+
+/// `getBlock` errors for slots without a block: skipped, or on a fork that was abandoned.
+const SLOT_SKIPPED: [i64; 2] = [-32007, -32009];
+
+/// The Anchor bridge program on a Solana cluster.
+pub struct SolanaRpc {
+    rpc: JsonRpc,
+    program_id: [u8; 32],
+    /// Commitment orders are read at.
+    commitment: String,
+    /// Commitment a slot must reach before its orders are proven.
+    finality: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SignatureInfo {
+    pub(crate) signature: String,
+    pub(crate) slot: u64,
+    pub(crate) err: Option<Value>,
+}
+
+impl SolanaRpc {
+    pub fn new(
+        url: impl Into<String>,
+        program_id: [u8; 32],
+        commitment: impl Into<String>,
+    ) -> Self {
+        Self {
+            rpc: JsonRpc::new(url),
+            program_id,
+            commitment: commitment.into(),
+            finality: "finalized".to_string(),
+        }
+    }
+
+    pub fn with_finality(mut self, finality: impl Into<String>) -> Self {
+        self.finality = finality.into();
+        self
+    }
+
+    /// Signatures of the program's transactions after `cursor`, newest first.
+    pub(crate) fn signatures(&self, cursor: Option<&str>) -> anyhow::Result<Vec<SignatureInfo>> {
+        let mut signatures: Vec<SignatureInfo> = Vec::new();
+        loop {
+            let mut config = json!({ "limit": SIGNATURE_PAGE, "commitment": self.commitment });
+            if let Some(cursor) = cursor {
+                config["until"] = json!(cursor);
+            }
+            if let Some(last) = signatures.last() {
+                config["before"] = json!(last.signature);
+            }
+            let page: Vec<SignatureInfo> = self.rpc.call(
+                "getSignaturesForAddress",
+                json!([bs58::encode(self.program_id).into_string(), config]),
+            )?;
+            let done = page.len() < SIGNATURE_PAGE;
+            signatures.extend(page);
+            if done {
+                return Ok(signatures);
+            }
+        }
+    }
+
+    /// The transaction as returned by `getTransaction`.
+    pub(crate) fn transaction(&self, signature: &str) -> anyhow::Result<Value> {
+        self.rpc.call(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "json",
+                "commitment": self.commitment,
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )
+    }
+
+    pub(crate) fn program_id(&self) -> &[u8; 32] {
+        &self.program_id
+    }
+
+    /// Every account of type `T` owned by the program, with its address.
+    pub(crate) fn program_accounts<T: AnchorAccount>(&self) -> anyhow::Result<Vec<([u8; 32], T)>> {
+        let filters = json!([
+            { "memcmp": { "offset": 0, "bytes": bs58::encode(T::DISCRIMINATOR).into_string() } },
+        ]);
+        let accounts: Vec<Value> = self.rpc.call(
+            "getProgramAccounts",
+            json!([
+                bs58::encode(self.program_id).into_string(),
+                { "encoding": "base64", "commitment": self.commitment, "filters": filters },
+            ]),
+        )?;
+        accounts
+            .iter()
+            .map(|account| {
+                let address = account
+                    .get("pubkey")
+                    .and_then(Value::as_str)
+                    .context("program account without an address")?;
+                let address = parse_pubkey(address).map_err(anyhow::Error::msg)?;
+                let data = account_data_from_json(account)?;
+                Ok((address, T::decode(&data)?))
+            })
+            .collect()
+    }
+
+    pub(crate) fn account(&self, address: &[u8; 32]) -> anyhow::Result<Vec<u8>> {
+        let address = bs58::encode(address).into_string();
+        let result: Value = self.rpc.call(
+            "getAccountInfo",
+            json!([address, { "encoding": "base64", "commitment": self.commitment }]),
+        )?;
+        let value = result
+            .get("value")
+            .filter(|value| !value.is_null())
+            .with_context(|| format!("account {address} not found"))?;
+        account_data_from_json(value)
+    }
+}
+
+impl SourceChain for SolanaRpc {
+    fn locked_orders(
+        &self,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<(Vec<LockedOrder>, Option<String>)> {
+        let signatures = self.signatures(cursor)?;
+        let next = signatures
+            .first()
+            .map(|info| info.signature.clone())
+            .or_else(|| cursor.map(str::to_string));
+
+        let mut orders = Vec::new();
+        let mut block_hash: Option<(u64, Option<String>)> = None;
+        for info in signatures.iter().rev().filter(|info| info.err.is_none()) {
+            if block_hash
+                .as_ref()
+                .is_none_or(|(slot, _)| *slot != info.slot)
+            {
+                block_hash = Some((info.slot, self.block_hash(info.slot)?));
+            }
+            // The transaction was seen on a fork that has been abandoned since.
+            let Some((_, Some(hash))) = &block_hash else {
+                continue;
+            };
+
+            let transaction = self.transaction(&info.signature)?;
+            let logs: Vec<String> = transaction
+                .pointer("/meta/logMessages")
+                .cloned()
+                .map(serde_json::from_value)
+                .transpose()?
+                .unwrap_or_default();
+            orders.extend(
+                events(&logs, &self.program_id)
+                    .into_iter()
+                    .filter_map(|event| match event {
+                        BridgeEvent::TokensLocked(event) => Some(event),
+                        _ => None,
+                    })
+                    .map(|event| LockedOrder {
+                        event,
+                        signature: info.signature.clone(),
+                        slot: info.slot,
+                        block_hash: hash.clone(),
+                    }),
+            );
+        }
+        Ok((orders, next))
+    }
+
+    fn order(&self, order_id: u64) -> anyhow::Result<TransferOrder> {
+        // Match the account type and the leading `order_id` field.
+        let filters = json!([
+            { "memcmp": { "offset": 0, "bytes": bs58::encode(AnchorTransferOrder::DISCRIMINATOR).into_string() } },
+            { "memcmp": { "offset": 8, "bytes": bs58::encode(order_id.to_le_bytes()).into_string() } },
+        ]);
+        let accounts: Vec<Value> = self.rpc.call(
+            "getProgramAccounts",
+            json!([
+                bs58::encode(self.program_id).into_string(),
+                { "encoding": "base64", "commitment": self.commitment, "filters": filters },
+            ]),
+        )?;
+        let account = accounts
+            .first()
+            .with_context(|| format!("order {order_id} account not found"))?;
+        let order = AnchorTransferOrder::decode(&account_data_from_json(account)?)?;
+
+        let token_config =
+            AnchorTokenConfig::decode(&self.account(&order.token_config.to_bytes())?)?;
+        Ok(guest_order(&order, token_config.solana_mint.to_bytes()))
+    }
+
+    fn block_hash(&self, slot: u64) -> anyhow::Result<Option<String>> {
+        // `getBlock` does not serve `processed` blocks; such orders wait until they are confirmed.
+        let commitment = match self.commitment.as_str() {
+            "processed" => "confirmed",
+            commitment => commitment,
+        };
+        let result = self.rpc.call::<Value>(
+            "getBlock",
+            json!([slot, {
+                "commitment": commitment,
+                "transactionDetails": "none",
+                "rewards": false,
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        );
+        match result {
+            Ok(block) => {
+                let hash = block
+                    .get("blockhash")
+                    .and_then(Value::as_str)
+                    .with_context(|| format!("block {slot} has no hash"))?;
+                Ok(Some(hash.to_string()))
+            }
+            Err(e)
+                if e.downcast_ref::<RpcError>()
+                    .is_some_and(|error| SLOT_SKIPPED.contains(&error.code)) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn finalized_slot(&self) -> anyhow::Result<u64> {
+        self.rpc
+            .call("getSlot", json!([{ "commitment": self.finality }]))
+    }
+}
+
+/// How often and how long [`EvmRpc`] polls for a claim transaction to be mined.
+const MINING_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const MINING_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The EVM bridge contract on an Anvil node.
+pub struct EvmRpc {
+    rpc: JsonRpc,
+    bridge: [u8; 20],
+    from: [u8; 20],
+    /// Blocks a transaction must be deep before it counts as final, including its own block.
+    confirmations: u64,
+}
+
+impl EvmRpc {
+    /// Connect to the bridge at `bridge`. Transactions are sent from `from`, by default the first
+    /// unlocked account of the node.
+    pub fn new(
+        url: impl Into<String>,
+        bridge: [u8; 20],
+        from: Option<[u8; 20]>,
+    ) -> anyhow::Result<Self> {
+        let rpc = JsonRpc::new(url);
+        let from = match from {
+            Some(from) => from,
+            None => {
+                let accounts: Vec<String> = rpc.call("eth_accounts", json!([]))?;
+                let account = accounts
+                    .first()
+                    .context("the node has no unlocked accounts")?;
+                parse_address(account).map_err(|e| anyhow::anyhow!(e))?
+            }
+        };
+        Ok(Self {
+            rpc,
+            bridge,
+            from,
+            confirmations: 1,
+        })
+    }
+
+    /// Connect to the bridge at `bridge` for reading only, without an account to send from.
+    pub fn read_only(url: impl Into<String>, bridge: [u8; 20]) -> Self {
+        Self {
+            rpc: JsonRpc::new(url),
+            bridge,
+            from: [0; 20],
+            confirmations: 1,
+        }
+    }
+
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations.max(1);
+        self
+    }
+
+    /// Fail unless the node serves chain `expected`, when one is configured.
+    pub fn check_chain_id(&self, expected: Option<u64>) -> anyhow::Result<()> {
+        if let Some(expected) = expected {
+            let chain_id: String = self.rpc.call("eth_chainId", json!([]))?;
+            let chain_id = parse_quantity(&chain_id)?;
+            anyhow::ensure!(
+                chain_id == expected,
+                "the EVM node serves chain {chain_id}, expected {expected}"
+            );
+        }
+        Ok(())
+    }
+
+    fn block_number(&self) -> anyhow::Result<u64> {
+        let number: String = self.rpc.call("eth_blockNumber", json!([]))?;
+        parse_quantity(&number)
+    }
+
+    /// Newest block with enough confirmations.
+    pub(crate) fn final_block_number(&self) -> anyhow::Result<u64> {
+        Ok(self.block_number()?.saturating_sub(self.confirmations - 1))
+    }
+
+    /// Logs of the bridge in blocks `from..=to`, as returned by `eth_getLogs`.
+    pub(crate) fn bridge_logs(&self, from: u64, to: u64) -> anyhow::Result<Vec<Value>> {
+        self.rpc.call(
+            "eth_getLogs",
+            json!([{
+                "address": format!("0x{}", hex::encode(self.bridge)),
+                "fromBlock": format!("{from:#x}"),
+                "toBlock": format!("{to:#x}"),
+            }]),
+        )
+    }
+
+    /// Unix time of block `number`.
+    pub(crate) fn block_timestamp(&self, number: u64) -> anyhow::Result<u64> {
+        let block: Value = self.rpc.call(
+            "eth_getBlockByNumber",
+            json!([format!("{number:#x}"), false]),
+        )?;
+        let timestamp = block
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .with_context(|| format!("block {number} not found"))?;
+        parse_quantity(timestamp)
+    }
+
+    fn call<C: SolCall>(&self, call: &C, block: String) -> anyhow::Result<C::Return> {
+        let result: String = self.rpc.call(
+            "eth_call",
+            json!([{
+                "to": format!("0x{}", hex::encode(self.bridge)),
+                "data": format!("0x{}", hex::encode(call.abi_encode())),
+            }, block]),
+        )?;
+        let result =
+            hex::decode(result.trim_start_matches("0x")).context("invalid eth_call result")?;
+        C::abi_decode_returns(&result)
+            .with_context(|| format!("failed to decode {} result", C::SIGNATURE))
+    }
+
+    /// Send a transaction to the bridge and return its hash.
+    fn send<C: SolCall>(&self, call: &C, value: U256) -> anyhow::Result<[u8; 32]> {
+        let tx_hash: String = self.rpc.call(
+            "eth_sendTransaction",
+            json!([{
+                "from": format!("0x{}", hex::encode(self.from)),
+                "to": format!("0x{}", hex::encode(self.bridge)),
+                "data": format!("0x{}", hex::encode(call.abi_encode())),
+                "value": format!("{value:#x}"),
+            }]),
+        )?;
+        let mut hash = [0u8; 32];
+        hex::decode_to_slice(tx_hash.trim_start_matches("0x"), &mut hash)
+            .with_context(|| format!("invalid transaction hash {tx_hash}"))?;
+        Ok(hash)
+    }
+
+    /// The claim on the order as of the latest block, `None` if there is none or it expired.
+    /// Our own claim is returned even once it expired: the contract does not let a relayer claim
+    /// an order again, but the claiming relayer may still unlock it.
+    fn active_claim(&self, order_id: u64) -> anyhow::Result<Option<Claim>> {
+        let block = self.block_number()?;
+        let order = self.call(
+            &evm::getTransferOrderCall { orderId: order_id },
+            "latest".to_string(),
+        )?;
+        let expiry = u64::try_from(order.claimExpiry).unwrap_or(u64::MAX);
+        let relayer: [u8; 20] = order.claimedBy.into();
+        if relayer == self.from {
+            return Ok(Some(Claim::Ours { expiry }));
+        }
+        if order.claimedBy.is_zero() || block >= expiry {
+            return Ok(None);
+        }
+        Ok(Some(Claim::Theirs { relayer, expiry }))
+    }
+
+    /// Wait for a transaction to be included in a block, without waiting for confirmations, and
+    /// return whether it succeeded.
+    fn wait_mined(&self, tx_hash: [u8; 32]) -> anyhow::Result<bool> {
+        let started = Instant::now();
+        loop {
+            let receipt: Value = self.rpc.call(
+                "eth_getTransactionReceipt",
+                json!([format!("0x{}", hex::encode(tx_hash))]),
+            )?;
+            if !receipt.is_null() {
+                return Ok(receipt.get("status").and_then(Value::as_str) == Some("0x1"));
+            }
+            anyhow::ensure!(
+                started.elapsed() < MINING_TIMEOUT,
+                "transaction 0x{} was not mined within {MINING_TIMEOUT:?}",
+                hex::encode(tx_hash)
+            );
+            thread::sleep(MINING_POLL_INTERVAL);
+        }
+    }
+
+    /// Block tag of the newest block with enough confirmations.
+    fn final_block(&self) -> anyhow::Result<String> {
+        if self.confirmations == 1 {
+            return Ok("latest".to_string());
+        }
+        Ok(format!("{:#x}", self.final_block_number()?))
+    }
+}
+
+pub(crate) fn parse_quantity(quantity: &str) -> anyhow::Result<u64> {
+    u64::from_str_radix(quantity.trim_start_matches("0x"), 16)
+        .with_context(|| format!("invalid quantity {quantity}"))
+}
+
+impl DestinationChain for EvmRpc {
+    fn is_completed(&self, order_id: u64) -> anyhow::Result<bool> {
+        let call = evm::getTransferOrderCall { orderId: order_id };
+        let order = self.call(&call, "latest".to_string())?;
+        Ok(order.status == evm::COMPLETED)
+    }
+
+    fn is_completed_final(&self, order_id: u64) -> anyhow::Result<bool> {
+        let call = evm::getTransferOrderCall { orderId: order_id };
+        let order = self.call(&call, self.final_block()?)?;
+        Ok(order.status == evm::COMPLETED)
+    }
+
+    fn claim(&self, order_id: u64) -> anyhow::Result<Claim> {
+        if let Some(claim) = self.active_claim(order_id)? {
+            return Ok(claim);
+        }
+
+        let bond = self.call(&evm::claimBondCall {}, "latest".to_string())?;
+        let tx_hash = self
+            .send(&evm::claimOrderCall { orderId: order_id }, bond)
+            .context("failed to submit claimOrder")?;
+        let success = self.wait_mined(tx_hash)?;
+        // Another relayer may have claimed the order first, in which case ours reverted.
+        match self.active_claim(order_id)? {
+            Some(claim) => Ok(claim),
+            None if success => anyhow::bail!("claimOrder was mined but the order is not claimed"),
+            None => anyhow::bail!("claimOrder 0x{} reverted", hex::encode(tx_hash)),
+        }
+    }
+
+    fn submit(&self, order_id: u64, proof_hash: [u8; 32]) -> anyhow::Result<[u8; 32]> {
+        let call = evm::unlockTokensCall {
+            orderId: order_id,
+            proofHash: proof_hash.into(),
+        };
+        self.send(&call, U256::ZERO)
+    }
+
+    fn receipt(&self, tx_hash: [u8; 32]) -> anyhow::Result<Option<bool>> {
+        let receipt: Value = self.rpc.call(
+            "eth_getTransactionReceipt",
+            json!([format!("0x{}", hex::encode(tx_hash))]),
+        )?;
+        if receipt.is_null() {
+            return Ok(None);
+        }
+        if self.confirmations > 1 {
+            let mined = receipt
+                .get("blockNumber")
+                .and_then(Value::as_str)
+                .context("receipt has no block number")?;
+            // A reorg moves the transaction to another block and restarts the count.
+            if self.block_number()? + 1 < parse_quantity(mined)? + self.confirmations {
+                return Ok(None);
+            }
+        }
+        Ok(Some(
+            receipt.get("status").and_then(Value::as_str) == Some("0x1"),
+        ))
+    }
+}
+
+/// Parse a hex EVM address.
+pub fn parse_address(s: &str) -> Result<[u8; 20], String> {
+    let bytes = hex::decode(s.trim_start_matches("0x")).map_err(|e| e.to_string())?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 20 bytes, got {}", bytes.len()))
+}
+
+/// Progress of a relayed order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelayStatus {
+    /// Locked on the source chain, nothing done yet. Waits here until its slot is final.
+    Observed,
+    /// A proof is being generated. A relayer resuming in this state proves again.
+    Proving,
+    /// The proof is stored and checked, the order is not submitted yet.
+    Proved,
+    /// `unlockTokens` was sent in `tx_hash` and is waiting to be mined.
+    Submitted,
+    /// Completed on the destination chain.
+    Confirmed,
+    /// Gave up after too many failed attempts.
+    Failed,
+    /// The block the order was locked in left the canonical fork before it became final.
+    Retracted,
+}
+
+impl RelayStatus {
+    /// States the relayer still has work to do in.
+    pub const UNFINISHED: [RelayStatus; 4] = [
+        RelayStatus::Observed,
+        RelayStatus::Proving,
+        RelayStatus::Proved,
+        RelayStatus::Submitted,
+    ];
+}
+
+/// An order as stored by the relayer.
+#[derive(Clone, Debug)]
+pub struct RelayedOrder {
+    pub order_id: u64,
+    pub order: TransferOrder,
+    /// Leaf of the order in the order tree.
+    pub leaf: [u8; 32],
+    /// Transaction that locked the order.
+    pub signature: String,
+    pub slot: u64,
+    /// Hash of the block at `slot` when the order was observed.
+    pub block_hash: String,
+    /// Whether `slot` is final, so the order can no longer be rolled back.
+    pub finalized: bool,
+    pub status: RelayStatus,
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub proof_hash: Option<[u8; 32]>,
+    pub tx_hash: Option<[u8; 32]>,
+    /// The claim on the order last seen on the destination chain.
+    pub claim: Option<Claim>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS orders (
+    order_id INTEGER PRIMARY KEY,
+    transfer_order BLOB NOT NULL,
+    leaf BLOB NOT NULL,
+    signature TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    block_hash TEXT NOT NULL,
+    finalized INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    proof BLOB,
+    proof_hash BLOB,
+    tx_hash BLOB,
+    claimed_by BLOB,
+    claim_expiry INTEGER,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS orders_status ON orders (status, order_id);
+CREATE TABLE IF NOT EXISTS cursor (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    signature TEXT NOT NULL
+);
+";
+
+/// The relayer's SQLite database of observed orders.
+pub struct RelayerStore {
+    conn: Mutex<Connection>,
+}
+
+impl RelayerStore {
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open relayer database {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    /// A store that lives in memory only, for tests.
+    pub fn open_in_memory() -> anyhow::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> anyhow::Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("failed to create the relayer tables")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Signature of the newest source transaction already processed.
+    pub fn cursor(&self) -> anyhow::Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row("SELECT signature FROM cursor WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    /// Record newly observed orders and move the cursor past them in one transaction. Orders that
+    /// are already known are left untouched, unless they were retracted: an order id seen again on
+    /// the canonical fork starts over. Returns the number of new orders.
+    pub fn observe(
+        &self,
+        orders: &[(LockedOrder, TransferOrder)],
+        cursor: Option<&str>,
+    ) -> anyhow::Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let now = unix_now();
+        let mut added = 0;
+        for (locked, order) in orders {
+            added += tx.execute(
+                "INSERT INTO orders
+                 (order_id, transfer_order, leaf, signature, slot, block_hash, status, created_at,
+                  updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)
+                 ON CONFLICT (order_id) DO UPDATE SET
+                     transfer_order = excluded.transfer_order, leaf = excluded.leaf,
+                     signature = excluded.signature, slot = excluded.slot,
+                     block_hash = excluded.block_hash, finalized = 0, status = excluded.status,
+                     attempts = 0, error = NULL, proof = NULL, proof_hash = NULL, tx_hash = NULL,
+                     claimed_by = NULL, claim_expiry = NULL, updated_at = excluded.updated_at
+                 WHERE orders.status = ?9",
+                params![
+                    order.order_id,
+                    bincode::serialize(order)?,
+                    hash_order(order),
+                    locked.signature,
+                    locked.slot,
+                    locked.block_hash,
+                    to_text(&RelayStatus::Observed),
+                    now,
+                    to_text(&RelayStatus::Retracted),
+                ],
+            )?;
+        }
+        if let Some(cursor) = cursor {
+            tx.execute(
+                "INSERT INTO cursor (id, signature) VALUES (0, ?1)
+                 ON CONFLICT (id) DO UPDATE SET signature = excluded.signature",
+                [cursor],
+            )?;
+        }
+        tx.commit()?;
+        Ok(added)
+    }
+
+    pub fn get(&self, order_id: u64) -> anyhow::Result<Option<RelayedOrder>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row(
+                "SELECT * FROM orders WHERE order_id = ?1",
+                [order_id],
+                order_from_row,
+            )
+            .optional()?)
+    }
+
+    /// Orders with the given status, by order id.
+    pub fn with_status(&self, status: RelayStatus) -> anyhow::Result<Vec<RelayedOrder>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement =
+            conn.prepare("SELECT * FROM orders WHERE status = ?1 ORDER BY order_id")?;
+        let orders = statement
+            .query_map([to_text(&status)], order_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(orders)
+    }
+
+    /// Orders the relayer still has to finish, by order id.
+    pub fn unfinished(&self) -> anyhow::Result<Vec<RelayedOrder>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn
+            .prepare("SELECT * FROM orders WHERE status IN (?1, ?2, ?3, ?4) ORDER BY order_id")?;
+        let [a, b, c, d] = RelayStatus::UNFINISHED.map(|status| to_text(&status));
+        let orders = statement
+            .query_map([a, b, c, d], order_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(orders)
+    }
+
+    /// Observed orders whose slot is not final yet, by order id.
+    pub fn pending_finality(&self) -> anyhow::Result<Vec<RelayedOrder>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement = conn.prepare(
+            "SELECT * FROM orders WHERE status = ?1 AND finalized = 0 ORDER BY order_id",
+        )?;
+        let orders = statement
+            .query_map([to_text(&RelayStatus::Observed)], order_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(orders)
+    }
+
+    /// Leaves of the order tree: the hashes of all final orders, by order id.
+    pub fn leaves(&self) -> anyhow::Result<Vec<[u8; 32]>> {
+        let conn = self.conn.lock().unwrap();
+        let mut statement =
+            conn.prepare("SELECT leaf FROM orders WHERE finalized = 1 ORDER BY order_id")?;
+        let leaves = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(leaves)
+    }
+
+    /// The proof stored for an order once it is proved.
+    pub fn proof(&self, order_id: u64) -> anyhow::Result<Option<SP1ProofWithPublicValues>> {
+        let conn = self.conn.lock().unwrap();
+        let proof: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT proof FROM orders WHERE order_id = ?1",
+                [order_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(proof.map(|proof| decode_blob(&proof)).transpose()?)
+    }
+
+    pub fn finalize(&self, order_id: u64) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE orders SET finalized = 1, updated_at = ?2 WHERE order_id = ?1",
+            params![order_id, unix_now()],
+        )
+    }
+
+    /// Retract an order that is not final. The cursor moves back to the newest final order, so
+    /// the order is read again if it lands on the canonical fork.
+    pub fn retract(&self, order_id: u64, reason: &str) -> anyhow::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let changed = tx.execute(
+            "UPDATE orders SET status = ?2, error = ?3, updated_at = ?4
+             WHERE order_id = ?1 AND finalized = 0",
+            params![
+                order_id,
+                to_text(&RelayStatus::Retracted),
+                reason,
+                unix_now()
+            ],
+        )?;
+        anyhow::ensure!(changed == 1, "order not found or already final");
+        let cursor: Option<String> = tx
+            .query_row(
+                "SELECT signature FROM orders WHERE finalized = 1 ORDER BY slot DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match cursor {
+            Some(cursor) => {
+                tx.execute("UPDATE cursor SET signature = ?1 WHERE id = 0", [cursor])?
+            }
+            None => tx.execute("DELETE FROM cursor", [])?,
+        };
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Record the claim on an order. The relayer of our own claims is stored as `NULL`.
+    pub fn record_claim(&self, order_id: u64, claim: &Claim) -> anyhow::Result<()> {
+        let (relayer, expiry) = match *claim {
+            Claim::Ours { expiry } => (None, expiry),
+            Claim::Theirs { relayer, expiry } => (Some(relayer), expiry),
+        };
+        self.update(
+            "UPDATE orders SET claimed_by = ?2, claim_expiry = ?3, updated_at = ?4
+             WHERE order_id = ?1",
+            params![order_id, relayer, expiry, unix_now()],
+        )
+    }
+
+    /// Move an order to `status` without touching anything else.
+    pub fn set_status(&self, order_id: u64, status: RelayStatus) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE orders SET status = ?2, updated_at = ?3 WHERE order_id = ?1",
+            params![order_id, to_text(&status), unix_now()],
+        )
+    }
+
+    pub fn proved(
+        &self,
+        order_id: u64,
+        proof: &SP1ProofWithPublicValues,
+        proof_hash: [u8; 32],
+    ) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE orders SET status = ?2, proof = ?3, proof_hash = ?4, updated_at = ?5
+             WHERE order_id = ?1",
+            params![
+                order_id,
+                to_text(&RelayStatus::Proved),
+                bincode::serialize(proof)?,
+                proof_hash,
+                unix_now()
+            ],
+        )
+    }
+
+    pub fn submitted(&self, order_id: u64, tx_hash: [u8; 32]) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE orders SET status = ?2, tx_hash = ?3, updated_at = ?4 WHERE order_id = ?1",
+            params![
+                order_id,
+                to_text(&RelayStatus::Submitted),
+                tx_hash,
+                unix_now()
+            ],
+        )
+    }
+
+    /// Mark an order as confirmed. The transaction hash is unknown when the order was found
+    /// completed on the destination chain before this relayer submitted it.
+    pub fn confirm(&self, order_id: u64, tx_hash: Option<[u8; 32]>) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE orders SET status = ?2, tx_hash = COALESCE(?3, tx_hash), error = NULL,
+             updated_at = ?4 WHERE order_id = ?1",
+            params![
+                order_id,
+                to_text(&RelayStatus::Confirmed),
+                tx_hash,
+                unix_now()
+            ],
+        )
+    }
+
+    /// Record a failed attempt. The order keeps its state, so the next attempt resumes where this
+    /// one stopped, and is marked as failed once it used up `max_attempts`.
+    pub fn attempt_failed(
+        &self,
+        order_id: u64,
+        error: &str,
+        max_attempts: u32,
+    ) -> anyhow::Result<RelayStatus> {
+        let conn = self.conn.lock().unwrap();
+        let status = conn.query_row(
+            "UPDATE orders SET attempts = attempts + 1, error = ?2, updated_at = ?3,
+             status = CASE WHEN attempts + 1 >= ?4 THEN ?5 ELSE status END
+             WHERE order_id = ?1 RETURNING status",
+            params![
+                order_id,
+                error,
+                unix_now(),
+                max_attempts,
+                to_text(&RelayStatus::Failed)
+            ],
+            |row| from_text(row.get(0)?),
+        )?;
+        Ok(status)
+    }
+
+    fn update(&self, sql: &str, params: impl rusqlite::Params) -> anyhow::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let changed = conn.execute(sql, params)?;
+        anyhow::ensure!(changed == 1, "order not found");
+        Ok(())
+    }
+}
+
+fn order_from_row(row: &Row<'_>) -> rusqlite::Result<RelayedOrder> {
+    let order: Vec<u8> = row.get("transfer_order")?;
+    let claimed_by: Option<[u8; 20]> = row.get("claimed_by")?;
+    let claim = row
+        .get::<_, Option<u64>>("claim_expiry")?
+        .map(|expiry| match claimed_by {
+            Some(relayer) => Claim::Theirs { relayer, expiry },
+            None => Claim::Ours { expiry },
+        });
+    Ok(RelayedOrder {
+        order_id: row.get("order_id")?,
+        order: decode_blob(&order)?,
+        leaf: row.get("leaf")?,
+        signature: row.get("signature")?,
+        slot: row.get("slot")?,
+        block_hash: row.get("block_hash")?,
+        finalized: row.get("finalized")?,
+        status: from_text(row.get("status")?)?,
+        attempts: row.get("attempts")?,
+        error: row.get("error")?,
+        proof_hash: row.get("proof_hash")?,
+        tx_hash: row.get("tx_hash")?,
+        claim,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Merkle proof of the leaf at `index` in the order tree.
+///
+/// The guest rejects empty paths, so a tree holding a single order gets a zero leaf as sibling.
+pub fn order_tree_proof(
+    hash: HashFunction,
+    mut leaves: Vec<[u8; 32]>,
+    index: usize,
+) -> Option<MerkleProof> {
+    if leaves.len() == 1 {
+        leaves.push([0; 32]);
+    }
+    match hash {
+        HashFunction::Sha256 => MerkleTree::<Sha256Hasher>::new(leaves).proof(index),
+        HashFunction::Keccak256 => MerkleTree::<Keccak256Hasher>::new(leaves).proof(index),
+    }
+}
+
+/// Proof bytes checked by on-chain verifiers. Empty for mock proofs and for proof modes that
+/// cannot be verified on chain.
+fn onchain_bytes(proof: &SP1ProofWithPublicValues) -> Vec<u8> {
+    match proof.proof {
+        SP1Proof::Plonk(_) | SP1Proof::Groth16(_) => proof.bytes(),
+        _ => Vec::new(),
+    }
+}
+
+/// Hash of a proof submitted on chain, binding the public values to the proof bytes.
+pub fn proof_hash(proof: &SP1ProofWithPublicValues) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(proof.public_values.as_slice());
+    hasher.update(onchain_bytes(proof));
+    hasher.finalize().into()
+}
+
+/// Settings of the relayer.
+#[derive(Clone, Debug)]
+pub struct RelayerConfig {
+    pub mode: ProofMode,
+    /// How long the relayer waits between polls of the source chain.
+    pub poll_interval: Duration,
+    /// How often an order is attempted before it is marked as failed.
+    pub max_attempts: u32,
+}
+
+impl Default for RelayerConfig {
+    fn default() -> Self {
+        Self {
+            mode: ProofMode::Groth16,
+            poll_interval: Duration::from_secs(2),
+            max_attempts: 3,
+        }
+    }
+}
+
+/// Moves orders from the source chain to the destination chain.
+pub struct Relayer {
+    source: Box<dyn SourceChain>,
+    destination: Box<dyn DestinationChain>,
+    backend: Box<dyn ProofBackend>,
+    store: RelayerStore,
+    /// `bytes32` vkey hash of the bridge program, for checking on-chain proof bytes.
+    vkey_hash: [u8; 32],
+    config: RelayerConfig,
+    metrics: Arc<Metrics>,
+}
+
+impl Relayer {
+    pub fn new(
+        source: Box<dyn SourceChain>,
+        destination: Box<dyn DestinationChain>,
+        backend: Box<dyn ProofBackend>,
+        store: RelayerStore,
+        vkey_hash: [u8; 32],
+        config: RelayerConfig,
+    ) -> Self {
+        Self {
+            source,
+            destination,
+            backend,
+            store,
+            vkey_hash,
+            config,
+            metrics: Arc::new(Metrics::new()),
+        }
+    }
+
+    /// Record metrics in `metrics`, for example to serve them next to another process's.
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn store(&self) -> &RelayerStore {
+        &self.store
+    }
+
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    /// Record the orders locked since the last poll and settle the ones waiting for finality.
+    /// Returns the number of new orders.
+    pub fn sync(&self) -> anyhow::Result<usize> {
+        let cursor = self.store.cursor()?;
+        let (locked, next) = self.source.locked_orders(cursor.as_deref())?;
+
+        let mut orders = Vec::with_capacity(locked.len());
+        for locked in locked {
+            let order_id = locked.event.order_id;
+            let order = self
+                .source
+                .order(order_id)
+                .with_context(|| format!("failed to read order {order_id}"))?;
+            orders.push((locked, order));
+        }
+        let added = self.store.observe(&orders, next.as_deref())?;
+        self.metrics.reached("observed", added as u64);
+        self.check_finality()?;
+        Ok(added)
+    }
+
+    /// Mark orders as final once their slot is final, and retract the ones whose block is no
+    /// longer on the canonical fork. Returns the number of orders finalized and retracted.
+    pub fn check_finality(&self) -> anyhow::Result<(usize, usize)> {
+        let pending = self.store.pending_finality()?;
+        if pending.is_empty() {
+            return Ok((0, 0));
+        }
+        let finalized_slot = self.source.finalized_slot()?;
+        let (mut finalized, mut retracted) = (0, 0);
+        for order in pending {
+            let order_id = order.order_id;
+            match self.source.block_hash(order.slot)? {
+                Some(hash) if hash == order.block_hash => {
+                    if order.slot <= finalized_slot {
+                        self.store.finalize(order_id)?;
+                        finalized += 1;
+                    }
+                }
+                hash => {
+                    let reason = match hash {
+                        Some(hash) => format!(
+                            "slot {} now holds block {hash} instead of {}",
+                            order.slot, order.block_hash
+                        ),
+                        None => format!("slot {} has no block on the canonical fork", order.slot),
+                    };
+                    tracing::warn!(order_id, "retracted, {reason}");
+                    self.store.retract(order_id, &reason)?;
+                    self.metrics.reached("retracted", 1);
+                    retracted += 1;
+                }
+            }
+        }
+        Ok((finalized, retracted))
+    }
+
+    /// Drive every unfinished order as far as it gets. Returns the number of orders confirmed.
+    pub fn relay_pending(&self) -> anyhow::Result<usize> {
+        let mut confirmed = 0;
+        let unfinished = self.store.unfinished()?;
+        self.metrics.set_queue_depth(unfinished.len());
+        for mut order in unfinished {
+            let order_id = order.order_id;
+            loop {
+                match self.step(&order) {
+                    Ok(RelayStatus::Confirmed) => {
+                        confirmed += 1;
+                        break;
+                    }
+                    // Still waiting for the transaction to be mined.
+                    Ok(status) if status == order.status => break,
+                    // Pick up what the step stored, such as the proof hash or transaction.
+                    Ok(_) => order = self.store.get(order_id)?.context("order disappeared")?,
+                    Err(e) => {
+                        let status = self.store.attempt_failed(
+                            order_id,
+                            &format!("{e:#}"),
+                            self.config.max_attempts,
+                        )?;
+                        tracing::warn!(order_id, ?status, "attempt failed: {e:#}");
+                        if status == RelayStatus::Failed {
+                            self.metrics.reached("failed", 1);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(confirmed)
+    }
+
+    /// Take the next step for an order and persist its new state. Returns the new state.
+    ///
+    /// Every step records its outcome before the next one starts, so a relayer stopped anywhere
+    /// repeats at most the step it was in. Submitting is guarded by the order status on the
+    /// destination chain, so repeating it never completes an order twice.
+    pub fn step(&self, order: &RelayedOrder) -> anyhow::Result<RelayStatus> {
+        let order_id = order.order_id;
+        // The order may still be rolled back.
+        if order.status == RelayStatus::Observed && !order.finalized {
+            return Ok(RelayStatus::Observed);
+        }
+        let _span = tracing::info_span!("step", order_id, status = ?order.status).entered();
+        match order.status {
+            RelayStatus::Observed => {
+                if let Some(status) = self.already_completed(order_id, RelayStatus::Observed)? {
+                    return Ok(status);
+                }
+                if !self.claim(order_id)? {
+                    return Ok(RelayStatus::Observed);
+                }
+                self.store.set_status(order_id, RelayStatus::Proving)?;
+                Ok(RelayStatus::Proving)
+            }
+            RelayStatus::Proving => {
+                let started = Instant::now();
+                let proof = self.prove(order)?;
+                let proof_hash = proof_hash(&proof);
+                self.store.proved(order_id, &proof, proof_hash)?;
+                self.metrics.proved(self.config.mode, started.elapsed());
+                tracing::info!(proof_hash = hex::encode(proof_hash), "proved");
+                Ok(RelayStatus::Proved)
+            }
+            RelayStatus::Proved => {
+                if let Some(status) = self.already_completed(order_id, RelayStatus::Proved)? {
+                    return Ok(status);
+                }
+                // Our claim may have lapsed while proving and been taken by another relayer.
+                if !self.claim(order_id)? {
+                    return Ok(RelayStatus::Proved);
+                }
+                let proof_hash = order.proof_hash.context("proved order has no proof hash")?;
+                let tx_hash = self
+                    .destination
+                    .submit(order_id, proof_hash)
+                    .inspect_err(|_| self.metrics.submission_failed("send"))
+                    .context("failed to submit unlockTokens")?;
+                self.store.submitted(order_id, tx_hash)?;
+                self.metrics.reached("submitted", 1);
+                tracing::info!(tx_hash = hex::encode(tx_hash), "submitted");
+                Ok(RelayStatus::Submitted)
+            }
+            RelayStatus::Submitted => {
+                let tx_hash = order
+                    .tx_hash
+                    .context("submitted order has no transaction")?;
+                match self.destination.receipt(tx_hash)? {
+                    None => Ok(RelayStatus::Submitted),
+                    Some(true) => {
+                        self.store.confirm(order_id, Some(tx_hash))?;
+                        self.metrics.reached("confirmed", 1);
+                        tracing::info!(tx_hash = hex::encode(tx_hash), "confirmed");
+                        Ok(RelayStatus::Confirmed)
+                    }
+                    Some(false) => {
+                        self.metrics.submission_failed("reverted");
+                        self.store.set_status(order_id, RelayStatus::Proved)?;
+                        // Another relayer may have completed the order first.
+                        if let Some(status) =
+                            self.already_completed(order_id, RelayStatus::Proved)?
+                        {
+                            return Ok(status);
+                        }
+                        anyhow::bail!("unlockTokens reverted in 0x{}", hex::encode(tx_hash))
+                    }
+                }
+            }
+            RelayStatus::Confirmed | RelayStatus::Failed | RelayStatus::Retracted => {
+                Ok(order.status)
+            }
+        }
+    }
+
+    /// Claim the order on the destination chain and record the claim. Returns `false` while
+    /// another relayer holds it, in which case the order waits for that claim to expire.
+    fn claim(&self, order_id: u64) -> anyhow::Result<bool> {
+        let claim = self
+            .destination
+            .claim(order_id)
+            .inspect_err(|_| self.metrics.submission_failed("claim"))
+            .context("failed to claim the order")?;
+        self.store.record_claim(order_id, &claim)?;
+        match claim {
+            Claim::Ours { .. } => Ok(true),
+            Claim::Theirs { relayer, expiry } => {
+                tracing::info!("claimed by 0x{} until block {expiry}", hex::encode(relayer));
+                Ok(false)
+            }
+        }
+    }
+
+    /// Check whether the destination chain already completed the order, before sending anything
+    /// for it. Returns `None` if it did not; otherwise confirms the order once the completion is
+    /// final and keeps it at `status` until then, so nothing is sent twice after a crash.
+    fn already_completed(
+        &self,
+        order_id: u64,
+        status: RelayStatus,
+    ) -> anyhow::Result<Option<RelayStatus>> {
+        let context = "failed to read the order status on the destination chain";
+        if !self.destination.is_completed(order_id).context(context)? {
+            return Ok(None);
+        }
+        if !self
+            .destination
+            .is_completed_final(order_id)
+            .context(context)?
+        {
+            tracing::info!("completed on the destination chain, waiting for confirmations");
+            return Ok(Some(status));
+        }
+        self.store.confirm(order_id, None)?;
+        self.metrics.reached("confirmed", 1);
+        tracing::info!("already completed on the destination chain");
+        Ok(Some(RelayStatus::Confirmed))
+    }
+
+    fn prove(&self, order: &RelayedOrder) -> anyhow::Result<SP1ProofWithPublicValues> {
+        let leaves = self.store.leaves()?;
+        let index = leaves
+            .iter()
+            .position(|leaf| *leaf == order.leaf)
+            .context("order is not in the order tree")?;
+        let hash = HashFunction::for_chain(order.order.source_chain);
+        let merkle_proof =
+            order_tree_proof(hash, leaves, index).context("no Merkle proof for order")?;
+        let input = BridgeInput {
+            order: order.order.clone(),
+            merkle_proof,
+        };
+
+        tracing::info!(
+            "proving {:?} proof with the {} backend",
+            self.config.mode,
+            self.backend.name()
+        );
+        let proof = self
+            .backend
+            .prove(&BRIDGE_PROGRAM, &input.stdin(), self.config.mode)
+            .context("failed to generate proof")?;
+        self.check_proof(&proof, order)?;
+        Ok(proof)
+    }
+
+    /// Check that the proof is about this order and that its on-chain bytes verify.
+    fn check_proof(
+        &self,
+        proof: &SP1ProofWithPublicValues,
+        order: &RelayedOrder,
+    ) -> anyhow::Result<()> {
+        let public_values = proof.public_values.as_slice();
+        let (_, order_hash) = decode_public_values(public_values)?;
+        anyhow::ensure!(
+            order_hash == order.leaf,
+            "proof commits to order hash 0x{}, expected 0x{}",
+            hex::encode(order_hash),
+            hex::encode(order.leaf)
+        );
+
+        let proof_bytes = onchain_bytes(proof);
+        if !proof_bytes.is_empty() {
+            bridge_verifier::verify_proof(&proof_bytes, public_values, &self.vkey_hash)
+                .map_err(|e| anyhow::anyhow!("on-chain proof bytes do not verify: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Poll and relay until the process exits.
+    pub fn run(&self) {
+        loop {
+            match self.sync() {
+                Ok(0) => {}
+                Ok(added) => tracing::info!("observed {added} new order(s)"),
+                Err(e) => tracing::warn!("failed to poll the source chain: {e:#}"),
+            }
+            if let Err(e) = self.relay_pending() {
+                tracing::error!("failed to relay orders: {e:#}");
+            }
+            thread::sleep(self.config.poll_interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anchor::Pubkey, input::demo_order};
+
+    fn locked(order_id: u64, signature: &str) -> (LockedOrder, TransferOrder) {
+        let mut order = demo_order();
+        order.order_id = order_id;
+        let event = TokensLocked {
+            order_id,
+            user: Pubkey::new_from_array(order.user),
+            amount: order.amount,
+            recipient: [0x33; 20],
+            relayer_fee: order.relayer_fee,
+            slot: order.created_at,
+            mint: Pubkey::new_from_array(order.token),
+        };
+        let locked = LockedOrder {
+            event,
+            signature: signature.to_string(),
+            slot: order.created_at,
+            block_hash: format!("block-{}", order.created_at),
+        };
+        (locked, order)
+    }
+
+    #[test]
+    fn test_observe_is_idempotent_and_moves_cursor() {
+        let store = RelayerStore::open_in_memory().unwrap();
+        assert_eq!(store.cursor().unwrap(), None);
+
+        let orders = [locked(1, "sig-1"), locked(2, "sig-2")];
+        assert_eq!(store.observe(&orders, Some("sig-2")).unwrap(), 2);
+        assert_eq!(store.observe(&orders[1..], Some("sig-3")).unwrap(), 0);
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("sig-3"));
+
+        let observed = store.with_status(RelayStatus::Observed).unwrap();
+        assert_eq!(observed.len(), 2);
+        assert_eq!(observed[0].leaf, hash_order(&orders[0].1));
+        // Only final orders are part of the order tree.
+        assert_eq!(store.leaves().unwrap().len(), 0);
+        store.finalize(1).unwrap();
+        store.finalize(2).unwrap();
+        assert_eq!(store.leaves().unwrap().len(), 2);
+
+        store.set_status(1, RelayStatus::Proving).unwrap();
+        store.submitted(2, [0x0b; 32]).unwrap();
+        store.confirm(2, None).unwrap();
+        let order = store.get(2).unwrap().unwrap();
+        assert_eq!(order.status, RelayStatus::Confirmed);
+        assert_eq!(order.tx_hash, Some([0x0b; 32]));
+        let unfinished = store.unfinished().unwrap();
+        assert_eq!(unfinished.len(), 1);
+        assert_eq!(unfinished[0].status, RelayStatus::Proving);
+    }
+
+    #[test]
+    fn test_failed_attempts_give_up_after_limit() {
+        let store = RelayerStore::open_in_memory().unwrap();
+        store.observe(&[locked(1, "sig-1")], None).unwrap();
+        store.set_status(1, RelayStatus::Proved).unwrap();
+
+        assert_eq!(
+            store.attempt_failed(1, "rpc down", 2).unwrap(),
+            RelayStatus::Proved
+        );
+        assert_eq!(
+            store.attempt_failed(1, "rpc down", 2).unwrap(),
+            RelayStatus::Failed
+        );
+        let order = store.get(1).unwrap().unwrap();
+        assert_eq!(order.attempts, 2);
+        assert_eq!(order.error.as_deref(), Some("rpc down"));
+    }
+
+    #[test]
+    fn test_retracted_order_is_observed_again() {
+        let store = RelayerStore::open_in_memory().unwrap();
+        let (first, second) = (locked(1, "sig-1"), locked(2, "sig-2"));
+        store.observe(&[first], Some("sig-1")).unwrap();
+        store.finalize(1).unwrap();
+        store
+            .observe(std::slice::from_ref(&second), Some("sig-2"))
+            .unwrap();
+
+        store.retract(2, "orphaned").unwrap();
+        assert!(store.retract(1, "orphaned").is_err());
+        let order = store.get(2).unwrap().unwrap();
+        assert_eq!(order.status, RelayStatus::Retracted);
+        assert_eq!(store.cursor().unwrap().as_deref(), Some("sig-1"));
+        assert!(store.unfinished().unwrap().iter().all(|o| o.order_id == 1));
+
+        // The order id shows up again on the canonical fork, in another block.
+        let (mut locked, mut order) = second;
+        locked.signature = "sig-3".to_string();
+        locked.block_hash = "block-other".to_string();
+        order.amount += 1;
+        assert_eq!(store.observe(&[(locked, order.clone())], None).unwrap(), 1);
+        let observed = store.get(2).unwrap().unwrap();
+        assert_eq!(observed.status, RelayStatus::Observed);
+        assert_eq!(observed.block_hash, "block-other");
+        assert_eq!(observed.leaf, hash_order(&order));
+    }
+
+    #[test]
+    fn test_lone_order_has_a_merkle_path() {
+        let leaf = hash_order(&demo_order());
+        let proof = order_tree_proof(HashFunction::Sha256, vec![leaf], 0).unwrap();
+        assert_eq!(proof.leaf, leaf);
+        assert!(!proof.proof.is_empty());
+        assert!(proof.verify(HashFunction::Sha256));
+    }
+}
@@ -0,0 +1,681 @@
+//! Prover job service: a persistent queue of proof requests worked off by a pool of provers.
+//!
+//! Jobs are stored in SQLite and move through `queued` → `executing` → `proving` → `done` or
+//! `failed`. The guest is executed before proving so invalid orders fail fast; transient proving
+//! failures are retried up to a configurable number of attempts and other proving failures fail the
+//! job right away. Jobs interrupted by a restart are queued again.
+//!
+//! The HTTP API is the protocol [`crate::backend::RemoteBackend`] speaks:
+//!
+//! - `POST /jobs` with a [`JobRequest`], answered with `{"id"}`.
+//! - `GET /jobs/{id}`, answered with the job status and, once done, the proof.
+//! - `GET /jobs/{id}/bundle`, the proof bundle of a finished bridge job (same format as
+//!   `bridge-prover prove --output`).
+//! - `GET /metrics`, the service's [`Metrics`] in the OpenMetrics text format.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp1_sdk::{SP1ProofWithPublicValues, SP1Stdin};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{
+    artifact::{bincode_base64, unix_now, ProofBundle},
+    backend::{JobStatus, Program, ProofBackend, TransientError, BRIDGE_PROGRAM},
+    input::{stdin_hash, BridgeInput, MerkleFile, OrderFile},
+    metrics::Metrics,
+    ProofMode,
+};
+
+/// Body of `POST /jobs`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobRequest {
+    pub mode: ProofMode,
+    #[serde(flatten)]
+    pub input: JobInput,
+}
+
+/// What to prove.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JobInput {
+    /// Raw guest inputs of a known program, as sent by the remote backend.
+    Stdin {
+        program: String,
+        #[serde(with = "bincode_base64")]
+        stdin: SP1Stdin,
+    },
+    /// A transfer order and its Merkle proof.
+    Order {
+        order: OrderFile,
+        merkle: MerkleFile,
+    },
+    /// An order looked up in the orders directory.
+    OrderId { order_id: u64 },
+}
+
+/// An order file in the orders directory, `{orders_dir}/{order_id}.json`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OrderRecord {
+    pub order: OrderFile,
+    pub merkle: MerkleFile,
+}
+
+/// A job as stored in the queue.
+#[derive(Clone, Debug)]
+pub struct Job {
+    pub id: i64,
+    pub program: String,
+    pub mode: ProofMode,
+    pub order_id: Option<u64>,
+    pub stdin: SP1Stdin,
+    pub status: JobStatus,
+    /// Number of times the job was picked up by a worker.
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub proof: Option<SP1ProofWithPublicValues>,
+    /// Proof bundle JSON, for jobs of the bridge program.
+    pub bundle: Option<String>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// Response of `GET /jobs/{id}`, a superset of [`crate::backend::RemoteJob`].
+#[derive(Serialize)]
+struct JobView<'a> {
+    id: String,
+    status: JobStatus,
+    program: &'a str,
+    mode: ProofMode,
+    order_id: Option<u64>,
+    attempts: u32,
+    error: Option<&'a str>,
+    created_at: u64,
+    updated_at: u64,
+    #[serde(with = "bincode_base64::option")]
+    proof: &'a Option<SP1ProofWithPublicValues>,
+}
+
+impl<'a> From<&'a Job> for JobView<'a> {
+    fn from(job: &'a Job) -> Self {
+        JobView {
+            id: job.id.to_string(),
+            status: job.status,
+            program: &job.program,
+            mode: job.mode,
+            order_id: job.order_id,
+            attempts: job.attempts,
+            error: job.error.as_deref(),
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            proof: &job.proof,
+        }
+    }
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    program TEXT NOT NULL,
+    mode TEXT NOT NULL,
+    order_id INTEGER,
+    stdin BLOB NOT NULL,
+    status TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    proof BLOB,
+    bundle TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS jobs_status ON jobs (status, id);
+";
+
+/// The SQLite job queue.
+pub struct JobStore {
+    conn: Mutex<Connection>,
+}
+
+impl JobStore {
+    /// Open or create the queue database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open job database {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    /// A queue that lives in memory only, for tests.
+    pub fn open_in_memory() -> anyhow::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> anyhow::Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("failed to create the jobs table")?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Queue a new job and return its id.
+    pub fn submit(
+        &self,
+        program: &Program,
+        mode: ProofMode,
+        order_id: Option<u64>,
+        stdin: &SP1Stdin,
+    ) -> anyhow::Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        let now = unix_now();
+        conn.execute(
+            "INSERT INTO jobs (program, mode, order_id, stdin, status, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+            params![
+                program.name,
+                to_text(&mode),
+                order_id,
+                bincode::serialize(stdin)?,
+                to_text(&JobStatus::Queued),
+                now,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn get(&self, id: i64) -> anyhow::Result<Option<Job>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row("SELECT * FROM jobs WHERE id = ?1", [id], job_from_row)
+            .optional()?)
+    }
+
+    /// Take the oldest queued job, moving it to `executing`.
+    pub fn claim(&self) -> anyhow::Result<Option<Job>> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn
+            .query_row(
+                "UPDATE jobs SET status = ?1, attempts = attempts + 1, updated_at = ?2
+                 WHERE id = (SELECT id FROM jobs WHERE status = ?3 ORDER BY id LIMIT 1)
+                 RETURNING *",
+                params![
+                    to_text(&JobStatus::Executing),
+                    unix_now(),
+                    to_text(&JobStatus::Queued)
+                ],
+                job_from_row,
+            )
+            .optional()?)
+    }
+
+    pub fn set_status(&self, id: i64, status: JobStatus) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE jobs SET status = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id, to_text(&status), unix_now()],
+        )
+    }
+
+    /// Store the proof of a finished job.
+    pub fn finish(
+        &self,
+        id: i64,
+        proof: &SP1ProofWithPublicValues,
+        bundle: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE jobs SET status = ?2, proof = ?3, bundle = ?4, error = NULL, updated_at = ?5
+             WHERE id = ?1",
+            params![
+                id,
+                to_text(&JobStatus::Done),
+                bincode::serialize(proof)?,
+                bundle,
+                unix_now()
+            ],
+        )
+    }
+
+    pub fn fail(&self, id: i64, error: &str) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE jobs SET status = ?2, error = ?3, updated_at = ?4 WHERE id = ?1",
+            params![id, to_text(&JobStatus::Failed), error, unix_now()],
+        )
+    }
+
+    /// Put a job back in the queue after a failed attempt, keeping the error for inspection.
+    pub fn retry(&self, id: i64, error: &str) -> anyhow::Result<()> {
+        self.update(
+            "UPDATE jobs SET status = ?2, error = ?3, updated_at = ?4 WHERE id = ?1",
+            params![id, to_text(&JobStatus::Queued), error, unix_now()],
+        )
+    }
+
+    /// Number of jobs waiting for a worker.
+    pub fn queued(&self) -> anyhow::Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.query_row(
+            "SELECT COUNT(*) FROM jobs WHERE status = ?1",
+            [to_text(&JobStatus::Queued)],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// Queue jobs again that were being worked on when the service stopped.
+    pub fn recover(&self) -> anyhow::Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute(
+            "UPDATE jobs SET status = ?1, updated_at = ?2 WHERE status IN (?3, ?4)",
+            params![
+                to_text(&JobStatus::Queued),
+                unix_now(),
+                to_text(&JobStatus::Executing),
+                to_text(&JobStatus::Proving)
+            ],
+        )?)
+    }
+
+    fn update(&self, sql: &str, params: impl rusqlite::Params) -> anyhow::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let changed = conn.execute(sql, params)?;
+        anyhow::ensure!(changed == 1, "job not found");
+        Ok(())
+    }
+}
+
+fn job_from_row(row: &Row<'_>) -> rusqlite::Result<Job> {
+    let stdin: Vec<u8> = row.get("stdin")?;
+    let proof: Option<Vec<u8>> = row.get("proof")?;
+    Ok(Job {
+        id: row.get("id")?,
+        program: row.get("program")?,
+        mode: from_text(row.get("mode")?)?,
+        order_id: row.get("order_id")?,
+        stdin: decode_blob(&stdin)?,
+        status: from_text(row.get("status")?)?,
+        attempts: row.get("attempts")?,
+        error: row.get("error")?,
+        proof: proof.as_deref().map(decode_blob).transpose()?,
+        bundle: row.get("bundle")?,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+pub(crate) fn decode_blob<T: DeserializeOwned>(bytes: &[u8]) -> rusqlite::Result<T> {
+    bincode::deserialize(bytes)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Blob, e))
+}
+
+/// Store enums by their serde name, the same spelling the API uses.
+pub(crate) fn to_text<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(text)) => text,
+        _ => unreachable!("unit enum variants serialize to strings"),
+    }
+}
+
+pub(crate) fn from_text<T: DeserializeOwned>(text: String) -> rusqlite::Result<T> {
+    serde_json::from_value(serde_json::Value::String(text)).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Settings of the job service.
+#[derive(Clone, Debug)]
+pub struct ServiceConfig {
+    /// Number of jobs proven concurrently.
+    pub workers: usize,
+    /// How often a job is attempted before it is marked as failed.
+    pub max_attempts: u32,
+    /// How long an idle worker waits before checking the queue again.
+    pub poll_interval: Duration,
+    /// Directory of `{order_id}.json` order records, for jobs submitted by order id.
+    pub orders_dir: Option<PathBuf>,
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        Self {
+            workers: 1,
+            max_attempts: 3,
+            poll_interval: Duration::from_secs(1),
+            orders_dir: None,
+        }
+    }
+}
+
+/// The job queue together with the backend that proves its jobs.
+pub struct Service {
+    store: JobStore,
+    backend: Box<dyn ProofBackend>,
+    /// `bytes32` vkey hash of the bridge program, recorded in proof bundles.
+    vkey_hash: [u8; 32],
+    config: ServiceConfig,
+    metrics: Arc<Metrics>,
+}
+
+impl Service {
+    pub fn new(
+        store: JobStore,
+        backend: Box<dyn ProofBackend>,
+        vkey_hash: [u8; 32],
+        config: ServiceConfig,
+    ) -> Self {
+        Self {
+            store,
+            backend,
+            vkey_hash,
+            config,
+            metrics: Arc::new(Metrics::new()),
+        }
+    }
+
+    /// Record metrics in `metrics` instead of metrics of its own.
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn store(&self) -> &JobStore {
+        &self.store
+    }
+
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    /// Resolve a request into guest inputs and queue it.
+    pub fn submit(&self, request: JobRequest) -> anyhow::Result<i64> {
+        let (program, stdin, order_id) = match request.input {
+            JobInput::Stdin { program, stdin } => {
+                let program = Program::by_name(&program)
+                    .with_context(|| format!("unknown program: {program}"))?;
+                (program, stdin, None)
+            }
+            JobInput::Order { order, merkle } => {
+                let input = BridgeInput::from_files(order, merkle);
+                (BRIDGE_PROGRAM, input.stdin(), Some(input.order.order_id))
+            }
+            JobInput::OrderId { order_id } => {
+                let input = self.load_order(order_id)?;
+                (BRIDGE_PROGRAM, input.stdin(), Some(order_id))
+            }
+        };
+        let id = self
+            .store
+            .submit(&program, request.mode, order_id, &stdin)?;
+        self.metrics.reached("queued", 1);
+        Ok(id)
+    }
+
+    fn load_order(&self, order_id: u64) -> anyhow::Result<BridgeInput> {
+        let dir = self
+            .config
+            .orders_dir
+            .as_ref()
+            .context("jobs by order id need an orders directory")?;
+        let path = dir.join(format!("{order_id}.json"));
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("order {order_id} not found"))?;
+        let record: OrderRecord = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        anyhow::ensure!(
+            record.order.order_id == order_id,
+            "{} holds order {}",
+            path.display(),
+            record.order.order_id
+        );
+        Ok(BridgeInput::from_files(record.order, record.merkle))
+    }
+
+    /// Start the workers. They run until the process exits.
+    pub fn spawn_workers(self: &Arc<Self>) -> Vec<JoinHandle<()>> {
+        (0..self.config.workers)
+            .map(|_| {
+                let service = self.clone();
+                thread::spawn(move || service.work())
+            })
+            .collect()
+    }
+
+    fn work(&self) {
+        loop {
+            match self.store.claim() {
+                Ok(Some(job)) => {
+                    let id = job.id;
+                    if let Err(e) = self.run(job) {
+                        tracing::error!(id, "failed to record result: {e:#}");
+                    }
+                }
+                Ok(None) => thread::sleep(self.config.poll_interval),
+                Err(e) => {
+                    tracing::error!("failed to claim a job: {e:#}");
+                    thread::sleep(self.config.poll_interval);
+                }
+            }
+        }
+    }
+
+    /// Execute and prove a claimed job, recording the outcome in the store.
+    pub fn run(&self, job: Job) -> anyhow::Result<()> {
+        let _span =
+            tracing::info_span!("job", id = job.id, program = job.program.as_str()).entered();
+        let Some(program) = Program::by_name(&job.program) else {
+            return self.fail(job.id, &format!("unknown program: {}", job.program));
+        };
+
+        tracing::info!(attempt = job.attempts, "executing");
+        match self.backend.execute(&program, &job.stdin) {
+            Ok((_, report)) => self.metrics.executed(report.total_instruction_count()),
+            // The guest is deterministic, running it again would fail the same way.
+            Err(e) => return self.fail(job.id, &format!("guest execution failed: {e:#}")),
+        }
+
+        self.store.set_status(job.id, JobStatus::Proving)?;
+        tracing::info!("proving {:?} proof", job.mode);
+        let started_at = unix_now();
+        let started = Instant::now();
+        let proof = match self.backend.prove(&program, &job.stdin, job.mode) {
+            Ok(proof) => proof,
+            // Only failures like network errors and timeouts can go away on another attempt.
+            Err(e) if e.is::<TransientError>() && job.attempts < self.config.max_attempts => {
+                tracing::warn!(attempt = job.attempts, "attempt failed: {e:#}");
+                return self.store.retry(job.id, &format!("{e:#}"));
+            }
+            Err(e) => return self.fail(job.id, &format!("{e:#}")),
+        };
+        self.metrics.proved(job.mode, started.elapsed());
+
+        let bundle = if program == BRIDGE_PROGRAM {
+            let bundle = ProofBundle::new(
+                proof.clone(),
+                job.mode,
+                self.vkey_hash,
+                stdin_hash(&job.stdin),
+                started_at,
+            );
+            match bundle {
+                Ok(bundle) => Some(serde_json::to_string_pretty(&bundle)?),
+                Err(e) => return self.fail(job.id, &format!("invalid proof output: {e:#}")),
+            }
+        } else {
+            None
+        };
+        self.store.finish(job.id, &proof, bundle.as_deref())?;
+        tracing::info!("done");
+        Ok(())
+    }
+
+    fn fail(&self, id: i64, error: &str) -> anyhow::Result<()> {
+        self.metrics.reached("failed", 1);
+        tracing::warn!("failed: {error}");
+        self.store.fail(id, error)
+    }
+
+    /// Answer HTTP requests until the server is closed.
+    pub fn serve(&self, server: &Server) {
+        for mut request in server.incoming_requests() {
+            let response = if (request.method(), request.url()) == (&Method::Get, "/metrics") {
+                match self.store.queued() {
+                    Ok(queued) => self.metrics.set_queue_depth(queued),
+                    Err(e) => tracing::warn!("failed to count queued jobs: {e:#}"),
+                }
+                self.metrics.response()
+            } else {
+                let (status, body) = self.handle(&mut request);
+                Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(
+                        Header::from_bytes("Content-Type", "application/json")
+                            .expect("valid header"),
+                    )
+            };
+            if let Err(e) = request.respond(response) {
+                tracing::warn!("failed to send response: {e}");
+            }
+        }
+    }
+
+    fn handle(&self, request: &mut tiny_http::Request) -> (u16, String) {
+        let path = request.url().trim_end_matches('/').to_string();
+        let segments: Vec<&str> = path.split('/').skip(1).collect();
+
+        match (request.method(), segments.as_slice()) {
+            (Method::Post, ["jobs"]) => {
+                let mut body = String::new();
+                if let Err(e) = request.as_reader().read_to_string(&mut body) {
+                    return error(400, format!("failed to read request: {e}"));
+                }
+                let request: JobRequest = match serde_json::from_str(&body) {
+                    Ok(request) => request,
+                    Err(e) => return error(400, format!("invalid job request: {e}")),
+                };
+                match self.submit(request) {
+                    Ok(id) => {
+                        tracing::info!(id, "queued");
+                        (200, serde_json::json!({ "id": id.to_string() }).to_string())
+                    }
+                    Err(e) => error(400, format!("{e:#}")),
+                }
+            }
+            (Method::Get, ["jobs", id]) => match self.job(id) {
+                Ok(job) => (200, serde_json::to_string(&JobView::from(&job)).unwrap()),
+                Err(response) => response,
+            },
+            (Method::Get, ["jobs", id, "bundle"]) => match self.job(id) {
+                Ok(Job {
+                    bundle: Some(bundle),
+                    ..
+                }) => (200, bundle),
+                Ok(job) => error(404, format!("job {} has no proof bundle", job.id)),
+                Err(response) => response,
+            },
+            _ => error(404, "not found".to_string()),
+        }
+    }
+
+    fn job(&self, id: &str) -> Result<Job, (u16, String)> {
+        let id = id
+            .parse()
+            .map_err(|_| error(404, format!("job {id} not found")))?;
+        match self.store.get(id) {
+            Ok(Some(job)) => Ok(job),
+            Ok(None) => Err(error(404, format!("job {id} not found"))),
+            Err(e) => Err(error(500, format!("{e:#}"))),
+        }
+    }
+}
+
+pub(crate) fn error(status: u16, message: String) -> (u16, String) {
+    (status, serde_json::json!({ "error": message }).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::{RemoteJobRequest, AGGREGATION_PROGRAM},
+        input::demo_order,
+    };
+
+    fn stdin(value: u64) -> SP1Stdin {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&value);
+        stdin
+    }
+
+    #[test]
+    fn test_claims_jobs_in_order() {
+        let store = JobStore::open_in_memory().unwrap();
+        let first = store
+            .submit(&BRIDGE_PROGRAM, ProofMode::Core, Some(1), &stdin(1))
+            .unwrap();
+        let second = store
+            .submit(&AGGREGATION_PROGRAM, ProofMode::Groth16, None, &stdin(2))
+            .unwrap();
+
+        let job = store.claim().unwrap().unwrap();
+        assert_eq!(job.id, first);
+        assert_eq!(job.status, JobStatus::Executing);
+        assert_eq!(job.attempts, 1);
+        assert_eq!(job.order_id, Some(1));
+        assert_eq!(job.stdin.buffer, stdin(1).buffer);
+
+        let job = store.claim().unwrap().unwrap();
+        assert_eq!(job.id, second);
+        assert_eq!(job.program, "aggregation");
+        assert_eq!(job.mode, ProofMode::Groth16);
+        assert!(store.claim().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_retry_and_recover_requeue_jobs() {
+        let store = JobStore::open_in_memory().unwrap();
+        let id = store
+            .submit(&BRIDGE_PROGRAM, ProofMode::Core, None, &stdin(1))
+            .unwrap();
+
+        store.claim().unwrap().unwrap();
+        store.retry(id, "prover unavailable").unwrap();
+        let job = store.get(id).unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!(job.error.as_deref(), Some("prover unavailable"));
+
+        store.claim().unwrap().unwrap();
+        store.set_status(id, JobStatus::Proving).unwrap();
+        assert_eq!(store.recover().unwrap(), 1);
+        let job = store.claim().unwrap().unwrap();
+        assert_eq!(job.attempts, 3);
+    }
+
+    #[test]
+    fn test_parses_every_request_kind() {
+        let remote = serde_json::to_string(&RemoteJobRequest {
+            program: "bridge".to_string(),
+            mode: ProofMode::Compressed,
+            stdin: stdin(7),
+        })
+        .unwrap();
+        let request: JobRequest = serde_json::from_str(&remote).unwrap();
+        assert!(
+            matches!(request.input, JobInput::Stdin { ref program, .. } if program == "bridge")
+        );
+        assert_eq!(request.mode, ProofMode::Compressed);
+
+        let order = serde_json::to_value(OrderFile::from(&demo_order())).unwrap();
+        let body = serde_json::json!({ "mode": "core", "order": order, "merkle": { "proof": [] } });
+        let request: JobRequest = serde_json::from_value(body).unwrap();
+        assert!(matches!(request.input, JobInput::Order { .. }));
+
+        let request: JobRequest =
+            serde_json::from_str(r#"{"mode":"groth16","order_id":42}"#).unwrap();
+        assert!(matches!(request.input, JobInput::OrderId { order_id: 42 }));
+    }
+}
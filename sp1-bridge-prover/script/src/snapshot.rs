@@ -0,0 +1,154 @@
+//! Versioned snapshots of the exact guest inputs, for reproducing executions and proofs.
+//!
+//! A snapshot stores the raw [`SP1Stdin`] buffers the guest reads together with a readable copy of
+//! the order and Merkle proof. Replaying a snapshot feeds the guest byte-identical inputs, and the
+//! recorded input hash matches the one in proof bundles generated from the same inputs.
+
+use std::path::Path;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sp1_sdk::SP1Stdin;
+
+use crate::{
+    artifact::unix_now,
+    input::{hex_bytes32, stdin_hash, BridgeInput, MerkleFile, OrderFile},
+};
+
+/// Version of the snapshot layout, bumped whenever the guest input format changes.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// The guest inputs of one execution.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InputSnapshot {
+    pub version: u32,
+    pub created_at: u64,
+    /// Hash of the stdin buffers, see [`stdin_hash`].
+    #[serde(with = "hex_bytes32")]
+    pub input_hash: [u8; 32],
+    /// Readable copy of the order, for bug reports.
+    pub order: OrderFile,
+    /// Readable copy of the Merkle proof, for bug reports.
+    pub merkle_proof: MerkleFile,
+    /// Hex encoded stdin buffers in the order the guest reads them.
+    pub stdin: Vec<String>,
+}
+
+impl InputSnapshot {
+    /// Capture the inputs that would be written to the guest.
+    pub fn capture(input: &BridgeInput) -> Self {
+        let stdin = input.stdin();
+
+        Self {
+            version: SNAPSHOT_VERSION,
+            created_at: unix_now(),
+            input_hash: stdin_hash(&stdin),
+            order: OrderFile::from(&input.order),
+            merkle_proof: MerkleFile::from(&input.merkle_proof),
+            stdin: stdin
+                .buffer
+                .iter()
+                .map(|buffer| format!("0x{}", hex::encode(buffer)))
+                .collect(),
+        }
+    }
+
+    /// Write the snapshot to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write input snapshot {}", path.display()))
+    }
+
+    /// Load a snapshot, checking its version and input hash.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read input snapshot {}", path.display()))?;
+        let snapshot: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse input snapshot {}", path.display()))?;
+
+        anyhow::ensure!(
+            snapshot.version == SNAPSHOT_VERSION,
+            "unsupported input snapshot version {} (expected {})",
+            snapshot.version,
+            SNAPSHOT_VERSION
+        );
+        let hash = stdin_hash(&snapshot.raw_stdin()?);
+        anyhow::ensure!(
+            hash == snapshot.input_hash,
+            "input snapshot hash mismatch: recorded 0x{}, computed 0x{}",
+            hex::encode(snapshot.input_hash),
+            hex::encode(hash)
+        );
+        Ok(snapshot)
+    }
+
+    /// The stdin exactly as recorded.
+    pub fn raw_stdin(&self) -> anyhow::Result<SP1Stdin> {
+        let mut stdin = SP1Stdin::new();
+        for buffer in &self.stdin {
+            stdin.write_vec(hex::decode(buffer.trim_start_matches("0x"))?);
+        }
+        Ok(stdin)
+    }
+
+    /// Decode the recorded buffers back into the guest inputs.
+    ///
+    /// Fails if re-encoding the decoded inputs does not reproduce the recorded bytes.
+    pub fn input(&self) -> anyhow::Result<BridgeInput> {
+        let stdin = self.raw_stdin()?;
+        anyhow::ensure!(
+            stdin.buffer.len() == 2,
+            "expected 2 stdin buffers, snapshot has {}",
+            stdin.buffer.len()
+        );
+
+        let input = BridgeInput {
+            order: bincode::deserialize(&stdin.buffer[0]).context("invalid order buffer")?,
+            merkle_proof: bincode::deserialize(&stdin.buffer[1])
+                .context("invalid Merkle proof buffer")?,
+        };
+        anyhow::ensure!(
+            stdin_hash(&input.stdin()) == self.input_hash,
+            "decoded inputs do not re-encode to the recorded stdin"
+        );
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputArgs;
+
+    #[test]
+    fn test_snapshot_replays_identical_stdin() {
+        let input = InputArgs::default().load().unwrap();
+        let snapshot = InputSnapshot::capture(&input);
+
+        let path = std::env::temp_dir().join(format!("snapshot-{}.json", std::process::id()));
+        snapshot.save(&path).unwrap();
+        let loaded = InputSnapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.raw_stdin().unwrap().buffer, input.stdin().buffer);
+        let replayed = loaded.input().unwrap();
+        assert_eq!(replayed.merkle_proof.root, input.merkle_proof.root);
+        assert_eq!(stdin_hash(&replayed.stdin()), snapshot.input_hash);
+    }
+
+    #[test]
+    fn test_snapshot_rejects_modified_buffers() {
+        let input = InputArgs::default().load().unwrap();
+        let mut snapshot = InputSnapshot::capture(&input);
+        snapshot.stdin[0] = snapshot.stdin[0].replacen("01", "02", 1);
+
+        let path = std::env::temp_dir().join(format!("modified-{}.json", std::process::id()));
+        snapshot.save(&path).unwrap();
+        let result = InputSnapshot::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Groth16 proofs in the layout verified on Solana.
+//!
+//! The Solana program checks proofs with the `alt_bn128` pairing syscalls, which take the same
+//! big-endian points as the EVM precompiles but expect the A point negated. The payload built here
+//! carries the converted points together with the two public inputs of the SP1 Groth16 circuit, so
+//! the program only runs the pairing check instead of re-encoding anything within its compute
+//! budget.
+
+use std::path::Path;
+
+use anyhow::Context;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use borsh::{BorshDeserialize, BorshSerialize};
+use bridge_verifier::solana::{public_inputs, SolanaGroth16Proof};
+use serde::{Deserialize, Serialize};
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues};
+
+/// Groth16 proof and public inputs in the Borsh layout of the Solana instruction.
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SolanaProofPayload {
+    /// The A point, negated.
+    pub proof_a: [u8; 64],
+    pub proof_b: [u8; 128],
+    pub proof_c: [u8; 64],
+    /// Program vkey hash and the digest of the public values.
+    pub public_inputs: [[u8; 32]; 2],
+    /// Public values committed by the guest, decoded by the program after verification.
+    pub public_values: Vec<u8>,
+}
+
+impl SolanaProofPayload {
+    /// Convert a Groth16 proof of the program with `vkey_hash`.
+    pub fn new(proof: &SP1ProofWithPublicValues, vkey_hash: [u8; 32]) -> anyhow::Result<Self> {
+        let SP1Proof::Groth16(groth16) = &proof.proof else {
+            anyhow::bail!(
+                "Solana verifies Groth16 proofs only, got a {} proof",
+                proof.proof
+            );
+        };
+        anyhow::ensure!(
+            !groth16.encoded_proof.is_empty(),
+            "mock Groth16 proofs cannot be verified on chain"
+        );
+
+        // Same bytes as `SP1ProofWithPublicValues::bytes()` without an attached TEE proof.
+        let proof_bytes = [
+            &groth16.groth16_vkey_hash[..4],
+            &hex::decode(&groth16.encoded_proof).context("invalid Groth16 proof encoding")?,
+        ]
+        .concat();
+        let points = SolanaGroth16Proof::from_sp1_bytes(&proof_bytes)
+            .map_err(|e| anyhow::anyhow!("cannot convert Groth16 proof: {e}"))?;
+
+        let public_values = proof.public_values.to_vec();
+        Ok(Self {
+            proof_a: points.proof_a,
+            proof_b: points.proof_b,
+            proof_c: points.proof_c,
+            public_inputs: public_inputs(&vkey_hash, &public_values),
+            public_values,
+        })
+    }
+
+    /// The proof points as parsed by the on-chain verifier.
+    pub fn points(&self) -> SolanaGroth16Proof {
+        SolanaGroth16Proof {
+            proof_a: self.proof_a,
+            proof_b: self.proof_b,
+            proof_c: self.proof_c,
+        }
+    }
+
+    /// Borsh encoding passed as the proof argument of the instruction.
+    pub fn to_instruction_data(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("in-memory Borsh serialization cannot fail")
+    }
+
+    pub fn from_instruction_data(data: &[u8]) -> anyhow::Result<Self> {
+        Self::try_from_slice(data).context("invalid Solana proof payload")
+    }
+}
+
+/// JSON export of a [`SolanaProofPayload`] for clients building the transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolanaProofExport {
+    pub proof_a: String,
+    pub proof_b: String,
+    pub proof_c: String,
+    pub public_inputs: [String; 2],
+    pub public_values: String,
+    /// Base64 Borsh encoding of the whole payload.
+    pub instruction_data: String,
+}
+
+impl From<&SolanaProofPayload> for SolanaProofExport {
+    fn from(payload: &SolanaProofPayload) -> Self {
+        let hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+        Self {
+            proof_a: hex(&payload.proof_a),
+            proof_b: hex(&payload.proof_b),
+            proof_c: hex(&payload.proof_c),
+            public_inputs: payload.public_inputs.map(|input| hex(&input)),
+            public_values: hex(&payload.public_values),
+            instruction_data: STANDARD.encode(payload.to_instruction_data()),
+        }
+    }
+}
+
+impl SolanaProofExport {
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write Solana proof {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge_verifier::ProofSystem;
+    use sp1_prover::Groth16Bn254Proof;
+    use sp1_sdk::SP1PublicValues;
+
+    /// A Groth16 proof with arbitrary points; only the encoding is under test here, the pairing
+    /// check of the converted layout is covered in `bridge-verifier`.
+    fn groth16_proof(points: &[u8]) -> SP1ProofWithPublicValues {
+        let mut groth16_vkey_hash = [0u8; 32];
+        groth16_vkey_hash[..4].copy_from_slice(&ProofSystem::Groth16.prefix());
+        SP1ProofWithPublicValues {
+            proof: SP1Proof::Groth16(Groth16Bn254Proof {
+                public_inputs: [String::new(), String::new()],
+                encoded_proof: hex::encode(points),
+                raw_proof: String::new(),
+                groth16_vkey_hash,
+            }),
+            public_values: SP1PublicValues::from(&[7; 320]),
+            sp1_version: "v5.0.0".to_string(),
+            tee_proof: None,
+        }
+    }
+
+    fn points() -> Vec<u8> {
+        (0..=255u8).map(|i| i % 0x30).collect()
+    }
+
+    #[test]
+    fn test_payload_roundtrip() {
+        let proof = groth16_proof(&points());
+        let payload = SolanaProofPayload::new(&proof, [0x01; 32]).unwrap();
+
+        assert_eq!(payload.public_inputs[0], [0x01; 32]);
+        assert_eq!(payload.public_values, vec![7; 320]);
+        // B and C are passed through unchanged, A is negated.
+        assert_eq!(payload.proof_b[..], points()[64..192]);
+        assert_eq!(payload.proof_c[..], points()[192..]);
+        assert_ne!(payload.proof_a[..], points()[..64]);
+
+        let data = payload.to_instruction_data();
+        assert_eq!(data.len(), 64 + 128 + 64 + 64 + 4 + 320);
+        assert_eq!(
+            SolanaProofPayload::from_instruction_data(&data).unwrap(),
+            payload
+        );
+
+        // Converting back yields the bytes the Solidity verifier takes.
+        assert_eq!(
+            payload.points().to_sp1_bytes(ProofSystem::Groth16.prefix()),
+            proof.bytes()
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_groth16_proofs() {
+        let mut proof = groth16_proof(&points());
+        proof.proof = SP1Proof::Core(vec![]);
+        assert!(SolanaProofPayload::new(&proof, [0x01; 32]).is_err());
+
+        // Mock Groth16 proofs have no encoded proof.
+        let proof = groth16_proof(&[]);
+        assert!(SolanaProofPayload::new(&proof, [0x01; 32]).is_err());
+
+        let proof = groth16_proof(&points()[..200]);
+        assert!(SolanaProofPayload::new(&proof, [0x01; 32]).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Verifying keys of the guest programs and the pinned key registry.
+//!
+//! The Solidity contracts and the Solana Groth16 verifier both take the `bytes32` hash of the program
+//! vkey, the latter unchanged as the first public input of the `alt_bn128` pairing check. The
+//! aggregation guest verifies proofs against the eight `u32` words of the digest instead.
+//!
+//! `vkeys.json` records the vkey of every guest version and pins the version deployed on chain. The
+//! registry is checked in at the root of `sp1-bridge-prover`, next to `bridge.example.toml`.
+//! Host tools refuse to produce or submit proofs whose vkey differs from the pinned one, since
+//! those would be rejected on chain. The relayer and the prover service also refuse to start
+//! without a pinned vkey, see [`require_pinned`].
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sp1_sdk::{HashableKey, SP1VerifyingKey};
+
+use crate::{artifact::unix_now, backend::Program, config::ProverConfig, input::hex_bytes32};
+
+/// File name of the registry next to the configuration file, see [`registry_path`].
+pub const DEFAULT_REGISTRY: &str = "vkeys.json";
+
+/// Directory of the checked-in registry, the root of `sp1-bridge-prover`.
+const REGISTRY_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+/// The vkey of a program in every format the verifiers use.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VkeyExport {
+    pub program: String,
+    /// `bytes32` hash for the Solidity and Solana verifiers.
+    #[serde(with = "hex_bytes32")]
+    pub bytes32: [u8; 32],
+    /// Digest words checked by `verify_sp1_proof` in the aggregation guest.
+    pub hash_u32: [u32; 8],
+}
+
+impl VkeyExport {
+    pub fn new(program: &Program, vk: &SP1VerifyingKey) -> Self {
+        Self {
+            program: program.name.to_string(),
+            bytes32: vk.bytes32_raw(),
+            hash_u32: vk.hash_u32(),
+        }
+    }
+
+    /// Rust constant holding the hash as a byte array, ready to paste into an Anchor program.
+    pub fn solana_const(&self) -> String {
+        let bytes: Vec<String> = self.bytes32.iter().map(|b| format!("0x{b:02x}")).collect();
+        format!(
+            "pub const {}_VKEY_HASH: [u8; 32] = [{}];",
+            self.program.to_uppercase(),
+            bytes.join(", ")
+        )
+    }
+}
+
+/// The vkey of one guest version.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VkeyEntry {
+    pub program: String,
+    pub version: String,
+    #[serde(with = "hex_bytes32")]
+    pub vkey: [u8; 32],
+    pub hash_u32: [u32; 8],
+    /// SHA-256 of the guest ELF the vkey was derived from.
+    #[serde(with = "hex_bytes32")]
+    pub elf_sha256: [u8; 32],
+    pub sp1_version: String,
+    pub created_at: u64,
+}
+
+impl VkeyEntry {
+    pub fn new(program: &Program, version: &str, vk: &SP1VerifyingKey) -> Self {
+        Self {
+            program: program.name.to_string(),
+            version: version.to_string(),
+            vkey: vk.bytes32_raw(),
+            hash_u32: vk.hash_u32(),
+            elf_sha256: Sha256::digest(program.elf).into(),
+            sp1_version: sp1_sdk::SP1_CIRCUIT_VERSION.to_string(),
+            created_at: unix_now(),
+        }
+    }
+}
+
+/// The `vkeys.json` registry.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VkeyRegistry {
+    /// Version deployed on chain, per program.
+    #[serde(default)]
+    pub pinned: BTreeMap<String, String>,
+    #[serde(default)]
+    pub versions: Vec<VkeyEntry>,
+}
+
+impl VkeyRegistry {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read vkey registry {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("failed to parse vkey registry {}", path.display()))
+    }
+
+    /// Load the registry, or start an empty one if the file does not exist.
+    pub fn load_or_default(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("failed to write vkey registry {}", path.display()))
+    }
+
+    pub fn get(&self, program: &str, version: &str) -> Option<&VkeyEntry> {
+        self.versions
+            .iter()
+            .find(|entry| entry.program == program && entry.version == version)
+    }
+
+    /// The entry of the pinned version of `program`.
+    pub fn pinned(&self, program: &str) -> Option<&VkeyEntry> {
+        let version = self.pinned.get(program)?;
+        self.get(program, version)
+    }
+
+    /// Add a version. Recording a version again is allowed only with the same vkey.
+    pub fn record(&mut self, entry: VkeyEntry) -> anyhow::Result<()> {
+        match self.get(&entry.program, &entry.version) {
+            Some(existing) if existing.vkey == entry.vkey => Ok(()),
+            Some(existing) => anyhow::bail!(
+                "{} version {} is already recorded with vkey 0x{}",
+                entry.program,
+                entry.version,
+                hex::encode(existing.vkey)
+            ),
+            None => {
+                self.versions.push(entry);
+                Ok(())
+            }
+        }
+    }
+
+    /// Pin a recorded version as the one deployed on chain.
+    pub fn pin(&mut self, program: &str, version: &str) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.get(program, version).is_some(),
+            "{program} version {version} is not recorded"
+        );
+        self.pinned.insert(program.to_string(), version.to_string());
+        Ok(())
+    }
+
+    /// Fail if `vkey` is not the vkey of the pinned version of `program`. Programs without a
+    /// pinned version are not checked.
+    pub fn check(&self, program: &str, vkey: [u8; 32]) -> anyhow::Result<()> {
+        let Some(pinned) = self.pinned(program) else {
+            return Ok(());
+        };
+        anyhow::ensure!(
+            pinned.vkey == vkey,
+            "{program} vkey 0x{} does not match the pinned version {} (0x{}); \
+             rebuild the pinned guest or pin the new version",
+            hex::encode(vkey),
+            pinned.version,
+            hex::encode(pinned.vkey)
+        );
+        Ok(())
+    }
+
+    /// Like [`Self::check`], but fail if `program` has no pinned version.
+    pub fn require(&self, program: &str, vkey: [u8; 32]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.pinned(program).is_some(),
+            "no {program} version is pinned"
+        );
+        self.check(program, vkey)
+    }
+}
+
+/// Path of the registry: `prover.vkey_registry`, which [`crate::config::Config::load`] resolves
+/// next to the configuration file, or else the checked-in `sp1-bridge-prover/vkeys.json`. Never
+/// relative to the working directory, so a daemon started elsewhere does not miss its registry.
+pub fn registry_path(config: &ProverConfig) -> PathBuf {
+    config
+        .vkey_registry
+        .clone()
+        .unwrap_or_else(|| Path::new(REGISTRY_DIR).join(DEFAULT_REGISTRY))
+}
+
+/// Check `vk` against the pinned version in the registry at `path`, if there is one.
+pub fn check_pinned(path: &Path, program: &Program, vk: &SP1VerifyingKey) -> anyhow::Result<()> {
+    if !path.exists() {
+        tracing::warn!(
+            "no vkey registry at {}, not checking the {} vkey",
+            path.display(),
+            program.name
+        );
+        return Ok(());
+    }
+    VkeyRegistry::load(path)?.check(program.name, vk.bytes32_raw())
+}
+
+/// Like [`check_pinned`], but a missing registry or a program without a pinned version is an
+/// error. Used by the binaries that submit proofs on chain.
+pub fn require_pinned(path: &Path, program: &Program, vk: &SP1VerifyingKey) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        path.exists(),
+        "no vkey registry at {}; record and pin the deployed guest with \
+         `bridge-prover vkey --record <version> --pin`",
+        path.display()
+    );
+    VkeyRegistry::load(path)?
+        .require(program.name, vk.bytes32_raw())
+        .with_context(|| format!("vkey registry {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(version: &str, vkey: u8) -> VkeyEntry {
+        VkeyEntry {
+            program: "bridge".to_string(),
+            version: version.to_string(),
+            vkey: [vkey; 32],
+            hash_u32: [vkey as u32; 8],
+            elf_sha256: [0; 32],
+            sp1_version: "v5.0.0".to_string(),
+            created_at: 1,
+        }
+    }
+
+    #[test]
+    fn test_check_against_pinned_version() {
+        let mut registry = VkeyRegistry::default();
+        registry.record(entry("v1", 1)).unwrap();
+        registry.record(entry("v2", 2)).unwrap();
+
+        // Nothing pinned yet, every vkey passes.
+        registry.check("bridge", [9; 32]).unwrap();
+
+        registry.pin("bridge", "v1").unwrap();
+        registry.check("bridge", [1; 32]).unwrap();
+        assert!(registry.check("bridge", [2; 32]).is_err());
+        registry.check("aggregation", [2; 32]).unwrap();
+
+        assert!(registry.pin("bridge", "v3").is_err());
+    }
+
+    #[test]
+    fn test_recorded_versions_are_immutable() {
+        let mut registry = VkeyRegistry::default();
+        registry.record(entry("v1", 1)).unwrap();
+        registry.record(entry("v1", 1)).unwrap();
+        assert!(registry.record(entry("v1", 2)).is_err());
+        assert_eq!(registry.versions.len(), 1);
+    }
+
+    #[test]
+    fn test_registry_roundtrip() {
+        let mut registry = VkeyRegistry::default();
+        registry.record(entry("v1", 1)).unwrap();
+        registry.pin("bridge", "v1").unwrap();
+
+        let path = std::env::temp_dir().join(format!("vkeys-{}.json", std::process::id()));
+        registry.save(&path).unwrap();
+        let loaded = VkeyRegistry::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.pinned("bridge"), Some(&entry("v1", 1)));
+    }
+
+    #[test]
+    fn test_default_registry_is_checked_in() {
+        let path = registry_path(&ProverConfig::default());
+        assert!(path.ends_with(DEFAULT_REGISTRY));
+        VkeyRegistry::load(&path).unwrap();
+    }
+
+    #[test]
+    fn test_require_needs_a_pinned_version() {
+        let mut registry = VkeyRegistry::default();
+        registry.record(entry("v1", 1)).unwrap();
+        assert!(registry.require("bridge", [1; 32]).is_err());
+
+        registry.pin("bridge", "v1").unwrap();
+        registry.require("bridge", [1; 32]).unwrap();
+        assert!(registry.require("bridge", [2; 32]).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Executes the bridge guest against valid and tampered inputs.
+//!
+//! Everything runs on the CPU executor (and the mock prover for the proof round trip), so the
+//! suite needs no proving hardware. Each negative case must make the guest panic.
+
+use alloy_sol_types::SolType;
+use fibonacci_lib::bridge::{
+    compute_root, hash_order, public_values, BridgeProofPublicValues, MerkleProof, OrderStatus,
+    TransferOrder,
+};
+use fibonacci_lib::hash::HashFunction;
+use fibonacci_script::{
+    decode_public_values,
+    input::{demo_order, demo_siblings, BridgeInput},
+    BRIDGE_VERIFY_ELF,
+};
+use sp1_sdk::{Prover, ProverClient, SP1PublicValues};
+
+/// An input whose Merkle proof is built from `order` itself.
+fn valid_input(order: TransferOrder) -> BridgeInput {
+    let hash = HashFunction::for_chain(order.source_chain);
+    let merkle_proof = MerkleProof::from_siblings(hash, hash_order(&order), demo_siblings());
+    BridgeInput {
+        order,
+        merkle_proof,
+    }
+}
+
+fn execute(input: &BridgeInput) -> anyhow::Result<SP1PublicValues> {
+    let client = ProverClient::builder().mock().build();
+    let (public_values, _) = client.execute(BRIDGE_VERIFY_ELF, &input.stdin()).run()?;
+    Ok(public_values)
+}
+
+fn assert_rejected(input: &BridgeInput) {
+    assert!(
+        execute(input).is_err(),
+        "guest accepted order {} with root 0x{}",
+        input.order.order_id,
+        hex::encode(input.merkle_proof.root)
+    );
+}
+
+#[test]
+fn test_valid_order_commits_public_values() {
+    let input = valid_input(demo_order());
+    let output = execute(&input).unwrap();
+
+    let (decoded, order_hash) = decode_public_values(output.as_slice()).unwrap();
+    let expected = public_values(&input.order, input.merkle_proof.root);
+    assert_eq!(
+        BridgeProofPublicValues::abi_encode(&decoded),
+        BridgeProofPublicValues::abi_encode(&expected)
+    );
+    assert_eq!(order_hash, hash_order(&input.order));
+    assert_eq!(decoded.targetChain, 1);
+    assert_eq!(decoded.hashFunction, HashFunction::Sha256 as u8);
+}
+
+#[test]
+fn test_evm_order_uses_keccak_tree() {
+    let order = TransferOrder {
+        source_chain: 1,
+        ..demo_order()
+    };
+    let input = valid_input(order);
+    let output = execute(&input).unwrap();
+
+    let (decoded, order_hash) = decode_public_values(output.as_slice()).unwrap();
+    assert_eq!(decoded.hashFunction, HashFunction::Keccak256 as u8);
+    assert_eq!(decoded.stateRoot, input.merkle_proof.root);
+    assert_eq!(order_hash, hash_order(&input.order));
+}
+
+#[test]
+fn test_rejects_tree_with_wrong_hash_function() {
+    // EVM 订单必须使用 Keccak 树，提交 SHA-256 树应被拒绝
+    let order = TransferOrder {
+        source_chain: 1,
+        ..demo_order()
+    };
+    let input = BridgeInput {
+        merkle_proof: MerkleProof::from_siblings(
+            HashFunction::Sha256,
+            hash_order(&order),
+            demo_siblings(),
+        ),
+        order,
+    };
+    assert_rejected(&input);
+}
+
+#[test]
+fn test_mock_proof_roundtrip() {
+    let client = ProverClient::builder().mock().build();
+    let input = valid_input(demo_order());
+    let (pk, vk) = client.setup(BRIDGE_VERIFY_ELF);
+
+    let proof = client.prove(&pk, &input.stdin()).run().unwrap();
+    client.verify(&proof, &vk).unwrap();
+
+    let (decoded, _) = decode_public_values(proof.public_values.as_slice()).unwrap();
+    assert_eq!(decoded.orderId, input.order.order_id);
+}
+
+#[test]
+fn test_rejects_wrong_merkle_root() {
+    let mut input = valid_input(demo_order());
+    input.merkle_proof.root = [0xaa; 32];
+    assert_rejected(&input);
+}
+
+#[test]
+fn test_rejects_tampered_amount() {
+    let mut input = valid_input(demo_order());
+    input.order.amount *= 1000;
+    assert_rejected(&input);
+}
+
+#[test]
+fn test_rejects_tampered_recipient() {
+    let mut input = valid_input(demo_order());
+    input.order.recipient = [0xee; 32];
+    assert_rejected(&input);
+}
+
+#[test]
+fn test_rejects_completed_order() {
+    // 状态不参与订单哈希，Merkle 证明仍然有效，只能靠状态检查拦截
+    let order = TransferOrder {
+        status: OrderStatus::Completed,
+        ..demo_order()
+    };
+    assert_rejected(&valid_input(order));
+}
+
+#[test]
+fn test_rejects_zero_amount() {
+    let order = TransferOrder {
+        amount: 0,
+        ..demo_order()
+    };
+    assert_rejected(&valid_input(order));
+}
+
+#[test]
+fn test_rejects_empty_proof_path() {
+    // 空路径下 compute_root 返回叶子本身，root = leaf 会被 verify() 接受
+    let order = demo_order();
+    let leaf = hash_order(&order);
+    let input = BridgeInput {
+        order,
+        merkle_proof: MerkleProof {
+            leaf,
+            proof: vec![],
+            root: leaf,
+        },
+    };
+    assert!(input.merkle_proof.verify(HashFunction::Sha256));
+    assert_rejected(&input);
+}
+
+#[test]
+fn test_rejects_internal_node_as_leaf() {
+    // 用第一层内部节点冒充叶子，剩余路径仍能推出同一个根
+    let input = valid_input(demo_order());
+    let siblings = demo_siblings();
+    let node = compute_root(
+        HashFunction::Sha256,
+        &input.merkle_proof.leaf,
+        &siblings[..1],
+    );
+    let forged = MerkleProof {
+        leaf: node,
+        proof: siblings[1..].to_vec(),
+        root: input.merkle_proof.root,
+    };
+    assert_eq!(
+        compute_root(HashFunction::Sha256, &forged.leaf, &forged.proof),
+        forged.root
+    );
+
+    assert_rejected(&BridgeInput {
+        order: input.order,
+        merkle_proof: forged,
+    });
+}
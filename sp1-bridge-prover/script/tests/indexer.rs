@@ -0,0 +1,289 @@
+//! Runs the indexer on recorded history of both bridges.
+//!
+//! The fixtures hold `getTransaction` results of the Anchor program and `eth_getLogs` results of
+//! the EVM bridge: two orders locked on Solana, the first one unlocked on the EVM bridge, and an
+//! order locked on the EVM bridge and unlocked on Solana. They also hold a failed lock, an unlock
+//! in a block that was reorganized away, and an unlock without a lock. The recorded sources reveal
+//! the history in steps, like chains that grow between polls.
+
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use fibonacci_script::{
+    artifact::unix_now,
+    indexer::{
+        Chain, EvmHistory, EvmLog, Indexer, IndexerConfig, IndexerStore, OrderState,
+        ProgramTransaction, SolanaHistory, TokenVolume,
+    },
+};
+use serde_json::Value;
+use tiny_http::Server;
+
+const MINT: &str = "5bV6jUfhDHCQVA1WfKBUnXUsboJgoKgkzkKcxr3joew5";
+const EVM_TOKEN: &str = "0x7c7c7c7c7c7c7c7c7c7c7c7c7c7c7c7c7c7c7c7c";
+const EVM_RELAYER: &str = "0xf3f3f3f3f3f3f3f3f3f3f3f3f3f3f3f3f3f3f3f3";
+
+fn fixture<T: serde::de::DeserializeOwned>(name: &str) -> T {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/indexer")
+        .join(name);
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// Recorded program transactions, of which the first `visible` have happened so far.
+struct RecordedSolana {
+    transactions: Vec<ProgramTransaction>,
+    visible: AtomicUsize,
+}
+
+impl RecordedSolana {
+    fn new(visible: usize) -> Self {
+        Self {
+            transactions: fixture("solana_transactions.json"),
+            visible: AtomicUsize::new(visible),
+        }
+    }
+}
+
+impl SolanaHistory for RecordedSolana {
+    fn transactions(
+        &self,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<(Vec<ProgramTransaction>, Option<String>)> {
+        let visible = &self.transactions[..self.visible.load(Ordering::SeqCst)];
+        let start = cursor.map_or(0, |cursor| {
+            visible
+                .iter()
+                .position(|tx| tx.signature() == cursor)
+                .expect("cursor is a recorded signature")
+                + 1
+        });
+        let next = visible
+            .last()
+            .map(|tx| tx.signature().to_string())
+            .or_else(|| cursor.map(str::to_string));
+        Ok((visible[start..].to_vec(), next))
+    }
+}
+
+/// Recorded bridge logs, of which the blocks up to `head` are final so far.
+struct RecordedEvm {
+    logs: Vec<EvmLog>,
+    head: AtomicU64,
+}
+
+impl RecordedEvm {
+    fn new(head: u64) -> Self {
+        Self {
+            logs: fixture("evm_logs.json"),
+            head: AtomicU64::new(head),
+        }
+    }
+}
+
+impl EvmHistory for RecordedEvm {
+    fn logs(&self, from_block: u64) -> anyhow::Result<(Vec<EvmLog>, u64)> {
+        let head = self.head.load(Ordering::SeqCst);
+        let logs = self
+            .logs
+            .iter()
+            .filter(|log| {
+                let block = u64::from_str_radix(&log.block_number[2..], 16).unwrap();
+                (from_block..=head).contains(&block)
+            })
+            .cloned()
+            .collect();
+        Ok((logs, from_block.max(head + 1)))
+    }
+}
+
+fn indexer(solana: RecordedSolana, evm: RecordedEvm, store: IndexerStore) -> Indexer {
+    Indexer::new(
+        Box::new(solana),
+        Box::new(evm),
+        store,
+        IndexerConfig::default(),
+    )
+}
+
+fn temp_database(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("indexer-{name}-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn test_correlates_orders_across_chains() {
+    let indexer = indexer(
+        RecordedSolana::new(4),
+        RecordedEvm::new(300),
+        IndexerStore::open_in_memory().unwrap(),
+    );
+    // Three events on each chain; the failed lock and the removed log are skipped.
+    assert_eq!(indexer.sync().unwrap(), 6);
+    let store = indexer.store();
+
+    let order = store.order(Chain::Solana, 1).unwrap().unwrap();
+    assert_eq!(order.state, OrderState::Completed);
+    let source = order.source.unwrap();
+    assert_eq!(source.token, MINT);
+    assert_eq!(source.recipient, format!("0x{}", "33".repeat(20)));
+    assert_eq!((source.amount.as_str(), source.block), ("999000", 100));
+    let destination = order.destination.unwrap();
+    assert_eq!(destination.relayer, EVM_RELAYER);
+    assert_eq!(destination.token, EVM_TOKEN);
+    assert_eq!(
+        (destination.amount.as_str(), destination.block),
+        ("949000", 210)
+    );
+
+    // The unlock of order 2 was reorganized away.
+    let order = store.order(Chain::Solana, 2).unwrap().unwrap();
+    assert_eq!(order.state, OrderState::Pending);
+    assert!(store.order(Chain::Solana, 3).unwrap().is_none());
+
+    let order = store.order(Chain::Evm, 0).unwrap().unwrap();
+    assert_eq!(order.state, OrderState::Completed);
+    assert_eq!(order.source.unwrap().token, EVM_TOKEN);
+    let destination = order.destination.unwrap();
+    assert_eq!(destination.relayer, bs58::encode([0x66; 32]).into_string());
+    assert_eq!(destination.token, MINT);
+
+    let order = store.order(Chain::Solana, 7).unwrap().unwrap();
+    assert_eq!(order.state, OrderState::Unmatched);
+    assert!(order.source.is_none());
+
+    let user = bs58::encode([0x11; 32]).into_string();
+    let orders = store.by_user(&user).unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].order_id, 1);
+    let recipient = bs58::encode([0x55; 32]).into_string();
+    assert_eq!(
+        store.by_recipient(&recipient).unwrap()[0].source_chain,
+        Chain::Evm
+    );
+
+    // Order 1 was locked at 1_700_000_000 and order 2 fifty seconds later.
+    assert!(store.pending_since(1_700_000_049).unwrap().is_empty());
+    assert_eq!(store.pending_since(1_700_000_050).unwrap()[0].order_id, 2);
+
+    assert_eq!(
+        store.volume().unwrap(),
+        vec![
+            TokenVolume {
+                chain: Chain::Solana,
+                token: MINT.to_string(),
+                orders: 2,
+                completed: 1,
+                locked: "1498500".to_string(),
+                released: "949000".to_string(),
+            },
+            TokenVolume {
+                chain: Chain::Evm,
+                token: EVM_TOKEN.to_string(),
+                orders: 1,
+                completed: 1,
+                locked: "999000".to_string(),
+                released: "949000".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_resumes_from_cursors() {
+    let database = temp_database("resume");
+
+    // Only the locks have happened at the first poll.
+    let first = indexer(
+        RecordedSolana::new(2),
+        RecordedEvm::new(205),
+        IndexerStore::open(&database).unwrap(),
+    );
+    assert_eq!(first.sync().unwrap(), 3);
+    assert_eq!(first.sync().unwrap(), 0);
+    let order = first.store().order(Chain::Solana, 1).unwrap().unwrap();
+    assert_eq!(order.state, OrderState::Pending);
+    drop(first);
+
+    // A restarted indexer reads only what happened since.
+    let second = indexer(
+        RecordedSolana::new(4),
+        RecordedEvm::new(300),
+        IndexerStore::open(&database).unwrap(),
+    );
+    assert_eq!(second.sync().unwrap(), 3);
+    assert_eq!(second.sync().unwrap(), 0);
+    let store = second.store();
+    assert_eq!(
+        store.order(Chain::Solana, 1).unwrap().unwrap().state,
+        OrderState::Completed
+    );
+    assert_eq!(
+        store.order(Chain::Evm, 0).unwrap().unwrap().state,
+        OrderState::Completed
+    );
+    assert_eq!(store.cursor(Chain::Evm).unwrap().as_deref(), Some("301"));
+
+    let _ = std::fs::remove_file(&database);
+}
+
+#[test]
+fn test_query_api() {
+    let indexer = Arc::new(indexer(
+        RecordedSolana::new(4),
+        RecordedEvm::new(300),
+        IndexerStore::open_in_memory().unwrap(),
+    ));
+    indexer.sync().unwrap();
+
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    thread::spawn(move || indexer.serve(&server));
+    let get = |path: &str| {
+        let response = reqwest::blocking::get(format!("{url}{path}")).unwrap();
+        (
+            response.status().as_u16(),
+            response.json::<Value>().unwrap(),
+        )
+    };
+
+    let (status, order) = get("/orders/solana/1");
+    assert_eq!(status, 200);
+    assert_eq!(order["state"], "completed");
+    assert_eq!(order["source"]["amount"], "999000");
+    assert_eq!(order["destination"]["relayer"], EVM_RELAYER);
+
+    assert_eq!(get("/orders/evm/0").1["state"], "completed");
+    assert_eq!(get("/orders/solana/3").0, 404);
+    assert_eq!(get("/orders/bitcoin/1").0, 404);
+
+    let user = bs58::encode([0x12; 32]).into_string();
+    let (_, orders) = get(&format!("/orders?user={user}"));
+    assert_eq!(orders.as_array().unwrap().len(), 1);
+    assert_eq!(orders[0]["order_id"], 2);
+    let (_, orders) = get(&format!("/orders?recipient=0x{}", "33".repeat(20)));
+    assert_eq!(orders.as_array().unwrap().len(), 2);
+    assert_eq!(get("/orders").0, 400);
+
+    // The recorded orders were locked in 2023.
+    let older_than = unix_now() - 1_700_000_000 + 1;
+    let (_, pending) = get(&format!("/orders/pending?older_than={older_than}"));
+    assert!(pending.as_array().unwrap().is_empty());
+    let (_, pending) = get("/orders/pending?older_than=3600");
+    assert_eq!(pending.as_array().unwrap().len(), 1);
+    assert_eq!(pending[0]["order_id"], 2);
+    assert_eq!(get("/orders/pending").0, 400);
+
+    let (status, volume) = get("/volume");
+    assert_eq!(status, 200);
+    assert_eq!(volume[0]["chain"], "solana");
+    assert_eq!(volume[0]["locked"], "1498500");
+    assert_eq!(volume[1]["chain"], "evm");
+}
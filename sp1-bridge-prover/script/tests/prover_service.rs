@@ -0,0 +1,306 @@
+//! Runs the prover service end to end with a stub proving backend.
+//!
+//! The stub decodes the order from the guest inputs and returns the public values the guest would
+//! commit, so submission, the job states, retries, proof bundles and the remote backend client are
+//! all exercised without an SP1 prover.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use alloy_sol_types::SolType;
+use fibonacci_lib::bridge::{
+    hash_order, public_values, BridgeProofPublicValues, MerkleProof, TransferOrder,
+};
+use fibonacci_script::{
+    artifact::ProofBundle,
+    backend::{LocalBackend, Program, ProofBackend, RemoteBackend, TransientError, BRIDGE_PROGRAM},
+    decode_public_values,
+    input::{demo_order, demo_siblings, BridgeInput, MerkleFile, OrderFile},
+    service::{JobStore, OrderRecord, Service, ServiceConfig},
+    ProofMode,
+};
+use serde_json::{json, Value};
+use sp1_sdk::{
+    ExecutionReport, SP1Proof, SP1ProofWithPublicValues, SP1ProvingKey, SP1PublicValues, SP1Stdin,
+    SP1VerifyingKey,
+};
+use tiny_http::Server;
+
+const VKEY_HASH: [u8; 32] = [0x0a; 32];
+
+/// Stands in for the prover: rejects zero amounts like the guest and fails the first
+/// `failures` proofs, with a [`TransientError`] if `transient`.
+struct StubBackend {
+    failures: usize,
+    transient: bool,
+    proofs: AtomicUsize,
+}
+
+fn read_input(stdin: &SP1Stdin) -> (TransferOrder, MerkleProof) {
+    (
+        bincode::deserialize(&stdin.buffer[0]).unwrap(),
+        bincode::deserialize(&stdin.buffer[1]).unwrap(),
+    )
+}
+
+impl ProofBackend for StubBackend {
+    fn name(&self) -> &str {
+        "stub"
+    }
+
+    fn keys(&self, _: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)> {
+        unimplemented!("the stub backend has no keys")
+    }
+
+    fn execute(
+        &self,
+        _: &Program,
+        stdin: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        let (order, _) = read_input(stdin);
+        anyhow::ensure!(order.amount > 0, "Amount must be positive");
+        Ok((SP1PublicValues::new(), ExecutionReport::default()))
+    }
+
+    fn prove(
+        &self,
+        _: &Program,
+        stdin: &SP1Stdin,
+        _: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        if self.proofs.fetch_add(1, Ordering::SeqCst) < self.failures {
+            if self.transient {
+                return Err(TransientError("remote prover returned 503".to_string()).into());
+            }
+            anyhow::bail!("prover out of memory");
+        }
+        let (order, merkle_proof) = read_input(stdin);
+        let mut bytes =
+            BridgeProofPublicValues::abi_encode(&public_values(&order, merkle_proof.root));
+        bytes.extend_from_slice(&hash_order(&order));
+        Ok(SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(vec![]),
+            public_values: SP1PublicValues::from(&bytes),
+            sp1_version: "v5.0.0".to_string(),
+            tee_proof: None,
+        })
+    }
+
+    fn verify(&self, _: &SP1ProofWithPublicValues, _: &SP1VerifyingKey) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Start a service with in-memory storage whose first `failures` proofs fail transiently and
+/// return its URL.
+fn start(failures: usize, config: ServiceConfig) -> String {
+    let backend = StubBackend {
+        failures,
+        transient: true,
+        proofs: AtomicUsize::new(0),
+    };
+    start_with(backend, config)
+}
+
+fn start_with(backend: StubBackend, config: ServiceConfig) -> String {
+    let service = Arc::new(Service::new(
+        JobStore::open_in_memory().unwrap(),
+        Box::new(backend),
+        VKEY_HASH,
+        ServiceConfig {
+            poll_interval: Duration::from_millis(10),
+            ..config
+        },
+    ));
+    service.spawn_workers();
+
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    thread::spawn(move || service.serve(&server));
+    url
+}
+
+fn submit(url: &str, body: Value) -> String {
+    let response: Value = reqwest::blocking::Client::new()
+        .post(format!("{url}/jobs"))
+        .json(&body)
+        .send()
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .json()
+        .unwrap();
+    response["id"].as_str().unwrap().to_string()
+}
+
+/// Poll a job until it is done or failed.
+fn wait(url: &str, id: &str) -> Value {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let job: Value = reqwest::blocking::get(format!("{url}/jobs/{id}"))
+            .unwrap()
+            .json()
+            .unwrap();
+        if job["status"] == "done" || job["status"] == "failed" {
+            return job;
+        }
+        assert!(Instant::now() < deadline, "job {id} did not finish: {job}");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn bundle(url: &str, id: &str) -> ProofBundle {
+    let bundle = reqwest::blocking::get(format!("{url}/jobs/{id}/bundle"))
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .text()
+        .unwrap();
+    serde_json::from_str(&bundle).unwrap()
+}
+
+fn order_body(order: &TransferOrder) -> Value {
+    let input = BridgeInput::from_files(
+        OrderFile::from(order),
+        MerkleFile {
+            proof: demo_siblings(),
+            ..Default::default()
+        },
+    );
+    json!({
+        "order": OrderFile::from(&input.order),
+        "merkle": MerkleFile::from(&input.merkle_proof),
+    })
+}
+
+#[test]
+fn test_remote_backend_proves_through_service() {
+    let url = start(0, ServiceConfig::default());
+    let input = BridgeInput::from_files(
+        OrderFile::from(&demo_order()),
+        MerkleFile {
+            proof: demo_siblings(),
+            ..Default::default()
+        },
+    );
+
+    let remote = RemoteBackend::new(&url, Duration::from_secs(10), LocalBackend::mock())
+        .with_poll_interval(Duration::from_millis(10));
+    let proof = remote
+        .prove(&BRIDGE_PROGRAM, &input.stdin(), ProofMode::Compressed)
+        .unwrap();
+
+    let (public_values, order_hash) = decode_public_values(proof.public_values.as_slice()).unwrap();
+    assert_eq!(public_values.orderId, input.order.order_id);
+    assert_eq!(public_values.stateRoot.0, input.merkle_proof.root);
+    assert_eq!(order_hash, hash_order(&input.order));
+
+    let bundle = bundle(&url, "1");
+    assert_eq!(bundle.metadata.mode, ProofMode::Compressed);
+    assert_eq!(bundle.metadata.vkey_hash, VKEY_HASH);
+    assert_eq!(bundle.metadata.order_id, input.order.order_id);
+}
+
+#[test]
+fn test_order_id_job_retries_failed_proof() {
+    let orders_dir = std::env::temp_dir().join(format!("orders-{}", std::process::id()));
+    std::fs::create_dir_all(&orders_dir).unwrap();
+    let mut order = demo_order();
+    order.order_id = 42;
+    let record: OrderRecord = serde_json::from_value(order_body(&order)).unwrap();
+    std::fs::write(
+        orders_dir.join("42.json"),
+        serde_json::to_string(&record).unwrap(),
+    )
+    .unwrap();
+
+    let url = start(
+        1,
+        ServiceConfig {
+            workers: 2,
+            orders_dir: Some(orders_dir.clone()),
+            ..Default::default()
+        },
+    );
+    let id = submit(&url, json!({ "mode": "groth16", "order_id": 42 }));
+    let job = wait(&url, &id);
+    std::fs::remove_dir_all(&orders_dir).unwrap();
+
+    assert_eq!(job["status"], "done", "{job}");
+    assert_eq!(job["attempts"], 2);
+    assert_eq!(job["order_id"], 42);
+    assert_eq!(bundle(&url, &id).metadata.order_id, 42);
+
+    // The failed attempt is not counted as a proof.
+    let metrics = reqwest::blocking::get(format!("{url}/metrics"))
+        .unwrap()
+        .text()
+        .unwrap();
+    assert!(metrics.contains("bridge_orders_total{stage=\"queued\"} 1"));
+    assert!(metrics.contains("bridge_proof_duration_seconds_count{mode=\"groth16\"} 1"));
+    assert!(metrics.contains("bridge_proof_cycles_count 2"));
+    assert!(metrics.contains("bridge_queue_depth 0"));
+}
+
+#[test]
+fn test_permanent_prove_error_is_not_retried() {
+    let backend = StubBackend {
+        failures: 1,
+        transient: false,
+        proofs: AtomicUsize::new(0),
+    };
+    let url = start_with(backend, ServiceConfig::default());
+    let mut body = order_body(&demo_order());
+    body["mode"] = json!("core");
+    let id = submit(&url, body);
+    let job = wait(&url, &id);
+
+    assert_eq!(job["status"], "failed", "{job}");
+    assert_eq!(job["attempts"], 1);
+    assert!(job["error"]
+        .as_str()
+        .unwrap()
+        .contains("prover out of memory"));
+}
+
+#[test]
+fn test_invalid_order_fails_without_proving() {
+    let url = start(0, ServiceConfig::default());
+    let mut order = demo_order();
+    order.amount = 0;
+
+    let mut body = order_body(&order);
+    body["mode"] = json!("core");
+    let id = submit(&url, body);
+    let job = wait(&url, &id);
+
+    assert_eq!(job["status"], "failed");
+    assert_eq!(job["attempts"], 1);
+    assert!(job["error"]
+        .as_str()
+        .unwrap()
+        .contains("Amount must be positive"));
+    assert!(job["proof"].is_null());
+}
+
+#[test]
+fn test_rejects_unknown_order_and_job() {
+    let url = start(0, ServiceConfig::default());
+    let client = reqwest::blocking::Client::new();
+
+    let response = client
+        .post(format!("{url}/jobs"))
+        .json(&json!({ "mode": "core", "order_id": 7 }))
+        .send()
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let response = client.get(format!("{url}/jobs/99")).send().unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -0,0 +1,1035 @@
+//! Runs the relayer against stand-in Solana and EVM JSON-RPC nodes.
+//!
+//! The Solana stand-in serves the transaction history, logs and accounts of the Anchor bridge
+//! program; the EVM stand-in plays an Anvil node with an unlocked account and records every
+//! `unlockTokens` call. Proofs come from a stub backend that returns the public values the guest
+//! would commit. Reorgs are scripted with a fake Solana source whose forks the tests switch
+//! between. Crashes are simulated in process by panicking inside the backend or the destination
+//! chain and reopening the relayer on the same database. One test kills the `relayer` binary
+//! itself; it proves with the mock backend, so it needs the built guest like `guest_execution`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use alloy_sol_types::{sol, SolCall, SolType};
+use anchor_lang::{AccountSerialize, Event};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use fibonacci_lib::{
+    bridge::{hash_order, public_values, BridgeProofPublicValues, MerkleProof, TransferOrder},
+    hash::HashFunction,
+};
+use fibonacci_script::{
+    anchor::{AnchorOrderStatus, AnchorTokenConfig, AnchorTransferOrder, Pubkey, TokensLocked},
+    backend::{Program, ProofBackend},
+    input::demo_order,
+    relayer::{
+        Claim, DestinationChain, EvmRpc, LockedOrder, RelayStatus, Relayer, RelayerConfig,
+        RelayerStore, SolanaRpc, SourceChain,
+    },
+    ProofMode,
+};
+use serde_json::{json, Value};
+use sp1_sdk::{
+    ExecutionReport, SP1Proof, SP1ProofWithPublicValues, SP1ProvingKey, SP1PublicValues, SP1Stdin,
+    SP1VerifyingKey,
+};
+use tiny_http::{Response, Server};
+
+sol! {
+    function claimOrder(uint64 orderId) external payable;
+    function unlockTokens(uint64 orderId, bytes32 proofHash) external;
+    function getTransferOrder(uint64 orderId) external view returns (uint8 status);
+    function claimBond() external view returns (uint256);
+    function claimWindowBlocks() external view returns (uint64);
+}
+
+const PROGRAM_ID: [u8; 32] = [0x01; 32];
+const TOKEN_CONFIG: Pubkey = Pubkey::new_from_array([0x22; 32]);
+const MINT: Pubkey = Pubkey::new_from_array([0x44; 32]);
+const BRIDGE: [u8; 20] = [0xbb; 20];
+/// The stand-in's unlocked account, sending the relayer's transactions.
+const RELAYER: [u8; 20] = [0xf3; 20];
+const CLAIM_WINDOW: u64 = 20;
+
+/// State of the stand-in nodes.
+#[derive(Default)]
+struct Chains {
+    /// Transactions of the bridge program as (signature, slot, logs), oldest first.
+    transactions: Vec<(String, u64, Vec<String>)>,
+    /// `TransferOrder` account data by order id.
+    orders: BTreeMap<u64, Vec<u8>>,
+    /// Number of `getTransaction` requests served.
+    transaction_reads: usize,
+    /// Order ids passed to `unlockTokens`, in call order.
+    unlocks: Vec<u64>,
+    /// Orders whose `unlockTokens` transaction reverts.
+    reverting: BTreeSet<u64>,
+    /// Orders completed by a successful `unlockTokens`, with the block completing them.
+    completed: BTreeMap<u64, u64>,
+    /// Latest claim on each order, as (relayer, expiry block).
+    claims: BTreeMap<u64, ([u8; 20], u64)>,
+    /// Orders a competing relayer claims in the block before the next `claimOrder` of another
+    /// relayer.
+    front_run: BTreeMap<u64, [u8; 20]>,
+    /// Outcome and block of every transaction; each transaction is mined in its own block.
+    receipts: BTreeMap<String, (bool, u64)>,
+    /// Number of receipt requests answered with `null`, as if the transaction was still pending.
+    pending_receipt_reads: usize,
+    block_number: u64,
+}
+
+impl Chains {
+    /// Lock `order_ids` in one transaction.
+    fn lock(&mut self, signature: &str, order_ids: &[u64]) {
+        let slot = 100 + self.transactions.len() as u64;
+        let mut logs = vec![format!(
+            "Program {} invoke [1]",
+            bs58::encode(PROGRAM_ID).into_string()
+        )];
+        for &order_id in order_ids {
+            let order = AnchorTransferOrder {
+                order_id,
+                user: Pubkey::new_from_array([0x11; 32]),
+                status: AnchorOrderStatus::Pending,
+                token_config: TOKEN_CONFIG,
+                source_chain: 0,
+                amount: 1_000_000 * order_id,
+                recipient: [0x33; 20],
+                relayer_fee: 1_000,
+                created_slot: slot,
+                proof_hash: [0; 32],
+                completed_by: Pubkey::default(),
+                completed_at: 0,
+                claimed_by: Pubkey::default(),
+                claim_expiry: 0,
+                claim_bond: 0,
+            };
+            self.orders.insert(order_id, account_data(&order));
+
+            let event = TokensLocked {
+                order_id,
+                user: order.user,
+                amount: order.amount,
+                recipient: order.recipient,
+                relayer_fee: order.relayer_fee,
+                slot,
+                mint: MINT,
+            };
+            logs.push(format!("Program data: {}", STANDARD.encode(event.data())));
+        }
+        self.transactions.push((signature.to_string(), slot, logs));
+    }
+
+    fn handle(&mut self, method: &str, params: &Value) -> Value {
+        match method {
+            "getSignaturesForAddress" => {
+                let until = params[1]["until"].as_str();
+                let newer: Vec<Value> = self
+                    .transactions
+                    .iter()
+                    .rev()
+                    .take_while(|(signature, _, _)| Some(signature.as_str()) != until)
+                    .map(|(signature, slot, _)| {
+                        json!({ "signature": signature, "slot": slot, "err": null })
+                    })
+                    .collect();
+                json!(newer)
+            }
+            "getTransaction" => {
+                self.transaction_reads += 1;
+                let (_, slot, logs) = self
+                    .transactions
+                    .iter()
+                    .find(|(signature, _, _)| signature == params[0].as_str().unwrap())
+                    .unwrap();
+                json!({ "slot": slot, "meta": { "err": null, "logMessages": logs } })
+            }
+            "getProgramAccounts" => {
+                let id = bs58::decode(params[1]["filters"][1]["memcmp"]["bytes"].as_str().unwrap())
+                    .into_vec()
+                    .unwrap();
+                let order_id = u64::from_le_bytes(id.try_into().unwrap());
+                let accounts: Vec<Value> = self
+                    .orders
+                    .get(&order_id)
+                    .map(|data| {
+                        json!({
+                            "pubkey": bs58::encode([order_id as u8; 32]).into_string(),
+                            "account": { "data": [STANDARD.encode(data), "base64"] },
+                        })
+                    })
+                    .into_iter()
+                    .collect();
+                json!(accounts)
+            }
+            // Every transaction is final and its block never changes.
+            "getBlock" => json!({ "blockhash": format!("block-{}", params[0]) }),
+            "getSlot" => json!(self.transactions.last().map_or(0, |(_, slot, _)| *slot)),
+            "getAccountInfo" => {
+                let config = AnchorTokenConfig {
+                    solana_mint: MINT,
+                    evm_token: [0x55; 20],
+                    is_native_solana: true,
+                    total_locked: 0,
+                };
+                assert_eq!(params[0].as_str(), Some(TOKEN_CONFIG.to_string().as_str()));
+                json!({
+                    "context": { "slot": 1 },
+                    "value": { "data": [STANDARD.encode(account_data(&config)), "base64"] },
+                })
+            }
+            "eth_accounts" => json!([format!("0x{}", hex::encode(RELAYER))]),
+            "eth_sendTransaction" => {
+                let tx = &params[0];
+                assert_eq!(
+                    tx["to"].as_str(),
+                    Some(format!("0x{}", hex::encode(BRIDGE)).as_str())
+                );
+                let from = tx["from"].as_str().unwrap();
+                let from: [u8; 20] = hex::decode(from.trim_start_matches("0x"))
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+                let data = hex::decode(tx["data"].as_str().unwrap().trim_start_matches("0x"));
+                self.send(from, &data.unwrap())
+            }
+            "eth_call" => {
+                let data =
+                    hex::decode(params[0]["data"].as_str().unwrap().trim_start_matches("0x"))
+                        .unwrap();
+                let block = match params[1].as_str().unwrap() {
+                    "latest" => self.block_number,
+                    block => u64::from_str_radix(block.trim_start_matches("0x"), 16).unwrap(),
+                };
+                let mut result = vec![0u8; 32];
+                if let Ok(call) = getTransferOrderCall::abi_decode(&data) {
+                    // Only the status and the claim are read; the rest is left zeroed.
+                    result = vec![0u8; 15 * 32];
+                    result[8 * 32 + 31] = self
+                        .completed
+                        .get(&call.orderId)
+                        .is_some_and(|completed| *completed <= block)
+                        as u8;
+                    if let Some((relayer, expiry)) = self.claims.get(&call.orderId) {
+                        result[12 * 32 + 12..13 * 32].copy_from_slice(relayer);
+                        result[14 * 32 - 8..14 * 32].copy_from_slice(&expiry.to_be_bytes());
+                    }
+                } else if claimWindowBlocksCall::abi_decode(&data).is_ok() {
+                    result[24..].copy_from_slice(&CLAIM_WINDOW.to_be_bytes());
+                } else {
+                    claimBondCall::abi_decode(&data).unwrap();
+                }
+                json!(format!("0x{}", hex::encode(result)))
+            }
+            "eth_getTransactionReceipt" => {
+                if self.pending_receipt_reads > 0 {
+                    self.pending_receipt_reads -= 1;
+                    return Value::Null;
+                }
+                let (success, block) = self.receipts[params[0].as_str().unwrap()];
+                json!({
+                    "status": if success { "0x1" } else { "0x0" },
+                    "blockNumber": format!("{block:#x}"),
+                })
+            }
+            "eth_blockNumber" => json!(format!("{:#x}", self.block_number)),
+            _ => panic!("unexpected RPC method {method}"),
+        }
+    }
+}
+
+impl Chains {
+    /// Mine a transaction to the bridge in its own block and return its hash.
+    fn send(&mut self, from: [u8; 20], data: &[u8]) -> Value {
+        if let Ok(call) = claimOrderCall::abi_decode(data) {
+            if let Some(competitor) = self.front_run.remove(&call.orderId) {
+                self.send(competitor, data);
+            }
+        }
+        self.block_number += 1;
+        let block = self.block_number;
+        let claim_active = |claims: &BTreeMap<u64, ([u8; 20], u64)>, order_id| {
+            claims
+                .get(&order_id)
+                .is_some_and(|(relayer, expiry)| block < *expiry && *relayer != from)
+        };
+        let success = if let Ok(call) = claimOrderCall::abi_decode(data) {
+            // Like the contract, a relayer cannot claim an order again once its claim lapsed.
+            let success = !self.completed.contains_key(&call.orderId)
+                && !self
+                    .claims
+                    .get(&call.orderId)
+                    .is_some_and(|(relayer, expiry)| block < *expiry || *relayer == from);
+            if success {
+                self.claims
+                    .insert(call.orderId, (from, block + CLAIM_WINDOW));
+            }
+            success
+        } else {
+            let call = unlockTokensCall::abi_decode(data).unwrap();
+            self.unlocks.push(call.orderId);
+            // Like the contract, unlocks of completed orders or orders claimed by another
+            // relayer revert.
+            !self.reverting.contains(&call.orderId)
+                && !claim_active(&self.claims, call.orderId)
+                && self.completed.insert(call.orderId, block).is_none()
+        };
+
+        let hash = format!("0x{:064x}", self.receipts.len() + 1);
+        self.receipts.insert(hash.clone(), (success, block));
+        json!(hash)
+    }
+}
+
+fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+/// Serve the stand-in nodes on a random local port and return its URL.
+fn serve(chains: Arc<Mutex<Chains>>) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            let request_json: Value = serde_json::from_str(&body).unwrap();
+            let result = chains.lock().unwrap().handle(
+                request_json["method"].as_str().unwrap(),
+                &request_json["params"],
+            );
+            let response = json!({ "jsonrpc": "2.0", "id": request_json["id"], "result": result });
+            request
+                .respond(Response::from_string(response.to_string()))
+                .unwrap();
+        }
+    });
+    url
+}
+
+/// Returns the public values the guest would commit and records the Merkle proofs it was given.
+#[derive(Default)]
+struct StubBackend {
+    merkle_proofs: Arc<Mutex<Vec<MerkleProof>>>,
+    /// Crash instead of proving.
+    crash: bool,
+}
+
+impl ProofBackend for StubBackend {
+    fn name(&self) -> &str {
+        "stub"
+    }
+
+    fn keys(&self, _: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)> {
+        unimplemented!("the stub backend has no keys")
+    }
+
+    fn execute(
+        &self,
+        _: &Program,
+        _: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        unimplemented!("the relayer does not execute")
+    }
+
+    fn prove(
+        &self,
+        _: &Program,
+        stdin: &SP1Stdin,
+        _: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        assert!(!self.crash, "killed while proving");
+        let order: TransferOrder = bincode::deserialize(&stdin.buffer[0]).unwrap();
+        let merkle_proof: MerkleProof = bincode::deserialize(&stdin.buffer[1]).unwrap();
+        assert!(!merkle_proof.proof.is_empty());
+        assert!(merkle_proof.verify(HashFunction::for_chain(order.source_chain)));
+
+        let mut bytes =
+            BridgeProofPublicValues::abi_encode(&public_values(&order, merkle_proof.root));
+        bytes.extend_from_slice(&hash_order(&order));
+        self.merkle_proofs.lock().unwrap().push(merkle_proof);
+        Ok(SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(vec![]),
+            public_values: SP1PublicValues::from(&bytes),
+            sp1_version: "v5.0.0".to_string(),
+            tee_proof: None,
+        })
+    }
+
+    fn verify(&self, _: &SP1ProofWithPublicValues, _: &SP1VerifyingKey) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Blocks of a scripted Solana fork, each with a hash and the orders locked in it.
+#[derive(Default)]
+struct Fork {
+    blocks: BTreeMap<u64, (String, Vec<TransferOrder>)>,
+    finalized_slot: u64,
+    /// Bumped on every reorg, so blocks of the new fork get new hashes.
+    generation: u32,
+}
+
+impl Fork {
+    /// Add a block at `slot` locking orders with the given ids and amounts.
+    fn lock(&mut self, slot: u64, orders: &[(u64, u64)]) {
+        let orders = orders
+            .iter()
+            .map(|&(order_id, amount)| TransferOrder {
+                order_id,
+                amount,
+                created_at: slot,
+                ..demo_order()
+            })
+            .collect();
+        let hash = format!("fork{}-{slot}", self.generation);
+        self.blocks.insert(slot, (hash, orders));
+    }
+
+    /// Abandon every block from `slot` on; the blocks added next form the new fork.
+    fn reorg(&mut self, slot: u64) {
+        assert!(
+            slot > self.finalized_slot,
+            "final slots cannot be rolled back"
+        );
+        self.blocks.retain(|&block, _| block < slot);
+        self.generation += 1;
+    }
+}
+
+/// A Solana source serving a scripted fork.
+#[derive(Clone, Default)]
+struct ScriptedSolana(Arc<Mutex<Fork>>);
+
+impl SourceChain for ScriptedSolana {
+    fn locked_orders(
+        &self,
+        cursor: Option<&str>,
+    ) -> anyhow::Result<(Vec<LockedOrder>, Option<String>)> {
+        let fork = self.0.lock().unwrap();
+        let locked: Vec<LockedOrder> = fork
+            .blocks
+            .iter()
+            .flat_map(|(&slot, (hash, orders))| {
+                orders.iter().map(move |order| LockedOrder {
+                    event: TokensLocked {
+                        order_id: order.order_id,
+                        user: Pubkey::new_from_array(order.user),
+                        amount: order.amount,
+                        recipient: [0x33; 20],
+                        relayer_fee: order.relayer_fee,
+                        slot,
+                        mint: Pubkey::new_from_array(order.token),
+                    },
+                    signature: format!("{hash}-{}", order.order_id),
+                    slot,
+                    block_hash: hash.clone(),
+                })
+            })
+            .collect();
+        // A cursor on an abandoned fork is not in the history; start over like the RPC does.
+        let start = cursor
+            .and_then(|cursor| locked.iter().position(|l| l.signature == cursor))
+            .map_or(0, |index| index + 1);
+        let next = locked
+            .last()
+            .map(|l| l.signature.clone())
+            .or_else(|| cursor.map(str::to_string));
+        Ok((locked.into_iter().skip(start).collect(), next))
+    }
+
+    fn order(&self, order_id: u64) -> anyhow::Result<TransferOrder> {
+        let fork = self.0.lock().unwrap();
+        fork.blocks
+            .values()
+            .flat_map(|(_, orders)| orders)
+            .find(|order| order.order_id == order_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("order {order_id} not found"))
+    }
+
+    fn block_hash(&self, slot: u64) -> anyhow::Result<Option<String>> {
+        let fork = self.0.lock().unwrap();
+        Ok(fork.blocks.get(&slot).map(|(hash, _)| hash.clone()))
+    }
+
+    fn finalized_slot(&self) -> anyhow::Result<u64> {
+        Ok(self.0.lock().unwrap().finalized_slot)
+    }
+}
+
+/// Where the relayer process is killed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum KillPoint {
+    WhileProving,
+    BeforeSend,
+    AfterSend,
+    WhileConfirming,
+}
+
+/// The EVM stand-in, crashing at a kill point.
+struct CrashingEvm {
+    inner: EvmRpc,
+    kill: KillPoint,
+}
+
+impl DestinationChain for CrashingEvm {
+    fn is_completed(&self, order_id: u64) -> anyhow::Result<bool> {
+        self.inner.is_completed(order_id)
+    }
+
+    fn is_completed_final(&self, order_id: u64) -> anyhow::Result<bool> {
+        self.inner.is_completed_final(order_id)
+    }
+
+    fn claim(&self, order_id: u64) -> anyhow::Result<Claim> {
+        self.inner.claim(order_id)
+    }
+
+    fn submit(&self, order_id: u64, proof_hash: [u8; 32]) -> anyhow::Result<[u8; 32]> {
+        assert_ne!(self.kill, KillPoint::BeforeSend, "killed before sending");
+        let tx_hash = self.inner.submit(order_id, proof_hash)?;
+        assert_ne!(self.kill, KillPoint::AfterSend, "killed after sending");
+        Ok(tx_hash)
+    }
+
+    fn receipt(&self, tx_hash: [u8; 32]) -> anyhow::Result<Option<bool>> {
+        assert_ne!(
+            self.kill,
+            KillPoint::WhileConfirming,
+            "killed while confirming"
+        );
+        self.inner.receipt(tx_hash)
+    }
+}
+
+fn temp_database(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("relayer-{name}-{}.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn relayer(url: &str, store: RelayerStore, backend: StubBackend) -> Relayer {
+    relayer_between(
+        Box::new(SolanaRpc::new(url, PROGRAM_ID, "confirmed")),
+        Box::new(EvmRpc::new(url, BRIDGE, None).unwrap()),
+        store,
+        backend,
+    )
+}
+
+fn relayer_between(
+    source: Box<dyn SourceChain>,
+    destination: Box<dyn DestinationChain>,
+    store: RelayerStore,
+    backend: StubBackend,
+) -> Relayer {
+    Relayer::new(
+        source,
+        destination,
+        Box::new(backend),
+        store,
+        [0x0a; 32],
+        RelayerConfig {
+            mode: ProofMode::Core,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn test_relays_every_locked_order() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1, 2]);
+    chains.lock().unwrap().lock("sig-b", &[3]);
+    let url = serve(chains.clone());
+
+    let backend = StubBackend::default();
+    let merkle_proofs = backend.merkle_proofs.clone();
+    let relayer = relayer(&url, RelayerStore::open_in_memory().unwrap(), backend);
+
+    assert_eq!(relayer.sync().unwrap(), 3);
+    assert_eq!(relayer.relay_pending().unwrap(), 3);
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1, 2, 3]);
+
+    let store = relayer.store();
+    for order_id in 1..=3 {
+        let order = store.get(order_id).unwrap().unwrap();
+        assert_eq!(order.status, RelayStatus::Confirmed);
+        assert_eq!(order.order.token, MINT.to_bytes());
+        assert!(store.proof(order_id).unwrap().is_some());
+        assert_eq!(order.order.amount, 1_000_000 * order_id);
+        assert!(order.proof_hash.is_some_and(|hash| hash != [0; 32]));
+    }
+    // Every order was proven against the same order tree.
+    let roots: BTreeSet<_> = merkle_proofs
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|p| p.root)
+        .collect();
+    assert_eq!(roots.len(), 1);
+
+    // Only the new transaction is read on the next poll.
+    chains.lock().unwrap().lock("sig-c", &[4]);
+    let reads = chains.lock().unwrap().transaction_reads;
+    assert_eq!(relayer.sync().unwrap(), 1);
+    assert_eq!(chains.lock().unwrap().transaction_reads, reads + 1);
+    assert_eq!(relayer.relay_pending().unwrap(), 1);
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1, 2, 3, 4]);
+    assert!(store.unfinished().unwrap().is_empty());
+
+    let metrics = relayer.metrics().encode();
+    for stage in ["observed", "proved", "submitted", "confirmed"] {
+        assert!(
+            metrics.contains(&format!("bridge_orders_total{{stage=\"{stage}\"}} 4")),
+            "{metrics}"
+        );
+    }
+    assert!(metrics.contains("bridge_proof_duration_seconds_count{mode=\"core\"} 4"));
+    assert!(metrics.contains("bridge_queue_depth 1"));
+}
+
+#[test]
+fn test_resumes_failed_submission_after_restart() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1]);
+    chains.lock().unwrap().reverting.insert(1);
+    let url = serve(chains.clone());
+
+    let path = temp_database("revert");
+
+    let first = relayer(
+        &url,
+        RelayerStore::open(&path).unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(first.sync().unwrap(), 1);
+    assert_eq!(first.relay_pending().unwrap(), 0);
+    // The proof is kept, only the submission is retried.
+    let order = first.store().get(1).unwrap().unwrap();
+    assert_eq!(order.status, RelayStatus::Proved);
+    assert_eq!(order.attempts, 1);
+    let error = order.error.unwrap();
+    assert!(error.contains("reverted"), "{error}");
+    assert!(first
+        .metrics()
+        .encode()
+        .contains("bridge_submission_failures_total{reason=\"reverted\"} 1"));
+    drop(first);
+
+    // The restarted relayer keeps its place in the history and retries the pending order.
+    chains.lock().unwrap().reverting.clear();
+    let reads = chains.lock().unwrap().transaction_reads;
+    let second = relayer(
+        &url,
+        RelayerStore::open(&path).unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(second.sync().unwrap(), 0);
+    assert_eq!(chains.lock().unwrap().transaction_reads, reads);
+    assert_eq!(second.relay_pending().unwrap(), 1);
+    assert_eq!(
+        second.store().get(1).unwrap().unwrap().status,
+        RelayStatus::Confirmed
+    );
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1, 1]);
+
+    drop(second);
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Kill the relayer at `kill` and restart it on the same database, with unlocks final after
+/// `confirmations` blocks. The kill is simulated in process, by a panic at the kill point.
+fn kill_and_resume(kill: KillPoint, confirmations: u64, path: &Path) -> (RelayStatus, Vec<u64>) {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1]);
+    let url = serve(chains.clone());
+
+    let killed = relayer_between(
+        Box::new(SolanaRpc::new(&url, PROGRAM_ID, "confirmed")),
+        Box::new(CrashingEvm {
+            inner: EvmRpc::new(&url, BRIDGE, None)
+                .unwrap()
+                .with_confirmations(confirmations),
+            kill,
+        }),
+        RelayerStore::open(path).unwrap(),
+        StubBackend {
+            crash: kill == KillPoint::WhileProving,
+            ..Default::default()
+        },
+    );
+    assert_eq!(killed.sync().unwrap(), 1);
+    assert!(catch_unwind(AssertUnwindSafe(|| killed.relay_pending())).is_err());
+    let status = killed.store().get(1).unwrap().unwrap().status;
+    drop(killed);
+
+    let resumed = relayer_between(
+        Box::new(SolanaRpc::new(&url, PROGRAM_ID, "confirmed")),
+        Box::new(
+            EvmRpc::new(&url, BRIDGE, None)
+                .unwrap()
+                .with_confirmations(confirmations),
+        ),
+        RelayerStore::open(path).unwrap(),
+        StubBackend::default(),
+    );
+    if confirmations > 1 {
+        // The unlock sent before the crash is not final yet; the order waits for it.
+        assert_eq!(resumed.relay_pending().unwrap(), 0);
+        assert_eq!(resumed.store().get(1).unwrap().unwrap().status, status);
+        chains.lock().unwrap().block_number += confirmations - 1;
+    }
+    assert_eq!(resumed.relay_pending().unwrap(), 1);
+    let order = resumed.store().get(1).unwrap().unwrap();
+    assert_eq!(order.status, RelayStatus::Confirmed);
+    assert_eq!(order.attempts, 0);
+
+    let unlocks = chains.lock().unwrap().unlocks.clone();
+    (status, unlocks)
+}
+
+#[test]
+fn test_resumes_after_kill_at_every_step() {
+    let expected = [
+        (KillPoint::WhileProving, 1, RelayStatus::Proving),
+        (KillPoint::BeforeSend, 1, RelayStatus::Proved),
+        (KillPoint::AfterSend, 1, RelayStatus::Proved),
+        (KillPoint::AfterSend, 3, RelayStatus::Proved),
+        (KillPoint::WhileConfirming, 1, RelayStatus::Submitted),
+    ];
+    for (kill, confirmations, status) in expected {
+        let path = temp_database(&format!("{kill:?}-{confirmations}"));
+        let (killed_in, unlocks) = kill_and_resume(kill, confirmations, &path);
+        assert_eq!(killed_in, status, "killed at {kill:?}");
+        // The order is never submitted twice, even when the crash followed the submission.
+        assert_eq!(unlocks, vec![1], "killed at {kill:?}");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
+/// A `relayer` process on the stand-in nodes, logging to `relayer.log`, killed when dropped.
+struct RelayerProcess {
+    child: Child,
+    log: PathBuf,
+}
+
+impl RelayerProcess {
+    fn spawn(url: &str, dir: &Path) -> Self {
+        let config = dir.join("bridge.toml");
+        std::fs::write(
+            &config,
+            "[prover]\nbackends = \"mock\"\nvkey_registry = \"vkeys.json\"\n",
+        )
+        .unwrap();
+        let log = dir.join("relayer.log");
+        let child = Command::new(env!("CARGO_BIN_EXE_relayer"))
+            .arg("--config")
+            .arg(&config)
+            .args(["--solana-rpc", url, "--evm-rpc", url])
+            .args(["--program-id", &bs58::encode(PROGRAM_ID).into_string()])
+            .args(["--commitment", "confirmed", "--finality", "confirmed"])
+            .args(["--evm-bridge", &format!("0x{}", hex::encode(BRIDGE))])
+            .args(["--evm-confirmations", "3", "--mode", "core"])
+            .args([
+                "--poll-interval-secs",
+                "1",
+                "--metrics-listen",
+                "127.0.0.1:0",
+            ])
+            .arg("--allow-unpinned-vkey")
+            .arg("--database")
+            .arg(dir.join("relayer.sqlite"))
+            .env_remove("BRIDGE_CONFIG")
+            .env_remove("BRIDGE_PROVER_BACKENDS")
+            .stdout(Stdio::null())
+            .stderr(std::fs::File::create(&log).unwrap())
+            .spawn()
+            .unwrap();
+        Self { child, log }
+    }
+
+    /// Poll `done` until it holds, failing if the process exits first. The first run also sets
+    /// up the guest keys.
+    fn wait_for(&mut self, what: &str, mut done: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(600);
+        while !done() {
+            if let Some(status) = self.child.try_wait().unwrap() {
+                let log = std::fs::read_to_string(&self.log).unwrap_or_default();
+                panic!("relayer exited with {status} before {what}:\n{log}");
+            }
+            assert!(Instant::now() < deadline, "timed out waiting for {what}");
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Kill the process with `SIGKILL`, leaving no chance to clean up.
+    fn kill(mut self) {
+        self.child.kill().unwrap();
+        self.child.wait().unwrap();
+    }
+}
+
+impl Drop for RelayerProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_resumes_after_the_process_is_killed() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1]);
+    let url = serve(chains.clone());
+    let dir = std::env::temp_dir().join(format!("relayer-process-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let database = dir.join("relayer.sqlite");
+    let status = || {
+        RelayerStore::open(&database)
+            .and_then(|store| store.get(1))
+            .ok()
+            .flatten()
+            .map(|order| order.status)
+    };
+
+    // Killed once the unlock is sent, before it is final and possibly before it is recorded.
+    let mut killed = RelayerProcess::spawn(&url, &dir);
+    killed.wait_for("the unlock", || !chains.lock().unwrap().unlocks.is_empty());
+    killed.kill();
+    assert_ne!(status(), Some(RelayStatus::Confirmed));
+
+    let mut resumed = RelayerProcess::spawn(&url, &dir);
+    chains.lock().unwrap().block_number += 2;
+    resumed.wait_for("the confirmation", || {
+        status() == Some(RelayStatus::Confirmed)
+    });
+    resumed.kill();
+
+    // The restarted process found the unlock on chain instead of sending it again.
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_orders_wait_for_finality_and_reorgs_retract_them() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    let url = serve(chains.clone());
+    let solana = ScriptedSolana::default();
+    solana.0.lock().unwrap().lock(10, &[(1, 100)]);
+    solana.0.lock().unwrap().lock(11, &[(2, 200)]);
+    solana.0.lock().unwrap().finalized_slot = 10;
+
+    let relayer = relayer_between(
+        Box::new(solana.clone()),
+        Box::new(EvmRpc::new(&url, BRIDGE, None).unwrap()),
+        RelayerStore::open_in_memory().unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(relayer.sync().unwrap(), 2);
+    // Only the order in the final slot is relayed.
+    assert_eq!(relayer.relay_pending().unwrap(), 1);
+    let store = relayer.store();
+    assert!(store.get(1).unwrap().unwrap().finalized);
+    let pending = store.get(2).unwrap().unwrap();
+    assert_eq!(
+        (pending.status, pending.finalized),
+        (RelayStatus::Observed, false)
+    );
+
+    // Slot 11 is replaced by a block locking order 3; order 2 lands again, for another amount.
+    {
+        let mut fork = solana.0.lock().unwrap();
+        fork.reorg(11);
+        fork.lock(11, &[(3, 300)]);
+        fork.lock(12, &[(2, 250)]);
+    }
+    assert_eq!(relayer.sync().unwrap(), 1);
+    let retracted = store.get(2).unwrap().unwrap();
+    assert_eq!(retracted.status, RelayStatus::Retracted);
+    assert!(retracted.error.unwrap().contains("fork1-11"));
+
+    // The next poll reads order 2 from the new fork.
+    solana.0.lock().unwrap().finalized_slot = 12;
+    assert_eq!(relayer.sync().unwrap(), 1);
+    assert_eq!(relayer.relay_pending().unwrap(), 2);
+    let order = store.get(2).unwrap().unwrap();
+    assert_eq!(order.status, RelayStatus::Confirmed);
+    assert_eq!((order.slot, order.order.amount), (12, 250));
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1, 2, 3]);
+    assert_eq!(store.leaves().unwrap().len(), 3);
+}
+
+#[test]
+fn test_orders_in_skipped_slots_are_retracted() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    let url = serve(chains.clone());
+    let solana = ScriptedSolana::default();
+    solana.0.lock().unwrap().lock(10, &[(1, 100)]);
+
+    let relayer = relayer_between(
+        Box::new(solana.clone()),
+        Box::new(EvmRpc::new(&url, BRIDGE, None).unwrap()),
+        RelayerStore::open_in_memory().unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(relayer.sync().unwrap(), 1);
+    solana.0.lock().unwrap().reorg(10);
+    assert_eq!(relayer.check_finality().unwrap(), (0, 1));
+    assert_eq!(relayer.store().cursor().unwrap(), None);
+    assert_eq!(relayer.relay_pending().unwrap(), 0);
+    assert!(chains.lock().unwrap().unlocks.is_empty());
+}
+
+#[test]
+fn test_unlock_waits_for_confirmations() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1]);
+    let url = serve(chains.clone());
+
+    let relayer = relayer_between(
+        Box::new(SolanaRpc::new(&url, PROGRAM_ID, "confirmed")),
+        Box::new(
+            EvmRpc::new(&url, BRIDGE, None)
+                .unwrap()
+                .with_confirmations(3),
+        ),
+        RelayerStore::open_in_memory().unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(relayer.sync().unwrap(), 1);
+    assert_eq!(relayer.relay_pending().unwrap(), 0);
+    assert_eq!(
+        relayer.store().get(1).unwrap().unwrap().status,
+        RelayStatus::Submitted
+    );
+
+    // Two more blocks on top of the one holding the unlock.
+    chains.lock().unwrap().block_number += 2;
+    assert_eq!(relayer.relay_pending().unwrap(), 1);
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1]);
+}
+
+#[test]
+fn test_defers_orders_claimed_by_another_relayer() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1, 2]);
+    let url = serve(chains.clone());
+
+    // A competing relayer claimed order 2 before this one started.
+    let competitor = [0xcc; 20];
+    chains
+        .lock()
+        .unwrap()
+        .send(competitor, &claimOrderCall { orderId: 2 }.abi_encode());
+
+    let relayer = relayer(
+        &url,
+        RelayerStore::open_in_memory().unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(relayer.sync().unwrap(), 2);
+    assert_eq!(relayer.relay_pending().unwrap(), 1);
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1]);
+
+    let store = relayer.store();
+    assert!(matches!(
+        store.get(1).unwrap().unwrap().claim,
+        Some(Claim::Ours { .. })
+    ));
+    // Order 2 is not even proven while the competitor's claim is active.
+    let deferred = store.get(2).unwrap().unwrap();
+    assert_eq!(deferred.status, RelayStatus::Observed);
+    assert_eq!(
+        deferred.claim,
+        Some(Claim::Theirs {
+            relayer: competitor,
+            expiry: 1 + CLAIM_WINDOW
+        })
+    );
+    assert_eq!(deferred.attempts, 0);
+
+    // The competitor lets its claim lapse; the order is claimed and relayed.
+    chains.lock().unwrap().block_number += CLAIM_WINDOW;
+    assert_eq!(relayer.relay_pending().unwrap(), 1);
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1, 2]);
+    assert_eq!(chains.lock().unwrap().claims[&2].0, RELAYER);
+}
+
+#[test]
+fn test_unlocks_after_its_own_claim_lapsed() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1]);
+    let url = serve(chains.clone());
+
+    // This relayer claimed the order in an earlier run and let the claim lapse.
+    chains
+        .lock()
+        .unwrap()
+        .send(RELAYER, &claimOrderCall { orderId: 1 }.abi_encode());
+    chains.lock().unwrap().block_number += CLAIM_WINDOW;
+
+    let relayer = relayer(
+        &url,
+        RelayerStore::open_in_memory().unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(relayer.sync().unwrap(), 1);
+    // The claim is not sent again, which would revert, and the order is still unlocked.
+    assert_eq!(relayer.relay_pending().unwrap(), 1);
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1]);
+    assert_eq!(chains.lock().unwrap().receipts.len(), 2);
+    assert_eq!(
+        relayer.store().get(1).unwrap().unwrap().claim,
+        Some(Claim::Ours {
+            expiry: 1 + CLAIM_WINDOW
+        })
+    );
+}
+
+#[test]
+fn test_waits_for_the_claim_to_be_mined() {
+    let chains = Arc::new(Mutex::new(Chains::default()));
+    chains.lock().unwrap().lock("sig-a", &[1, 2]);
+    let url = serve(chains.clone());
+
+    // Order 1 is claimed normally, but its claim is only mined after a few polls.
+    chains.lock().unwrap().pending_receipt_reads = 2;
+    // A competitor's claim on order 2 is mined first, so this relayer's claim reverts.
+    let competitor = [0xcc; 20];
+    chains.lock().unwrap().front_run.insert(2, competitor);
+
+    let relayer = relayer(
+        &url,
+        RelayerStore::open_in_memory().unwrap(),
+        StubBackend::default(),
+    );
+    assert_eq!(relayer.sync().unwrap(), 2);
+    assert_eq!(relayer.relay_pending().unwrap(), 1);
+    assert_eq!(chains.lock().unwrap().unlocks, vec![1]);
+
+    let store = relayer.store();
+    assert!(matches!(
+        store.get(1).unwrap().unwrap().claim,
+        Some(Claim::Ours { .. })
+    ));
+    // The reverted claim is recorded as the competitor's and the order is not proven.
+    let deferred = store.get(2).unwrap().unwrap();
+    assert_eq!(deferred.status, RelayStatus::Observed);
+    assert!(matches!(
+        deferred.claim,
+        Some(Claim::Theirs { relayer, .. }) if relayer == competitor
+    ));
+    assert_eq!(deferred.attempts, 0);
+}
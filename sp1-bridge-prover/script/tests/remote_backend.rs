@@ -0,0 +1,252 @@
+//! Drives the remote proving backend against a local stand-in prover service.
+//!
+//! The stand-in speaks just enough HTTP/1.1 to serve scripted responses, so the tests cover
+//! submission, polling, retries, timeouts and fallback without an SP1 prover.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use fibonacci_script::{
+    backend::{
+        FallbackBackend, JobStatus, LocalBackend, Program, ProofBackend, RemoteBackend, RemoteJob,
+        RemoteJobRequest, BRIDGE_PROGRAM,
+    },
+    ProofMode,
+};
+use sp1_sdk::{
+    ExecutionReport, SP1Proof, SP1ProofWithPublicValues, SP1ProvingKey, SP1PublicValues, SP1Stdin,
+    SP1VerifyingKey,
+};
+
+/// A request received by the stand-in service.
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+/// Serve `handler` on a random local port until the test process exits and return its URL.
+fn serve<F>(handler: F) -> String
+where
+    F: Fn(&Request) -> (u16, String) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let (status, body) = handler(&Request {
+                method,
+                path,
+                body: String::from_utf8(body).unwrap(),
+            });
+            write!(
+                stream,
+                "HTTP/1.1 {status} Stand-in\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+fn fake_proof(tag: u8) -> SP1ProofWithPublicValues {
+    SP1ProofWithPublicValues {
+        proof: SP1Proof::Core(vec![]),
+        public_values: SP1PublicValues::from(&[tag; 4]),
+        sp1_version: "v5.0.0".to_string(),
+        tee_proof: None,
+    }
+}
+
+fn job(status: JobStatus, error: Option<&str>, proof: Option<SP1ProofWithPublicValues>) -> String {
+    serde_json::to_string(&RemoteJob {
+        id: "job-1".to_string(),
+        status,
+        error: error.map(str::to_string),
+        proof,
+    })
+    .unwrap()
+}
+
+fn remote(url: &str, timeout: Duration) -> Box<dyn ProofBackend> {
+    Box::new(
+        RemoteBackend::new(url, timeout, LocalBackend::mock())
+            .with_poll_interval(Duration::from_millis(20)),
+    )
+}
+
+/// A backend that always returns the same proof and counts its calls.
+struct StubBackend {
+    proof: SP1ProofWithPublicValues,
+    calls: Arc<AtomicUsize>,
+}
+
+impl ProofBackend for StubBackend {
+    fn name(&self) -> &str {
+        "stub"
+    }
+
+    fn keys(&self, _: &Program) -> Arc<(SP1ProvingKey, SP1VerifyingKey)> {
+        unimplemented!("the stub backend has no keys")
+    }
+
+    fn execute(
+        &self,
+        _: &Program,
+        _: &SP1Stdin,
+    ) -> anyhow::Result<(SP1PublicValues, ExecutionReport)> {
+        unimplemented!("the stub backend does not execute")
+    }
+
+    fn prove(
+        &self,
+        _: &Program,
+        _: &SP1Stdin,
+        _: ProofMode,
+    ) -> anyhow::Result<SP1ProofWithPublicValues> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.proof.clone())
+    }
+
+    fn verify(&self, _: &SP1ProofWithPublicValues, _: &SP1VerifyingKey) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_remote_proof_after_transient_submit_failure() {
+    let submissions = Arc::new(AtomicUsize::new(0));
+    let polls = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(Mutex::new(None));
+    let url = {
+        let (submissions, polls, received) = (submissions.clone(), polls.clone(), received.clone());
+        serve(
+            move |request| match (request.method.as_str(), request.path.as_str()) {
+                ("POST", "/jobs") => {
+                    if submissions.fetch_add(1, Ordering::SeqCst) == 0 {
+                        return (503, "{}".to_string());
+                    }
+                    let job: RemoteJobRequest = serde_json::from_str(&request.body).unwrap();
+                    *received.lock().unwrap() = Some((job.program, job.mode));
+                    (200, r#"{"id":"job-1"}"#.to_string())
+                }
+                ("GET", "/jobs/job-1") => match polls.fetch_add(1, Ordering::SeqCst) {
+                    0 => (200, job(JobStatus::Proving, None, None)),
+                    _ => (200, job(JobStatus::Done, None, Some(fake_proof(7)))),
+                },
+                _ => (404, "{}".to_string()),
+            },
+        )
+    };
+
+    let backend = FallbackBackend::new(
+        vec![remote(&url, Duration::from_secs(10))],
+        2,
+        Duration::ZERO,
+    );
+    let proof = backend
+        .prove(&BRIDGE_PROGRAM, &SP1Stdin::new(), ProofMode::Compressed)
+        .unwrap();
+
+    assert_eq!(proof.public_values.as_slice(), &[7; 4]);
+    assert_eq!(submissions.load(Ordering::SeqCst), 2);
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        received.lock().unwrap().take(),
+        Some(("bridge".to_string(), ProofMode::Compressed))
+    );
+}
+
+#[test]
+fn test_remote_timeout_falls_back_to_next_backend() {
+    let url = serve(|request| match request.method.as_str() {
+        "POST" => (200, r#"{"id":"job-1"}"#.to_string()),
+        _ => (200, job(JobStatus::Proving, None, None)),
+    });
+    let calls = Arc::new(AtomicUsize::new(0));
+    let stub = StubBackend {
+        proof: fake_proof(9),
+        calls: calls.clone(),
+    };
+
+    let backend = FallbackBackend::new(
+        vec![remote(&url, Duration::from_millis(100)), Box::new(stub)],
+        1,
+        Duration::ZERO,
+    );
+    let proof = backend
+        .prove(&BRIDGE_PROGRAM, &SP1Stdin::new(), ProofMode::Core)
+        .unwrap();
+
+    assert_eq!(proof.public_values.as_slice(), &[9; 4]);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_failed_remote_job_is_not_retried() {
+    let submissions = Arc::new(AtomicUsize::new(0));
+    let url = {
+        let submissions = submissions.clone();
+        serve(move |request| match request.method.as_str() {
+            "POST" => {
+                submissions.fetch_add(1, Ordering::SeqCst);
+                (200, r#"{"id":"job-1"}"#.to_string())
+            }
+            _ => (
+                200,
+                job(
+                    JobStatus::Failed,
+                    Some("guest panicked: Order hash mismatch"),
+                    None,
+                ),
+            ),
+        })
+    };
+
+    let backend = FallbackBackend::new(
+        vec![remote(&url, Duration::from_secs(10))],
+        3,
+        Duration::ZERO,
+    );
+    let error = backend
+        .prove(&BRIDGE_PROGRAM, &SP1Stdin::new(), ProofMode::Core)
+        .unwrap_err();
+
+    assert_eq!(submissions.load(Ordering::SeqCst), 1);
+    assert!(format!("{error:#}").contains("Order hash mismatch"));
+}
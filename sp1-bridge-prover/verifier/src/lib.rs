@@ -0,0 +1,170 @@
+//! SP1 Groth16 / Plonk 证明验证器
+//!
+//! 只用 BN254 配对运算检查链上格式的证明字节（`SP1ProofWithPublicValues::bytes()`），
+//! 不依赖 `sp1-sdk` 和 `ProverClient`，可在 `no_std` 环境中编译。Anchor 程序、链下监听器
+//! 以及 relayer 提交前的检查都可以复用同一套验证逻辑。
+//!
+//! 证明字节的前 4 个字节是 SP1 Groth16 / Plonk 电路验证密钥的 SHA-256 前缀，
+//! 据此即可区分证明系统。
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{format, string::String};
+use core::fmt;
+
+use sha2::{Digest, Sha256};
+use sp1_verifier::{
+    Groth16Error, Groth16Verifier, PlonkError, PlonkVerifier, GROTH16_VK_BYTES, PLONK_VK_BYTES,
+};
+
+pub mod solana;
+
+/// 证明字节中电路验证密钥哈希前缀的长度
+pub const VK_PREFIX_LEN: usize = 4;
+
+/// 链上可验证的证明系统
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProofSystem {
+    Groth16,
+    Plonk,
+}
+
+impl ProofSystem {
+    /// 根据证明字节的前缀判断证明系统，前缀不属于当前 SP1 版本时返回 `None`
+    pub fn detect(proof: &[u8]) -> Option<Self> {
+        let prefix = proof.get(..VK_PREFIX_LEN)?;
+        [ProofSystem::Groth16, ProofSystem::Plonk]
+            .into_iter()
+            .find(|system| prefix == system.prefix())
+    }
+
+    /// 该证明系统的证明字节前缀
+    pub fn prefix(self) -> [u8; VK_PREFIX_LEN] {
+        match self {
+            ProofSystem::Groth16 => vk_prefix(&GROTH16_VK_BYTES),
+            ProofSystem::Plonk => vk_prefix(&PLONK_VK_BYTES),
+        }
+    }
+}
+
+fn vk_prefix(vk: &[u8]) -> [u8; VK_PREFIX_LEN] {
+    let digest = Sha256::digest(vk);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+/// 验证失败的原因
+#[derive(Debug)]
+pub enum VerifyError {
+    /// 证明前缀既不是当前 SP1 版本的 Groth16 也不是 Plonk 电路
+    UnknownProofSystem,
+    /// 该操作不支持此证明系统
+    UnsupportedProofSystem(ProofSystem),
+    /// 证明字节长度不对
+    InvalidLength(usize),
+    Groth16(Groth16Error),
+    Plonk(PlonkError),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::UnknownProofSystem => {
+                write!(
+                    f,
+                    "proof was not generated by a known SP1 Groth16 or Plonk circuit"
+                )
+            }
+            VerifyError::UnsupportedProofSystem(system) => {
+                write!(f, "{system:?} proofs are not supported here")
+            }
+            VerifyError::InvalidLength(len) => write!(f, "unexpected proof length: {len} bytes"),
+            VerifyError::Groth16(e) => write!(f, "invalid Groth16 proof: {e}"),
+            VerifyError::Plonk(e) => write!(f, "invalid Plonk proof: {e}"),
+        }
+    }
+}
+
+/// 程序 vkey 的 `bytes32` 哈希转为验证器接受的十六进制字符串
+fn vkey_hex(vkey_hash: &[u8; 32]) -> String {
+    format!("0x{}", hex::encode(vkey_hash))
+}
+
+/// 验证 Groth16 证明
+///
+/// - `proof`: 带 4 字节前缀的证明字节
+/// - `public_values`: guest 提交的完整公开值
+/// - `vkey_hash`: 程序 vkey 的 `bytes32` 哈希
+pub fn verify_groth16(
+    proof: &[u8],
+    public_values: &[u8],
+    vkey_hash: &[u8; 32],
+) -> Result<(), VerifyError> {
+    Groth16Verifier::verify(
+        proof,
+        public_values,
+        &vkey_hex(vkey_hash),
+        &GROTH16_VK_BYTES,
+    )
+    .map_err(VerifyError::Groth16)
+}
+
+/// 验证 Plonk 证明，参数同 [`verify_groth16`]
+pub fn verify_plonk(
+    proof: &[u8],
+    public_values: &[u8],
+    vkey_hash: &[u8; 32],
+) -> Result<(), VerifyError> {
+    PlonkVerifier::verify(proof, public_values, &vkey_hex(vkey_hash), &PLONK_VK_BYTES)
+        .map_err(VerifyError::Plonk)
+}
+
+/// 按前缀识别证明系统并验证，返回证明系统
+pub fn verify_proof(
+    proof: &[u8],
+    public_values: &[u8],
+    vkey_hash: &[u8; 32],
+) -> Result<ProofSystem, VerifyError> {
+    let system = ProofSystem::detect(proof).ok_or(VerifyError::UnknownProofSystem)?;
+    match system {
+        ProofSystem::Groth16 => verify_groth16(proof, public_values, vkey_hash)?,
+        ProofSystem::Plonk => verify_plonk(proof, public_values, vkey_hash)?,
+    }
+    Ok(system)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_detects_proof_system_from_prefix() {
+        let mut proof = vec![0u8; 260];
+        proof[..VK_PREFIX_LEN].copy_from_slice(&vk_prefix(&GROTH16_VK_BYTES));
+        assert_eq!(ProofSystem::detect(&proof), Some(ProofSystem::Groth16));
+
+        proof[..VK_PREFIX_LEN].copy_from_slice(&vk_prefix(&PLONK_VK_BYTES));
+        assert_eq!(ProofSystem::detect(&proof), Some(ProofSystem::Plonk));
+
+        assert_eq!(ProofSystem::detect(&[0u8; 260]), None);
+        assert_eq!(ProofSystem::detect(&[0u8; 2]), None);
+    }
+
+    #[test]
+    fn test_rejects_invalid_proofs() {
+        assert!(matches!(
+            verify_proof(&[0u8; 260], &[], &[0; 32]),
+            Err(VerifyError::UnknownProofSystem)
+        ));
+
+        // 前缀正确但证明点无效
+        let mut proof = vec![0u8; 4 + 256];
+        proof[..VK_PREFIX_LEN].copy_from_slice(&vk_prefix(&GROTH16_VK_BYTES));
+        assert!(matches!(
+            verify_proof(&proof, &[1, 2, 3], &[0; 32]),
+            Err(VerifyError::Groth16(_))
+        ));
+    }
+}
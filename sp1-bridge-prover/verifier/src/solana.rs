@@ -0,0 +1,227 @@
+//! Groth16 证明转为 Solana `alt_bn128` 系统调用使用的字节布局
+//!
+//! Solana 的配对系统调用与以太坊预编译（EIP-197）相同：点坐标为大端 32 字节，G2 坐标按
+//! (虚部, 实部) 排列。SP1 输出的 Groth16 证明本身就是这种布局（为 Solidity 验证器准备），
+//! 但 Solana 上的 Groth16 验证器检查的是 `e(-A, B) · e(α, β) · e(L, γ) · e(C, δ) = 1`，
+//! 所以 A 需要预先取负，省去链上的一次域运算。
+//!
+//! 公开输入有两个：程序 vkey 的 `bytes32` 哈希，以及公开值 SHA-256 摘要截断到 253 位的结果。
+
+use alloc::vec::Vec;
+
+use crate::{ProofSystem, VerifyError, VK_PREFIX_LEN};
+
+/// 去掉前缀后的 Groth16 证明长度：A (64) + B (128) + C (64)
+pub const GROTH16_PROOF_LEN: usize = 256;
+
+/// BN254 基域模数 q（大端）
+pub const BN254_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// G1 点取负：(x, y) -> (x, q - y)，无穷远点 (0, 0) 保持不变
+pub fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+    let mut negated = *point;
+    let y = &point[32..];
+    if y.iter().all(|b| *b == 0) {
+        return negated;
+    }
+
+    // 大端逐字节带借位减法
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut diff = BN254_FIELD_MODULUS[i] as i16 - y[i] as i16 - borrow;
+        borrow = i16::from(diff < 0);
+        if diff < 0 {
+            diff += 256;
+        }
+        negated[32 + i] = diff as u8;
+    }
+    negated
+}
+
+/// Solana Groth16 验证器使用的证明点
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolanaGroth16Proof {
+    /// 取负后的 A
+    pub proof_a: [u8; 64],
+    pub proof_b: [u8; 128],
+    pub proof_c: [u8; 64],
+}
+
+impl SolanaGroth16Proof {
+    /// 从 SP1 Groth16 证明字节（带 4 字节电路前缀）转换
+    pub fn from_sp1_bytes(proof: &[u8]) -> Result<Self, VerifyError> {
+        match ProofSystem::detect(proof) {
+            Some(ProofSystem::Groth16) => {}
+            Some(system) => return Err(VerifyError::UnsupportedProofSystem(system)),
+            None => return Err(VerifyError::UnknownProofSystem),
+        }
+        let points = &proof[VK_PREFIX_LEN..];
+        if points.len() != GROTH16_PROOF_LEN {
+            return Err(VerifyError::InvalidLength(proof.len()));
+        }
+
+        let mut bytes = [0u8; GROTH16_PROOF_LEN];
+        bytes.copy_from_slice(points);
+        let mut proof = Self::from_bytes(&bytes);
+        proof.proof_a = negate_g1(&proof.proof_a);
+        Ok(proof)
+    }
+
+    /// 还原为 SP1 证明字节，`prefix` 为原证明的 4 字节电路前缀
+    pub fn to_sp1_bytes(&self, prefix: [u8; VK_PREFIX_LEN]) -> Vec<u8> {
+        let original = Self {
+            proof_a: negate_g1(&self.proof_a),
+            ..self.clone()
+        };
+        [&prefix[..], &original.to_bytes()[..]].concat()
+    }
+
+    /// 按 A || B || C 顺序拼接，作为指令数据
+    pub fn to_bytes(&self) -> [u8; GROTH16_PROOF_LEN] {
+        let mut bytes = [0u8; GROTH16_PROOF_LEN];
+        bytes[..64].copy_from_slice(&self.proof_a);
+        bytes[64..192].copy_from_slice(&self.proof_b);
+        bytes[192..].copy_from_slice(&self.proof_c);
+        bytes
+    }
+
+    /// 从 [`Self::to_bytes`] 的布局解析，不做任何转换
+    pub fn from_bytes(bytes: &[u8; GROTH16_PROOF_LEN]) -> Self {
+        let mut proof = Self {
+            proof_a: [0; 64],
+            proof_b: [0; 128],
+            proof_c: [0; 64],
+        };
+        proof.proof_a.copy_from_slice(&bytes[..64]);
+        proof.proof_b.copy_from_slice(&bytes[64..192]);
+        proof.proof_c.copy_from_slice(&bytes[192..]);
+        proof
+    }
+}
+
+/// Groth16 电路的两个公开输入：vkey 哈希与公开值摘要
+pub fn public_inputs(vkey_hash: &[u8; 32], public_values: &[u8]) -> [[u8; 32]; 2] {
+    [*vkey_hash, sp1_verifier::hash_public_inputs(public_values)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bn::{pairing_batch, AffineG1, AffineG2, Fq, Fq2, Fr, Group, Gt, G1, G2};
+
+    fn fq_bytes(value: Fq) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn g1_bytes(point: G1) -> [u8; 64] {
+        let point = AffineG1::from_jacobian(point).unwrap();
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&fq_bytes(point.x()));
+        bytes[32..].copy_from_slice(&fq_bytes(point.y()));
+        bytes
+    }
+
+    fn g2_bytes(point: G2) -> [u8; 128] {
+        let point = AffineG2::from_jacobian(point).unwrap();
+        let mut bytes = [0u8; 128];
+        bytes[..32].copy_from_slice(&fq_bytes(point.x().imaginary()));
+        bytes[32..64].copy_from_slice(&fq_bytes(point.x().real()));
+        bytes[64..96].copy_from_slice(&fq_bytes(point.y().imaginary()));
+        bytes[96..].copy_from_slice(&fq_bytes(point.y().real()));
+        bytes
+    }
+
+    /// 按 EIP-197 解码，与 `alt_bn128_pairing` 系统调用一致
+    fn decode_g1(bytes: &[u8]) -> G1 {
+        let x = Fq::from_slice(&bytes[..32]).unwrap();
+        let y = Fq::from_slice(&bytes[32..64]).unwrap();
+        AffineG1::new(x, y).unwrap().into()
+    }
+
+    fn decode_g2(bytes: &[u8]) -> G2 {
+        let fq = |range: core::ops::Range<usize>| Fq::from_slice(&bytes[range]).unwrap();
+        let x = Fq2::new(fq(32..64), fq(0..32));
+        let y = Fq2::new(fq(96..128), fq(64..96));
+        AffineG2::new(x, y).unwrap().into()
+    }
+
+    fn scalar(value: &str) -> Fr {
+        Fr::from_str(value).unwrap()
+    }
+
+    /// 满足 e(A, B) = e(C, G2) 的一组点，按 SP1 的字节布局编码（带 Groth16 前缀）
+    fn sp1_proof() -> Vec<u8> {
+        let (a, b) = (scalar("123456789"), scalar("987654321"));
+        let proof_a = g1_bytes(G1::one() * a);
+        let proof_b = g2_bytes(G2::one() * b);
+        let proof_c = g1_bytes(G1::one() * (a * b));
+
+        let prefix = ProofSystem::Groth16.prefix();
+        [&prefix[..], &proof_a[..], &proof_b[..], &proof_c[..]].concat()
+    }
+
+    #[test]
+    fn test_negate_g1_matches_group_negation() {
+        let point = G1::one() * scalar("42");
+        assert_eq!(negate_g1(&g1_bytes(point)), g1_bytes(-point));
+        assert_eq!(negate_g1(&negate_g1(&g1_bytes(point))), g1_bytes(point));
+        assert_eq!(negate_g1(&[0; 64]), [0; 64]);
+    }
+
+    #[test]
+    fn test_negated_a_satisfies_pairing_check() {
+        let proof = SolanaGroth16Proof::from_sp1_bytes(&sp1_proof()).unwrap();
+
+        // 链上检查形如 e(-A, B) · e(C, G2) = 1
+        let pairs = [
+            (decode_g1(&proof.proof_a), decode_g2(&proof.proof_b)),
+            (decode_g1(&proof.proof_c), G2::one()),
+        ];
+        assert_eq!(pairing_batch(&pairs), Gt::one());
+
+        // 未取负的 A 不满足
+        let pairs = [
+            (-decode_g1(&proof.proof_a), decode_g2(&proof.proof_b)),
+            (decode_g1(&proof.proof_c), G2::one()),
+        ];
+        assert_ne!(pairing_batch(&pairs), Gt::one());
+    }
+
+    #[test]
+    fn test_roundtrip_to_sp1_bytes() {
+        let sp1_proof = sp1_proof();
+        let proof = SolanaGroth16Proof::from_sp1_bytes(&sp1_proof).unwrap();
+        assert_eq!(SolanaGroth16Proof::from_bytes(&proof.to_bytes()), proof);
+
+        let prefix = sp1_proof[..VK_PREFIX_LEN].try_into().unwrap();
+        assert_eq!(proof.to_sp1_bytes(prefix), sp1_proof);
+    }
+
+    #[test]
+    fn test_rejects_other_proofs() {
+        let mut plonk = sp1_proof();
+        plonk[..VK_PREFIX_LEN].copy_from_slice(&ProofSystem::Plonk.prefix());
+        assert!(matches!(
+            SolanaGroth16Proof::from_sp1_bytes(&plonk),
+            Err(VerifyError::UnsupportedProofSystem(ProofSystem::Plonk))
+        ));
+
+        let truncated = &sp1_proof()[..200];
+        assert!(matches!(
+            SolanaGroth16Proof::from_sp1_bytes(truncated),
+            Err(VerifyError::InvalidLength(200))
+        ));
+    }
+
+    #[test]
+    fn test_public_inputs_fit_the_scalar_field() {
+        let [vkey, digest] = public_inputs(&[0x01; 32], b"public values");
+        assert_eq!(vkey, [0x01; 32]);
+        assert!(digest[0] <= 0x1f);
+    }
+}